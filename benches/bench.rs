@@ -0,0 +1,117 @@
+// Copyright (c) 2015 Daniel Grunwald
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this
+// software and associated documentation files (the "Software"), to deal in the Software
+// without restriction, including without limitation the rights to use, copy, modify, merge,
+// publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons
+// to whom the Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED,
+// INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR
+// PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE
+// FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR
+// OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! Micro-benchmarks, run with `cargo bench`.
+//!
+//! This uses a plain `main` instead of the unstable `test::Bencher`,
+//! so that the benchmarks also run on stable Rust.
+
+extern crate cpython;
+
+use std::time::Instant;
+use cpython::{Python, PyConst, ToPyObject, ObjectProtocol, PythonObject, PyList};
+use cpython::_detail::ffi;
+
+/// Runs `f` `iterations` times and prints the average time per call.
+fn bench<F: FnMut()>(name: &str, iterations: u32, mut f: F) {
+    // warm up caches and the allocator
+    for _ in 0..iterations / 10 {
+        f();
+    }
+    let start = Instant::now();
+    for _ in 0..iterations {
+        f();
+    }
+    let elapsed = start.elapsed();
+    let nanos = elapsed.as_secs() * 1_000_000_000 + u64::from(elapsed.subsec_nanos());
+    println!("{:<45} {:>10} ns/iter", name, nanos / u64::from(iterations));
+}
+
+fn bench_conversion(py: Python) {
+    bench("str::to_py_object", 1_000_000, || {
+        "hello world".to_py_object(py);
+    });
+    let greeting = PyConst::new("hello world");
+    bench("PyConst<&str>::to_py_object", 1_000_000, || {
+        greeting.to_py_object(py);
+    });
+}
+
+fn bench_call_method(py: Python) {
+    let list = PyList::new(py, &[]);
+    let obj = list.as_object();
+    bench("call_method0 with &str name", 1_000_000, || {
+        obj.call_method0(py, "__len__").unwrap();
+    });
+    let name = py.intern("__len__");
+    bench("call_method0 with interned name", 1_000_000, || {
+        obj.call_method0(py, &name).unwrap();
+    });
+}
+
+fn bench_list(py: Python) {
+    let v: Vec<bool> = (0..1000).map(|i| i % 3 == 0).collect();
+    bench("Vec<bool>::to_py_object, 1000 items", 100_000, || {
+        v.to_py_object(py);
+    });
+    let v: Vec<bool> = (0..1_000_000).map(|i| i % 3 == 0).collect();
+    bench("Vec<bool>::to_py_object, 1M items", 100, || {
+        v.to_py_object(py);
+    });
+    // baseline: growing the list one element at a time
+    bench("PyList_Append loop, 1M items", 100, || {
+        let list = PyList::new(py, &[]);
+        for b in &v {
+            let item = b.to_py_object(py);
+            let r = unsafe { ffi::PyList_Append(list.as_object().as_ptr(), item.as_object().as_ptr()) };
+            assert_eq!(r, 0);
+        }
+    });
+}
+
+fn bench_int(py: Python) {
+    bench("u64::to_py_object, small value", 1_000_000, || {
+        100u64.to_py_object(py);
+    });
+    bench("u64::to_py_object, large value", 1_000_000, || {
+        (1u64 << 40).to_py_object(py);
+    });
+    let v: Vec<u64> = (0..1000).map(|i| i % 200).collect();
+    bench("Vec<u64>::to_py_object, 1000 small ints", 100_000, || {
+        v.to_py_object(py);
+    });
+    // baseline: the same list, converting through PyLong_FromUnsignedLongLong
+    bench("PyLong_FromUnsignedLongLong, 1000 small ints", 100_000, || {
+        unsafe {
+            let list = ffi::PyList_New(v.len() as ffi::Py_ssize_t);
+            for (i, &n) in v.iter().enumerate() {
+                ffi::PyList_SET_ITEM(list, i as ffi::Py_ssize_t, ffi::PyLong_FromUnsignedLongLong(n));
+            }
+            ffi::Py_DECREF(list);
+        }
+    });
+}
+
+fn main() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    bench_conversion(py);
+    bench_call_method(py);
+    bench_list(py);
+    bench_int(py);
+}
@@ -1,5 +1,6 @@
 use std::env;
 use std::io::Write;
+use std::process::Command;
 
 const CFG_KEY: &'static str = "py_sys_config";
 
@@ -37,4 +38,35 @@ fn main() {
             }
         }
     }
+
+    // `#[export_name = concat!(..)]` (used by the two-argument form of
+    // py_module_initializer!) requires Rust 1.54.
+    if rustc_minor_version().map_or(false, |minor| minor >= 54) {
+        println!("cargo:rustc-cfg=export_name_concat");
+    }
+    println!("cargo:rustc-check-cfg=cfg(export_name_concat)");
+
+    // python3-sys/build.rs also exports the interpreter's minor version;
+    // replicate its Py_3_x cfg flags.
+    #[cfg(feature="python3-sys")]
+    {
+        if let Ok(minor) = env::var("DEP_PYTHON3_PYTHON_MINOR_VERSION") {
+            let minor: u8 = minor.parse().unwrap();
+            for i in 4..(minor+1) {
+                println!("cargo:rustc-cfg=Py_3_{}", i);
+            }
+            // declare all of them to newer compilers' check-cfg lint
+            for i in 4..(minor.max(20)+1) {
+                println!("cargo:rustc-check-cfg=cfg(Py_3_{})", i);
+            }
+        }
+    }
+}
+
+fn rustc_minor_version() -> Option<u32> {
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+    // e.g. "rustc 1.54.0 (a178d0322 2021-07-26)"
+    let version = String::from_utf8(output.stdout).ok()?;
+    version.split(|c| c == ' ' || c == '.').nth(2)?.parse().ok()
 }
@@ -0,0 +1,17 @@
+#![crate_type = "dylib"]
+
+#[macro_use] extern crate cpython;
+
+use cpython::{PyModule, PyResult, Python};
+
+py_module_initializer!(module_init, init);
+
+fn init(py: Python, m: &PyModule) -> PyResult<()> {
+    m.add(py, "__doc__", "Module documentation string")?;
+    m.add(py, "answer", py_fn!(py, answer()))?;
+    Ok(())
+}
+
+fn answer(_: Python) -> PyResult<i32> {
+    Ok(42)
+}
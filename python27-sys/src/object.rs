@@ -666,7 +666,7 @@ pub unsafe fn PyType_CheckExact(op: *mut PyObject) -> c_int {
      -> *mut PyObject;
     pub fn PyType_GenericNew(t: *mut PyTypeObject, args: *mut PyObject,
                              kwds: *mut PyObject) -> *mut PyObject;
-    fn _PyType_Lookup(arg1: *mut PyTypeObject, arg2: *mut PyObject)
+    pub fn _PyType_Lookup(arg1: *mut PyTypeObject, arg2: *mut PyObject)
      -> *mut PyObject;
     fn _PyObject_LookupSpecial(arg1: *mut PyObject,
                                    arg2: *mut c_char,
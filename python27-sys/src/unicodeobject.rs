@@ -567,6 +567,20 @@ pub unsafe fn PyUnicode_FromStringAndSize(u: *const c_char, size: Py_ssize_t) ->
     PyUnicodeUCS2_FromStringAndSize(u, size)
 }
 
+#[inline(always)]
+#[cfg(py_sys_config="Py_UNICODE_SIZE_4")]
+pub unsafe fn PyUnicode_Decode(s: *const c_char, size: Py_ssize_t,
+                               encoding: *const c_char, errors: *const c_char) -> *mut PyObject {
+    PyUnicodeUCS4_Decode(s, size, encoding, errors)
+}
+
+#[inline(always)]
+#[cfg(not(py_sys_config="Py_UNICODE_SIZE_4"))]
+pub unsafe fn PyUnicode_Decode(s: *const c_char, size: Py_ssize_t,
+                               encoding: *const c_char, errors: *const c_char) -> *mut PyObject {
+    PyUnicodeUCS2_Decode(s, size, encoding, errors)
+}
+
 #[inline(always)]
 #[cfg(py_sys_config="Py_UNICODE_SIZE_4")]
 pub unsafe fn PyUnicode_AsUTF8String(u: *mut PyObject) -> *mut PyObject {
@@ -335,6 +335,9 @@ fn configure_from_path(expected_version: &PythonVersion) -> Result<String, Strin
             for i in 4..(minor+1) {
                 println!("cargo:rustc-cfg=Py_3_{}", i);
             }
+            // Export the minor version so that dependent build scripts can
+            // emit the same Py_3_x cfg flags (DEP_PYTHON3_PYTHON_MINOR_VERSION).
+            println!("cargo:python_minor_version={}", minor);
         }
     }
 
@@ -1,6 +1,14 @@
 use libc::{c_double, c_int};
 use object::*;
 
+#[cfg(not(Py_LIMITED_API))]
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct Py_complex {
+    pub real: c_double,
+    pub imag: c_double
+}
+
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
     pub static mut PyComplex_Type: PyTypeObject;
 }
@@ -22,3 +30,8 @@ pub unsafe fn PyComplex_CheckExact(op : *mut PyObject) -> c_int {
     pub fn PyComplex_ImagAsDouble(op: *mut PyObject) -> c_double;
 }
 
+#[cfg(not(Py_LIMITED_API))]
+#[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    pub fn PyComplex_FromCComplex(v: Py_complex) -> *mut PyObject;
+    pub fn PyComplex_AsCComplex(op: *mut PyObject) -> Py_complex;
+}
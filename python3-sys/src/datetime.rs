@@ -0,0 +1,239 @@
+//! Bindings for the `datetime.h` C API.
+//!
+//! Before using any of these functions, `PyDateTime_IMPORT()` must be called
+//! (with the GIL held) to load the `datetime` module's C API capsule.
+use libc::{c_char, c_int, c_uchar};
+use object::*;
+use pyport::Py_hash_t;
+use pycapsule::PyCapsule_Import;
+
+pub const PyDateTime_CAPSULE_NAME: &str = "datetime.datetime_CAPI\0";
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct PyDateTime_CAPI {
+    pub DateType: *mut PyTypeObject,
+    pub DateTimeType: *mut PyTypeObject,
+    pub TimeType: *mut PyTypeObject,
+    pub DeltaType: *mut PyTypeObject,
+    pub TZInfoType: *mut PyTypeObject,
+    #[cfg(Py_3_7)]
+    pub TimeZone_UTC: *mut PyObject,
+
+    pub Date_FromDate: unsafe extern "C" fn(year: c_int, month: c_int, day: c_int,
+                                            cls: *mut PyTypeObject) -> *mut PyObject,
+    pub DateTime_FromDateAndTime: unsafe extern "C" fn(year: c_int, month: c_int, day: c_int,
+                                                       hour: c_int, minute: c_int, second: c_int,
+                                                       usecond: c_int, tzinfo: *mut PyObject,
+                                                       cls: *mut PyTypeObject) -> *mut PyObject,
+    pub Time_FromTime: unsafe extern "C" fn(hour: c_int, minute: c_int, second: c_int,
+                                            usecond: c_int, tzinfo: *mut PyObject,
+                                            cls: *mut PyTypeObject) -> *mut PyObject,
+    pub Delta_FromDelta: unsafe extern "C" fn(days: c_int, seconds: c_int, useconds: c_int,
+                                              normalize: c_int,
+                                              cls: *mut PyTypeObject) -> *mut PyObject,
+    #[cfg(Py_3_7)]
+    pub TimeZone_FromTimeZone: unsafe extern "C" fn(offset: *mut PyObject,
+                                                    name: *mut PyObject) -> *mut PyObject,
+
+    pub DateTime_FromTimestamp: unsafe extern "C" fn(cls: *mut PyObject, args: *mut PyObject,
+                                                     kwargs: *mut PyObject) -> *mut PyObject,
+    pub Date_FromTimestamp: unsafe extern "C" fn(cls: *mut PyObject,
+                                                 args: *mut PyObject) -> *mut PyObject,
+    #[cfg(Py_3_6)]
+    pub DateTime_FromDateAndTimeAndFold: unsafe extern "C" fn(year: c_int, month: c_int, day: c_int,
+                                                              hour: c_int, minute: c_int, second: c_int,
+                                                              usecond: c_int, tzinfo: *mut PyObject,
+                                                              fold: c_int,
+                                                              cls: *mut PyTypeObject) -> *mut PyObject,
+    #[cfg(Py_3_6)]
+    pub Time_FromTimeAndFold: unsafe extern "C" fn(hour: c_int, minute: c_int, second: c_int,
+                                                   usecond: c_int, tzinfo: *mut PyObject,
+                                                   fold: c_int,
+                                                   cls: *mut PyTypeObject) -> *mut PyObject,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct PyDateTime_Delta {
+    pub ob_base: PyObject,
+    pub hashcode: Py_hash_t,
+    pub days: c_int,
+    pub seconds: c_int,
+    pub microseconds: c_int,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct PyDateTime_Date {
+    pub ob_base: PyObject,
+    pub hashcode: Py_hash_t,
+    pub hastzinfo: c_char,
+    pub data: [c_uchar; 4],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct PyDateTime_Time {
+    pub ob_base: PyObject,
+    pub hashcode: Py_hash_t,
+    pub hastzinfo: c_char,
+    pub data: [c_uchar; 6],
+    #[cfg(Py_3_6)]
+    pub fold: c_uchar,
+    /// Only valid if `hastzinfo` is non-zero.
+    pub tzinfo: *mut PyObject,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct PyDateTime_DateTime {
+    pub ob_base: PyObject,
+    pub hashcode: Py_hash_t,
+    pub hastzinfo: c_char,
+    pub data: [c_uchar; 10],
+    #[cfg(Py_3_6)]
+    pub fold: c_uchar,
+    /// Only valid if `hastzinfo` is non-zero.
+    pub tzinfo: *mut PyObject,
+}
+
+/// Pointer to the datetime C API; initialized by `PyDateTime_IMPORT()`.
+pub static mut PyDateTimeAPI: *mut PyDateTime_CAPI = ::core::ptr::null_mut();
+
+/// Loads the datetime C API into `PyDateTimeAPI`.
+///
+/// On failure, `PyDateTimeAPI` remains null and a Python exception is set.
+#[inline]
+pub unsafe fn PyDateTime_IMPORT() {
+    PyDateTimeAPI = PyCapsule_Import(PyDateTime_CAPSULE_NAME.as_ptr() as *const c_char, 1)
+        as *mut PyDateTime_CAPI;
+}
+
+#[inline]
+pub unsafe fn PyDate_Check(op: *mut PyObject) -> c_int {
+    PyObject_TypeCheck(op, (*PyDateTimeAPI).DateType)
+}
+
+#[inline]
+pub unsafe fn PyDate_CheckExact(op: *mut PyObject) -> c_int {
+    (Py_TYPE(op) == (*PyDateTimeAPI).DateType) as c_int
+}
+
+#[inline]
+pub unsafe fn PyDateTime_Check(op: *mut PyObject) -> c_int {
+    PyObject_TypeCheck(op, (*PyDateTimeAPI).DateTimeType)
+}
+
+#[inline]
+pub unsafe fn PyDateTime_CheckExact(op: *mut PyObject) -> c_int {
+    (Py_TYPE(op) == (*PyDateTimeAPI).DateTimeType) as c_int
+}
+
+#[inline]
+pub unsafe fn PyTime_Check(op: *mut PyObject) -> c_int {
+    PyObject_TypeCheck(op, (*PyDateTimeAPI).TimeType)
+}
+
+#[inline]
+pub unsafe fn PyDelta_Check(op: *mut PyObject) -> c_int {
+    PyObject_TypeCheck(op, (*PyDateTimeAPI).DeltaType)
+}
+
+#[inline]
+pub unsafe fn PyTZInfo_Check(op: *mut PyObject) -> c_int {
+    PyObject_TypeCheck(op, (*PyDateTimeAPI).TZInfoType)
+}
+
+// Accessors for date and datetime objects.
+// These must only be used on objects that pass PyDate_Check().
+#[inline]
+pub unsafe fn PyDateTime_GET_YEAR(o: *mut PyObject) -> c_int {
+    let data = (*(o as *mut PyDateTime_Date)).data;
+    ((data[0] as c_int) << 8) | (data[1] as c_int)
+}
+
+#[inline]
+pub unsafe fn PyDateTime_GET_MONTH(o: *mut PyObject) -> c_int {
+    (*(o as *mut PyDateTime_Date)).data[2] as c_int
+}
+
+#[inline]
+pub unsafe fn PyDateTime_GET_DAY(o: *mut PyObject) -> c_int {
+    (*(o as *mut PyDateTime_Date)).data[3] as c_int
+}
+
+// Accessors for datetime objects.
+// These must only be used on objects that pass PyDateTime_Check().
+#[inline]
+pub unsafe fn PyDateTime_DATE_GET_HOUR(o: *mut PyObject) -> c_int {
+    (*(o as *mut PyDateTime_DateTime)).data[4] as c_int
+}
+
+#[inline]
+pub unsafe fn PyDateTime_DATE_GET_MINUTE(o: *mut PyObject) -> c_int {
+    (*(o as *mut PyDateTime_DateTime)).data[5] as c_int
+}
+
+#[inline]
+pub unsafe fn PyDateTime_DATE_GET_SECOND(o: *mut PyObject) -> c_int {
+    (*(o as *mut PyDateTime_DateTime)).data[6] as c_int
+}
+
+#[inline]
+pub unsafe fn PyDateTime_DATE_GET_MICROSECOND(o: *mut PyObject) -> c_int {
+    let data = (*(o as *mut PyDateTime_DateTime)).data;
+    ((data[7] as c_int) << 16) | ((data[8] as c_int) << 8) | (data[9] as c_int)
+}
+
+#[cfg(Py_3_6)]
+#[inline]
+pub unsafe fn PyDateTime_DATE_GET_FOLD(o: *mut PyObject) -> c_int {
+    (*(o as *mut PyDateTime_DateTime)).fold as c_int
+}
+
+/// Returns a borrowed reference to the datetime's tzinfo, or to `None` if it is naive.
+#[inline]
+pub unsafe fn PyDateTime_DATE_GET_TZINFO(o: *mut PyObject) -> *mut PyObject {
+    let dt = o as *mut PyDateTime_DateTime;
+    if (*dt).hastzinfo != 0 { (*dt).tzinfo } else { Py_None() }
+}
+
+// Accessors for timedelta objects.
+// These must only be used on objects that pass PyDelta_Check().
+#[inline]
+pub unsafe fn PyDateTime_DELTA_GET_DAYS(o: *mut PyObject) -> c_int {
+    (*(o as *mut PyDateTime_Delta)).days
+}
+
+#[inline]
+pub unsafe fn PyDateTime_DELTA_GET_SECONDS(o: *mut PyObject) -> c_int {
+    (*(o as *mut PyDateTime_Delta)).seconds
+}
+
+#[inline]
+pub unsafe fn PyDateTime_DELTA_GET_MICROSECONDS(o: *mut PyObject) -> c_int {
+    (*(o as *mut PyDateTime_Delta)).microseconds
+}
+
+/// Equivalent to `datetime.date(year, month, day)`.
+#[inline]
+pub unsafe fn PyDate_FromDate(year: c_int, month: c_int, day: c_int) -> *mut PyObject {
+    ((*PyDateTimeAPI).Date_FromDate)(year, month, day, (*PyDateTimeAPI).DateType)
+}
+
+/// Equivalent to `datetime.datetime(year, month, day, hour, minute, second, usecond)`.
+#[inline]
+pub unsafe fn PyDateTime_FromDateAndTime(year: c_int, month: c_int, day: c_int,
+                                         hour: c_int, minute: c_int, second: c_int,
+                                         usecond: c_int) -> *mut PyObject {
+    ((*PyDateTimeAPI).DateTime_FromDateAndTime)(year, month, day, hour, minute, second, usecond,
+                                                Py_None(), (*PyDateTimeAPI).DateTimeType)
+}
+
+/// Equivalent to `datetime.timedelta(days, seconds, useconds)`.
+#[inline]
+pub unsafe fn PyDelta_FromDSU(days: c_int, seconds: c_int, useconds: c_int) -> *mut PyObject {
+    ((*PyDateTimeAPI).Delta_FromDelta)(days, seconds, useconds, 1, (*PyDateTimeAPI).DeltaType)
+}
+
@@ -1,5 +1,5 @@
 use libc::{c_char, c_int};
-use pyport::Py_ssize_t;
+use pyport::{Py_ssize_t, Py_hash_t};
 use object::*;
 
 #[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
@@ -77,3 +77,8 @@ pub unsafe fn PyDictViewSet_Check(op : *mut PyObject) -> c_int {
      -> c_int;
 }
 
+#[cfg(not(Py_LIMITED_API))]
+#[cfg_attr(windows, link(name="pythonXY"))] extern "C" {
+    pub fn _PyDict_GetItem_KnownHash(mp: *mut PyObject, key: *mut PyObject,
+                                     hash: Py_hash_t) -> *mut PyObject;
+}
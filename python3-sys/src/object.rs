@@ -427,6 +427,10 @@ mod typeobject {
     impl Default for PyBufferProcs {
         #[inline] fn default() -> Self { unsafe { ::core::mem::zeroed() } }
     }
+    pub const PyBufferProcs_INIT : PyBufferProcs = PyBufferProcs {
+        bf_getbuffer: None,
+        bf_releasebuffer: None,
+    };
 
     #[repr(C)]
     #[derive(Copy)]
@@ -712,6 +716,9 @@ pub unsafe fn PyType_CheckExact(op: *mut PyObject) -> c_int {
      -> *mut PyObject;
     pub fn PyType_GenericNew(t: *mut PyTypeObject, args: *mut PyObject,
                              kwds: *mut PyObject) -> *mut PyObject;
+    #[cfg(not(Py_LIMITED_API))]
+    pub fn _PyType_Lookup(arg1: *mut PyTypeObject, arg2: *mut PyObject)
+     -> *mut PyObject;
     pub fn PyType_ClearCache() -> c_uint;
     pub fn PyType_Modified(t: *mut PyTypeObject);
     
@@ -18,6 +18,14 @@ use pyarena::PyArena;
     #[cfg(Py_3_6)]
     pub fn Py_FinalizeEx() -> c_int;
     pub fn Py_IsInitialized() -> c_int;
+    #[cfg(all(Py_3_7, not(Py_3_13), not(Py_LIMITED_API)))]
+    pub fn _Py_IsFinalizing() -> c_int;
+    #[cfg(Py_3_13)]
+    pub fn Py_IsFinalizing() -> c_int;
+    /// The thread state of the thread running `Py_Finalize`, or null.
+    /// Replaced by `_Py_IsFinalizing()` in Python 3.7.
+    #[cfg(all(not(Py_3_7), not(Py_LIMITED_API)))]
+    pub static mut _Py_Finalizing: *mut PyThreadState;
     pub fn Py_NewInterpreter() -> *mut PyThreadState;
     pub fn Py_EndInterpreter(arg1: *mut PyThreadState) -> ();
 }
@@ -48,11 +48,30 @@ pub fn parse_args(
     args: &PyTuple, kwargs: Option<&PyDict>,
     output: &mut[Option<PyObject>]
 ) -> PyResult<()>
+{
+    parse_args_with_varargs(py, fname, params, args, kwargs, false, false, output).map(|_| ())
+}
+
+/// Parse argument list, collecting surplus arguments for `*args` and `**kwargs`
+///
+/// Works like `parse_args()`, but if `accept_args` is true, positional arguments beyond
+/// the declared parameters are returned in a tuple instead of causing an error.
+/// Similarly, if `accept_kwargs` is true, keyword arguments that do not match
+/// a declared parameter are returned in a dict.
+/// The tuple (resp. the dict) is `None` if and only if `accept_args` (resp. `accept_kwargs`) is false.
+#[allow(clippy::too_many_arguments)]
+pub fn parse_args_with_varargs(
+    py: Python,
+    fname: Option<&str>, params: &[ParamDescription],
+    args: &PyTuple, kwargs: Option<&PyDict>,
+    accept_args: bool, accept_kwargs: bool,
+    output: &mut[Option<PyObject>]
+) -> PyResult<(Option<PyTuple>, Option<PyDict>)>
 {
     assert!(params.len() == output.len());
     let nargs = args.len(py);
     let nkeywords = kwargs.map_or(0, |d| d.len(py));
-    if nargs + nkeywords > params.len() {
+    if !accept_args && !accept_kwargs && nargs + nkeywords > params.len() {
         return Err(err::PyErr::new::<exc::TypeError, _>(py,
             format!("{}{} takes at most {} argument{} ({} given)",
                     fname.unwrap_or("function"),
@@ -62,6 +81,16 @@ pub fn parse_args(
                     nargs + nkeywords
                 )));
     }
+    if !accept_args && nargs > params.len() {
+        return Err(err::PyErr::new::<exc::TypeError, _>(py,
+            format!("{}{} takes at most {} positional argument{} ({} given)",
+                    fname.unwrap_or("function"),
+                    if fname.is_some() { "()" } else { "" },
+                    params.len(),
+                    if params.len() == 1 { "" } else { "s" },
+                    nargs
+                )));
+    }
     let mut used_keywords = 0;
     // Iterate through the parameters and assign values to output:
     for (i, (p, out)) in params.iter().zip(output).enumerate() {
@@ -89,18 +118,29 @@ pub fn parse_args(
             }
         }
     }
+    let varargs = if !accept_args {
+        None
+    } else if nargs > params.len() {
+        Some(PyTuple::new(py, &args.as_slice(py)[params.len()..]))
+    } else {
+        Some(PyTuple::empty(py))
+    };
+    let extra_kwargs = if accept_kwargs { Some(PyDict::new(py)) } else { None };
     if used_keywords != nkeywords {
         // check for extraneous keyword arguments
-        for (key, _value) in kwargs.unwrap().items(py) {
-            let key = key.cast_as::<PyString>(py)?.to_string(py)?;
-            if !params.iter().any(|p| p.name == key) {
-                return Err(err::PyErr::new::<exc::TypeError, _>(py,
-                    format!("'{}' is an invalid keyword argument for this function",
-                            key)));
+        for (key, value) in kwargs.unwrap().items(py) {
+            let key_str = key.cast_as::<PyString>(py)?.to_string(py)?;
+            if !params.iter().any(|p| p.name == key_str) {
+                match extra_kwargs {
+                    Some(ref extra_kwargs) => extra_kwargs.set_item(py, key, value)?,
+                    None => return Err(err::PyErr::new::<exc::TypeError, _>(py,
+                        format!("'{}' is an invalid keyword argument for this function",
+                                key_str)))
+                }
             }
         }
     }
-    Ok(())
+    Ok((varargs, extra_kwargs))
 }
 
 /// This macro is used to parse a parameter list into a set of variables.
@@ -123,9 +163,15 @@ pub fn parse_args(
 ///
 ///   The types used must implement the `FromPyObject` trait.
 ///   If no type is specified, the parameter implicitly uses
-///   `&PyObject` (format 1), `&PyTuple` (format 4) or `&PyDict` (format 6).
-///   If a default value is specified, it must be a compile-time constant
-//    of type `ty`.
+///   `&PyObject` (format 1), `&PyTuple` (format 4) or `Option<&PyDict>` (format 6).
+///   At most one `*name` and one `**name` parameter may be used, and they must
+///   come after all other parameters.
+///   The `*name` parameter receives a tuple of the positional arguments that were not
+///   assigned to another parameter; the `**name` parameter receives a dict of the
+///   remaining keyword arguments.
+///   If a default value is specified, it must be an expression of type `ty`.
+///   The expression is evaluated (with the GIL held) only when the argument is missing.
+///   For reference types, the default value must have `'static` lifetime.
 ///  * `body`: expression of type `PyResult<_>`.
 ///     The extracted argument values are available in this scope.
 ///
@@ -308,9 +354,62 @@ macro_rules! py_argparse_impl {
         $body
     }};
 
-    // normal argparse logic
+    // normal argparse logic: first separate *args and **kwargs from the other parameters
     ($py:expr, $fname:expr, $args:expr, $kwargs:expr, $body:block,
         [ $( { $pname:ident : $ptype:ty = $detail:tt } )* ]
+    ) => {
+        py_argparse_split! {
+            { $py, $fname, $args, $kwargs, $body }
+            [] [] []
+            [ $( { $pname : $ptype = $detail } )* ]
+        }
+    };
+}
+
+// Helper for py_argparse_impl!():
+// py_argparse_split! { info [params] [varargs] [kwargs] [remaining params] }
+// moves the *args and **kwargs parameters out of the parameter list.
+// Their detail is replaced with the one for a normal parameter,
+// so that py_argparse_extract!() handles them like any other parameter.
+#[macro_export(local_inner_macros)]
+#[doc(hidden)]
+macro_rules! py_argparse_split {
+    ( $info:tt $params:tt $varargs:tt $kwargs:tt [] ) => {
+        py_argparse_impl_varargs! { $info $params $varargs $kwargs }
+    };
+    ( $info:tt $params:tt [] $kwargs:tt
+        [ { $pname:ident : $ptype:ty = [ {*} {} $rtype:tt ] } $($tail:tt)* ]
+    ) => {
+        py_argparse_split! {
+            $info $params [ { $pname : $ptype = [ {} {} $rtype ] } ] $kwargs [ $($tail)* ]
+        }
+    };
+    ( $info:tt $params:tt $varargs:tt []
+        [ { $pname:ident : $ptype:ty = [ {**} {} $rtype:tt ] } $($tail:tt)* ]
+    ) => {
+        py_argparse_split! {
+            $info $params $varargs [ { $pname : $ptype = [ {} {} $rtype ] } ] [ $($tail)* ]
+        }
+    };
+    ( $info:tt [ $($params:tt)* ] $varargs:tt $kwargs:tt [ $param:tt $($tail:tt)* ] ) => {
+        py_argparse_split! { $info [ $($params)* $param ] $varargs $kwargs [ $($tail)* ] }
+    };
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! py_argparse_is_nonempty {
+    ([]) => (false);
+    ([ $($t:tt)+ ]) => (true);
+}
+
+#[macro_export(local_inner_macros)]
+#[doc(hidden)]
+macro_rules! py_argparse_impl_varargs {
+    ( { $py:expr, $fname:expr, $args:expr, $kwargs:expr, $body:block }
+        [ $( { $pname:ident : $ptype:ty = $detail:tt } )* ]
+        [ $( $varargs:tt )* ]
+        [ $( $varkwargs:tt )* ]
     ) => {{
         const PARAMS: &'static [$crate::argparse::ParamDescription<'static>] = &[
             $(
@@ -319,17 +418,24 @@ macro_rules! py_argparse_impl {
         ];
         let py: $crate::Python = $py;
         let mut output = [$( py_replace_expr!($pname None) ),*];
-        match $crate::argparse::parse_args(py, $fname, PARAMS, $args, $kwargs, &mut output) {
-            Ok(()) => {
+        match $crate::argparse::parse_args_with_varargs(py, $fname, PARAMS, $args, $kwargs,
+            py_argparse_is_nonempty!([ $($varargs)* ]), py_argparse_is_nonempty!([ $($varkwargs)* ]),
+            &mut output)
+        {
+            Ok((_varargs, _varkwargs)) => {
+                let _extras = [
+                    _varargs.map($crate::PythonObject::into_object),
+                    _varkwargs.map($crate::PythonObject::into_object)
+                ];
                 // Experimental slice pattern syntax would be really nice here (#23121)
                 //let [$(ref $pname),*] = output;
                 // We'll use an iterator instead.
-                let mut _iter = output.iter();
+                let mut _iter = output.iter().chain(_extras.iter().filter(|e| e.is_some()));
                 // We'll have to generate a bunch of nested `match` statements
                 // (at least until we can use ? + catch, assuming that will be hygienic wrt. macros),
                 // so use a recursive helper macro for that:
                 py_argparse_extract!( py, _iter, $body,
-                    [ $( { $pname : $ptype = $detail } )* ])
+                    [ $( { $pname : $ptype = $detail } )* $( $varargs )* $( $varkwargs )* ])
             },
             Err(e) => Err(e)
         }
@@ -379,6 +485,38 @@ macro_rules! py_argparse_param_description {
     );
 }
 
+// Builds the text signature (the first line of `__doc__` that `inspect.signature()`
+// understands) from the output format of py_argparse_parse_plist!().
+// Rust default values are not valid Python expressions, so they are shown as `...`.
+#[macro_export(local_inner_macros)]
+#[doc(hidden)]
+macro_rules! py_argparse_text_signature {
+    { $fname:expr, [] } => (
+        _cpython__argparse__concat!($fname, "()\n--\n\n")
+    );
+    { $fname:expr, [ $first:tt $( $param:tt )* ] } => (
+        _cpython__argparse__concat!($fname, "(", py_argparse_text_signature_param!($first),
+            $( ", ", py_argparse_text_signature_param!($param), )* ")\n--\n\n")
+    );
+}
+
+#[macro_export(local_inner_macros)]
+#[doc(hidden)]
+macro_rules! py_argparse_text_signature_param {
+    { { $pname:ident : $ptype:ty = [ {} {} $rtype:tt ] } } => (
+        _cpython__argparse__stringify!($pname)
+    );
+    { { $pname:ident : $ptype:ty = [ {} {$default:expr} $rtype:tt ] } } => (
+        _cpython__argparse__concat!(_cpython__argparse__stringify!($pname), "=...")
+    );
+    { { $pname:ident : $ptype:ty = [ {*} {} $rtype:tt ] } } => (
+        _cpython__argparse__concat!("*", _cpython__argparse__stringify!($pname))
+    );
+    { { $pname:ident : $ptype:ty = [ {**} {} $rtype:tt ] } } => (
+        _cpython__argparse__concat!("**", _cpython__argparse__stringify!($pname))
+    );
+}
+
 #[macro_export(local_inner_macros)]
 #[doc(hidden)]
 macro_rules! py_argparse_extract {
@@ -413,7 +551,7 @@ macro_rules! py_argparse_extract {
     ( $py:expr, $iter:expr, $body:block,
         [ { $pname:ident : $ptype:ty = [ {} {$default:expr} {} ] } $($tail:tt)* ]
     ) => {
-        match $iter.next().unwrap().as_ref().map(|obj| obj.extract::<_>($py)).unwrap_or(Ok($default)) {
+        match $iter.next().unwrap().as_ref().map_or_else(|| Ok($default), |obj| obj.extract::<_>($py)) {
             Ok($pname) => py_argparse_extract!($py, $iter, $body, [$($tail)*]),
             Err(e) => Err(e)
         }
@@ -430,6 +568,23 @@ macro_rules! py_argparse_extract {
     };
 }
 
+// Rust 2018 support
+#[macro_export]
+#[doc(hidden)]
+macro_rules! _cpython__argparse__stringify {
+    ($($inner:tt)*) => {
+        stringify! { $($inner)* }
+    }
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! _cpython__argparse__concat {
+    ($($inner:tt)*) => {
+        concat! { $($inner)* }
+    }
+}
+
 #[doc(hidden)] // used in py_argparse_extract!() macro
 pub fn with_extracted_or_default<P: ?Sized, R, F>(py: Python, obj: Option<&PyObject>, f: F, default: &'static P) -> PyResult<R>
     where F: FnOnce(&P) -> PyResult<R>,
@@ -447,7 +602,7 @@ pub fn with_extracted_or_default<P: ?Sized, R, F>(py: Python, obj: Option<&PyObj
 #[cfg(test)]
 mod test {
     use python::{Python, PythonObject};
-    use objects::PyTuple;
+    use objects::{PyTuple, PyDict};
     use conversion::ToPyObject;
 
     #[test]
@@ -503,4 +658,44 @@ mod test {
         }).unwrap();
         assert!(called);
     }
+
+    #[test]
+    pub fn test_varargs() {
+        let gil_guard = Python::acquire_gil();
+        let py = gil_guard.python();
+        let mut called = false;
+        let tuple = (1, "abc", 2, 3).to_py_object(py);
+        let kwargs = PyDict::new(py);
+        kwargs.set_item(py, "z", 4).unwrap();
+        py_argparse!(py, None, &tuple, Some(&kwargs), (x: i32, y: &str, *args, **kwargs) {
+            assert_eq!(x, 1);
+            assert_eq!(y, "abc");
+            assert_eq!(args.as_slice(py).len(), 2);
+            let kwargs = kwargs.unwrap();
+            assert_eq!(kwargs.len(py), 1);
+            assert_eq!(kwargs.get_item(py, "z").unwrap().extract::<i32>(py).unwrap(), 4);
+            called = true;
+            Ok(())
+        }).unwrap();
+        assert!(called);
+    }
+
+    #[test]
+    pub fn test_default_value_is_lazy() {
+        let gil_guard = Python::acquire_gil();
+        let py = gil_guard.python();
+        let mut evaluated = 0;
+        let mut default = || { evaluated += 1; 42 };
+        let tuple = (1,).to_py_object(py);
+        py_argparse!(py, None, &tuple, None, (x: i32 = default()) {
+            assert_eq!(x, 1);
+            Ok(())
+        }).unwrap();
+        let tuple = PyTuple::empty(py);
+        py_argparse!(py, None, &tuple, None, (x: i32 = default()) {
+            assert_eq!(x, 42);
+            Ok(())
+        }).unwrap();
+        assert_eq!(evaluated, 1);
+    }
 }
@@ -17,10 +17,12 @@
 // DEALINGS IN THE SOFTWARE.
 
 use std;
+use std::cell::RefCell;
 use ffi;
-use python::{Python, PythonObject, PythonObjectWithCheckedDowncast, PyDrop, PyClone};
-use objects::PyObject;
-use err::PyResult;
+use python::{Python, PythonObject, PythonObjectWithCheckedDowncast, PyDrop, PyClone, ToPythonPointer};
+use pythonrun::GILProtected;
+use objects::{PyObject, PyTuple, PyList, exc};
+use err::{PyErr, PyResult};
 
 /// Conversion trait that allows various objects to be converted into Python objects.
 pub trait ToPyObject {
@@ -165,6 +167,19 @@ impl <'a, T: ?Sized> ToPyObject for &'a T where T: ToPyObject {
     }
 }
 
+/// `()` is converted to Python `None`.
+///
+/// This allows functions returning `PyResult<()>` to be exposed to Python
+/// as functions returning `None`.
+impl ToPyObject for () {
+    type ObjectType = PyObject;
+
+    #[inline]
+    fn to_py_object(&self, py: Python) -> PyObject {
+        py.None()
+    }
+}
+
 /// `Option::Some<T>` is converted like `T`.
 /// `Option::None` is converted to Python `None`.
 impl <T> ToPyObject for Option<T> where T: ToPyObject {
@@ -185,6 +200,10 @@ impl <T> ToPyObject for Option<T> where T: ToPyObject {
     }
 }
 
+/// Python `None` is extracted as `Option::None`.
+/// Any other object is extracted as `Option::Some<T>`, using the extraction for `T`.
+///
+/// For nested options, `None` always maps to the outermost `None`.
 impl <'source, T> FromPyObject<'source> for Option<T> where T: FromPyObject<'source> {
     fn extract(py: Python, obj: &'source PyObject) -> PyResult<Self> {
         if obj.as_ptr() == unsafe { ffi::Py_None() } {
@@ -226,3 +245,317 @@ where T: ExtractPyObject<'prepared>
 }
 */
 
+
+/// Defines a plain Rust struct that is converted to and from a Python tuple,
+/// mapping the struct fields by position.
+///
+/// `ToPyObject` converts the struct to a tuple of its fields (in declaration order).
+/// `FromPyObject` accepts a tuple or list with exactly one element per field.
+///
+/// # Example
+/// ```
+/// #[macro_use] extern crate cpython;
+/// use cpython::{Python, ToPyObject, PythonObject};
+///
+/// py_record!(Point { x: f64, y: f64 });
+///
+/// fn main() {
+///     let gil = Python::acquire_gil();
+///     let py = gil.python();
+///     let obj = Point { x: 1.0, y: 2.0 }.to_py_object(py).into_object();
+///     let p: Point = obj.extract(py).unwrap();
+///     assert_eq!((p.x, p.y), (1.0, 2.0));
+/// }
+/// ```
+#[macro_export]
+macro_rules! py_record {
+    ($(#[$attr:meta])* pub $name:ident { $( $field:ident : $ty:ty ),+ $(,)* }) => {
+        $(#[$attr])*
+        pub struct $name { $( pub $field: $ty, )+ }
+        py_record!(@impl $name { $( $field : $ty ),+ });
+    };
+    ($(#[$attr:meta])* $name:ident { $( $field:ident : $ty:ty ),+ $(,)* }) => {
+        $(#[$attr])*
+        struct $name { $( $field: $ty, )+ }
+        py_record!(@impl $name { $( $field : $ty ),+ });
+    };
+    (@impl $name:ident { $( $field:ident : $ty:ty ),+ }) => {
+        impl $crate::ToPyObject for $name {
+            type ObjectType = $crate::PyTuple;
+
+            fn to_py_object(&self, py: $crate::Python) -> $crate::PyTuple {
+                $crate::PyTuple::new(py, &[
+                    $( $crate::PythonObject::into_object($crate::ToPyObject::to_py_object(&self.$field, py)), )+
+                ])
+            }
+
+            fn into_py_object(self, py: $crate::Python) -> $crate::PyTuple {
+                $crate::PyTuple::new(py, &[
+                    $( $crate::PythonObject::into_object($crate::ToPyObject::into_py_object(self.$field, py)), )+
+                ])
+            }
+        }
+
+        impl <'source> $crate::FromPyObject<'source> for $name {
+            fn extract(py: $crate::Python, obj: &'source $crate::PyObject) -> $crate::PyResult<Self> {
+                let items = $crate::_detail::record_items(py, obj, [ $( stringify!($field) ),+ ].len())?;
+                let mut items = items.iter();
+                Ok($name {
+                    $( $field: items.next().unwrap().extract::<$ty>(py)?, )+
+                })
+            }
+        }
+    };
+}
+
+/// Defines a plain Rust struct that is converted to a Python `dict`,
+/// using the field names as keys.
+///
+/// Each field is converted with its own `ToPyObject` implementation.
+/// A field can be given a different key with `=> "key"`,
+/// or left out of the dict entirely with `=> _`.
+///
+/// Unlike `py_record!`, no `FromPyObject` implementation is generated,
+/// as skipped fields could not be reconstructed.
+///
+/// # Example
+/// ```
+/// #[macro_use] extern crate cpython;
+/// use cpython::{Python, PyDict, ToPyObject};
+///
+/// py_dict_record!(Config {
+///     name: String,
+///     max_size: u32 => "maxSize",
+///     cache: Vec<u8> => _,
+/// });
+///
+/// fn main() {
+///     let gil = Python::acquire_gil();
+///     let py = gil.python();
+///     let config = Config { name: "test".to_owned(), max_size: 10, cache: Vec::new() };
+///     let dict: PyDict = config.to_py_object(py);
+///     assert_eq!(dict.len(py), 2);
+///     assert_eq!(dict.get_item(py, "maxSize").unwrap().extract::<u32>(py).unwrap(), 10);
+///     assert!(dict.get_item(py, "cache").is_none());
+/// }
+/// ```
+#[macro_export]
+macro_rules! py_dict_record {
+    ($(#[$attr:meta])* pub $name:ident { $( $field:ident : $ty:ty $(=> $key:tt)* ),+ $(,)* }) => {
+        $(#[$attr])*
+        pub struct $name { $( pub $field: $ty, )+ }
+        py_dict_record!(@impl $name { $( $field $(=> $key)* ),+ });
+    };
+    ($(#[$attr:meta])* $name:ident { $( $field:ident : $ty:ty $(=> $key:tt)* ),+ $(,)* }) => {
+        $(#[$attr])*
+        struct $name { $( $field: $ty, )+ }
+        py_dict_record!(@impl $name { $( $field $(=> $key)* ),+ });
+    };
+    (@impl $name:ident { $( $field:ident $(=> $key:tt)* ),+ }) => {
+        impl $crate::ToPyObject for $name {
+            type ObjectType = $crate::PyDict;
+
+            fn to_py_object(&self, py: $crate::Python) -> $crate::PyDict {
+                let dict = $crate::PyDict::new(py);
+                $( py_dict_record!(@item py, dict, &self.$field, $field $($key)*); )+
+                dict
+            }
+        }
+    };
+    (@item $py:ident, $dict:ident, $value:expr, $field:ident _) => {};
+    (@item $py:ident, $dict:ident, $value:expr, $field:ident $key:tt) => {
+        $dict.set_item($py, $key, $value).unwrap()
+    };
+    (@item $py:ident, $dict:ident, $value:expr, $field:ident) => {
+        $dict.set_item($py, stringify!($field), $value).unwrap()
+    };
+}
+
+/// Returns the items of `obj`, which must be a tuple or list of length `len`.
+/// Used by the `py_record!` macro.
+#[doc(hidden)]
+pub fn record_items(py: Python, obj: &PyObject, len: usize) -> PyResult<Vec<PyObject>> {
+    let items: Vec<PyObject> = if let Ok(t) = obj.cast_as::<PyTuple>(py) {
+        t.as_slice(py).iter().map(|item| item.clone_ref(py)).collect()
+    } else {
+        let list = obj.cast_as::<PyList>(py)?;
+        list.iter(py).collect()
+    };
+    if items.len() == len {
+        Ok(items)
+    } else {
+        let msg = format!("Expected tuple or list of length {}, but got length {}.", len, items.len());
+        Err(PyErr::new::<exc::ValueError, _>(py, msg))
+    }
+}
+
+/// A constant value that is converted to a Python object only once.
+///
+/// The first conversion stores the resulting Python object;
+/// later conversions return new references to that same object.
+/// This avoids repeated allocations in loops that emit the same value many times.
+///
+/// The cached object belongs to the interpreter it was first converted in.
+///
+/// # Example
+/// ```
+/// use cpython::{Python, PythonObject, PyConst, ToPyObject};
+///
+/// let greeting = PyConst::new("hello world");
+/// let gil = Python::acquire_gil();
+/// let py = gil.python();
+/// let a = greeting.to_py_object(py);
+/// let b = greeting.to_py_object(py);
+/// assert!(a.as_object() == b.as_object());
+/// ```
+pub struct PyConst<T> {
+    value: T,
+    cache: GILProtected<RefCell<Option<PyObject>>>
+}
+
+impl <T> PyConst<T> where T: ToPyObject {
+    /// Creates a new `PyConst`. The value is not converted until it is first used.
+    #[inline]
+    #[cfg(feature="nightly")]
+    pub const fn new(value: T) -> PyConst<T> {
+        PyConst { value: value, cache: GILProtected::new(RefCell::new(None)) }
+    }
+
+    /// Creates a new `PyConst`. The value is not converted until it is first used.
+    #[inline]
+    #[cfg(not(feature="nightly"))]
+    pub fn new(value: T) -> PyConst<T> {
+        PyConst { value: value, cache: GILProtected::new(RefCell::new(None)) }
+    }
+
+    /// Gets the wrapped Rust value.
+    #[inline]
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Returns a pointer to the cached Python object, converting the value if necessary.
+    /// The pointer stays valid as long as self is alive.
+    fn cached_ptr(&self, py: Python) -> *mut ffi::PyObject {
+        let cache = self.cache.get(py);
+        if let Some(ref obj) = *cache.borrow() {
+            return obj.as_ptr();
+        }
+        let obj = self.value.to_py_object(py).into_object();
+        let mut slot = cache.borrow_mut();
+        // If the conversion released the GIL, another thread may have filled the cache.
+        if slot.is_none() {
+            *slot = Some(obj);
+        } else {
+            obj.release_ref(py);
+        }
+        slot.as_ref().unwrap().as_ptr()
+    }
+}
+
+impl <T> ToPyObject for PyConst<T> where T: ToPyObject {
+    type ObjectType = T::ObjectType;
+
+    #[inline]
+    fn to_py_object(&self, py: Python) -> T::ObjectType {
+        unsafe {
+            PyObject::from_borrowed_ptr(py, self.cached_ptr(py)).unchecked_cast_into()
+        }
+    }
+
+    #[inline]
+    fn with_borrowed_ptr<F, R>(&self, py: Python, f: F) -> R
+        where F: FnOnce(*mut ffi::PyObject) -> R
+    {
+        f(self.cached_ptr(py))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use python::{Python, PythonObject, ToPythonPointer};
+    use conversion::{ToPyObject, PyConst};
+
+    #[test]
+    fn test_option_to_py_object() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        assert_eq!(42, Some(42).to_py_object(py).extract::<i32>(py).unwrap());
+        assert!(None::<i32>.to_py_object(py) == py.None());
+    }
+
+    #[test]
+    fn test_unit_to_py_object() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        assert!(().to_py_object(py).is_none(py));
+        assert!(().into_py_object(py) == py.None());
+    }
+
+    #[test]
+    fn test_option_extract() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        assert_eq!(Some(42), 42i32.to_py_object(py).into_object().extract::<Option<i32>>(py).unwrap());
+        assert_eq!(None, py.None().extract::<Option<i32>>(py).unwrap());
+        assert!("abc".to_py_object(py).into_object().extract::<Option<i32>>(py).is_err());
+    }
+
+    #[test]
+    fn test_nested_option_extract() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        assert_eq!(None, py.None().extract::<Option<Option<i32>>>(py).unwrap());
+        assert_eq!(Some(Some(1)), 1i32.to_py_object(py).into_object().extract::<Option<Option<i32>>>(py).unwrap());
+    }
+
+    py_record!(Point { x: f64, y: f64 });
+
+    #[test]
+    fn test_record_round_trip() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let t = Point { x: 1.5, y: -2.0 }.to_py_object(py);
+        assert_eq!((1.5, -2.0), t.as_object().extract::<(f64, f64)>(py).unwrap());
+        let p = t.into_object().extract::<Point>(py).unwrap();
+        assert_eq!(1.5, p.x);
+        assert_eq!(-2.0, p.y);
+        let p = vec![3.0, 4.0].to_py_object(py).into_object().extract::<Point>(py).unwrap();
+        assert_eq!((3.0, 4.0), (p.x, p.y));
+        assert!((1.0, 2.0, 3.0).to_py_object(py).into_object().extract::<Point>(py).is_err());
+        assert!("ab".to_py_object(py).into_object().extract::<Point>(py).is_err());
+    }
+
+    py_dict_record!(User { name: String, user_id: u64 => "id", password: String => _ });
+
+    #[test]
+    fn test_dict_record() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let user = User { name: "alice".to_owned(), user_id: 7, password: "secret".to_owned() };
+        let dict = user.to_py_object(py);
+        assert_eq!(2, dict.len(py));
+        assert_eq!("alice", dict.get_item(py, "name").unwrap().extract::<String>(py).unwrap());
+        assert_eq!(7, dict.get_item(py, "id").unwrap().extract::<u64>(py).unwrap());
+        assert!(dict.get_item(py, "user_id").is_none());
+        assert!(dict.get_item(py, "password").is_none());
+        assert_eq!("secret", user.password);
+    }
+
+    #[test]
+    fn test_py_const() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let memo = PyConst::new("not an interned string");
+        let first = memo.to_py_object(py).into_object();
+        for _ in 0..100000 {
+            let obj = memo.to_py_object(py).into_object();
+            assert!(obj == first);
+        }
+        memo.with_borrowed_ptr(py, |ptr| assert_eq!(first.as_ptr(), ptr));
+        assert_eq!("not an interned string", first.extract::<String>(py).unwrap());
+        // without the memo, every conversion allocates a new object
+        let plain = "not an interned string".to_py_object(py).into_object();
+        assert!(plain != first);
+    }
+}
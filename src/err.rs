@@ -17,9 +17,10 @@
 // DEALINGS IN THE SOFTWARE.
 
 use std;
+use std::io;
 use python::{PythonObject, ToPythonPointer, Python, PythonObjectDowncastError,
         PythonObjectWithTypeObject, PyClone, PyDrop};
-use objects::{PyObject, PyType, exc};
+use objects::{PyObject, PyType, PyModule, exc};
 #[cfg(feature="python27-sys")]
 use objects::oldstyle::PyClass;
 use ffi;
@@ -33,10 +34,15 @@ use std::ffi::CString;
 Defines a new exception type.
 
 # Syntax
-`py_exception!(module, MyError)`
+`py_exception!(module, MyError[, BaseType])`
 
 * `module` is the name of the containing module.
 * `MyError` is the name of the new exception type.
+* `BaseType` is the Rust type of the base exception class; defaults to `cpython::exc::Exception`.
+
+The new type is created lazily on first use. To make it importable from Python,
+add it to the module in the module initializer:
+`m.add(py, "MyError", py.get_type::<MyError>())?;`
 
 # Example
 ```
@@ -189,6 +195,11 @@ impl PyErr {
         }
     }
 
+    /// Creates a new `RuntimeError` from a Rust error, using its `Display` message.
+    pub fn from_std_error<E>(py: Python, err: E) -> PyErr where E: std::error::Error {
+        PyErr::new::<exc::RuntimeError, _>(py, err.to_string())
+    }
+
     /// Retrieves the current error from the Python interpreter's global state.
     /// The error is cleared from the Python interpreter.
     /// If no error is set, returns a `SystemError`.
@@ -202,6 +213,22 @@ impl PyErr {
         }
     }
 
+    /// Retrieves and normalizes the current error, if any.
+    /// The error is cleared from the Python interpreter.
+    ///
+    /// Unlike `PyErr::fetch()`, returns `None` if no error is set.
+    /// Together with `PyErr::restore()`, this allows stashing an error
+    /// while running cleanup code that might itself call into Python.
+    pub fn take(py: Python) -> Option<PyErr> {
+        if PyErr::occurred(py) {
+            let mut err = PyErr::fetch(py);
+            err.normalize(py);
+            Some(err)
+        } else {
+            None
+        }
+    }
+
     unsafe fn new_from_ffi_tuple(py: Python, ptype: *mut ffi::PyObject, pvalue: *mut ffi::PyObject, ptraceback: *mut ffi::PyObject) -> PyErr {
         // Note: must not panic to ensure all owned pointers get acquired correctly,
         // and because we mustn't panic in normalize().
@@ -283,6 +310,11 @@ impl PyErr {
     /// Return true if the current exception matches the exception in `exc`.
     /// If `exc` is a class object, this also returns `true` when `self` is an instance of a subclass.
     /// If `exc` is a tuple, all exceptions in the tuple (and recursively in subtuples) are searched for a match.
+    ///
+    /// This only inspects `self`; the interpreter's global error state is left untouched.
+    ///
+    /// Example:
+    ///  `Err(ref e) if e.matches(py, py.get_type::<exc::KeyError>()) => ...`
     pub fn matches<T>(&self, py: Python, exc: T) -> bool
         where T: ToPyObject
     {
@@ -291,6 +323,23 @@ impl PyErr {
         })
     }
 
+    /// Formats the exception type, value and traceback into a string,
+    /// like Python's `traceback.format_exception()`.
+    ///
+    /// If there is no traceback, only the exception type and value are formatted.
+    pub fn format_traceback(&self, py: Python) -> PyResult<String> {
+        let mut err = self.clone_ref(py);
+        err.normalize(py);
+        let traceback = PyModule::import(py, "traceback")?;
+        let lines = traceback.call(py, "format_exception", (
+            &err.ptype,
+            err.pvalue.as_ref().map_or_else(|| py.None(), |v| v.clone_ref(py)),
+            err.ptraceback.as_ref().map_or_else(|| py.None(), |tb| tb.clone_ref(py)),
+        ), None)?;
+        let lines: Vec<String> = lines.extract(py)?;
+        Ok(lines.concat())
+    }
+
     /// Normalizes the error. This ensures that the exception value is an instance of the exception type.
     pub fn normalize(&mut self, py: Python) {
         // The normalization helper function involves temporarily moving out of the &mut self,
@@ -349,8 +398,98 @@ impl PyErr {
         }
     }
 
+    /// Sets `cause` as the `__cause__` of this exception and returns the chained error.
+    /// This is equivalent to the Python statement `raise self from cause`.
+    ///
+    /// Both errors are normalized in order to create the exception instances.
+    ///
+    /// Python 2 has no exception chaining: there, `cause` is only stored
+    /// as the `__cause__` attribute of the exception, and is not shown in tracebacks.
+    #[cfg(feature="python3-sys")]
+    pub fn with_cause(mut self, py: Python, mut cause: PyErr) -> PyErr {
+        let instance = self.instance(py);
+        let cause_instance = cause.instance(py);
+        unsafe {
+            if let Some(ref tb) = cause.ptraceback {
+                ffi::PyException_SetTraceback(cause_instance.as_ptr(), tb.as_ptr());
+            }
+            // PyException_SetCause steals the reference to cause_instance
+            ffi::PyException_SetCause(instance.as_ptr(), cause_instance.steal_ptr());
+        }
+        instance.release_ref(py);
+        cause.release_ref(py);
+        self
+    }
+
+    /// Sets `cause` as the `__cause__` of this exception and returns the chained error.
+    #[cfg(feature="python27-sys")]
+    pub fn with_cause(mut self, py: Python, mut cause: PyErr) -> PyErr {
+        let instance = self.instance(py);
+        let cause_instance = cause.instance(py);
+        let name = "__cause__".to_py_object(py);
+        unsafe {
+            // PyObject_SetAttr does not steal references
+            if ffi::PyObject_SetAttr(instance.as_ptr(), name.as_object().as_ptr(), cause_instance.as_ptr()) < 0 {
+                // old-style class instances may refuse the attribute; keep the error unchained
+                ffi::PyErr_Clear();
+            }
+        }
+        name.release_ref(py);
+        cause_instance.release_ref(py);
+        instance.release_ref(py);
+        cause.release_ref(py);
+        self
+    }
+
+    /// Sets the currently active exception as the `__context__` of `err` and returns the chained error.
+    /// This matches the implicit chaining of a Python `raise` statement inside an `except` block.
+    ///
+    /// The currently active exception is the error in the interpreter's global error state
+    /// (which is cleared by this function), or, if there is none,
+    /// the exception currently being handled (`sys.exc_info()`).
+    /// If neither exists, `err` is returned unchanged.
+    #[cfg(feature="python3-sys")]
+    pub fn new_chained(py: Python, mut err: PyErr) -> PyErr {
+        let context = if PyErr::occurred(py) {
+            Some(PyErr::fetch(py))
+        } else {
+            unsafe {
+                let mut ptype : *mut ffi::PyObject = ptr::null_mut();
+                let mut pvalue : *mut ffi::PyObject = ptr::null_mut();
+                let mut ptraceback : *mut ffi::PyObject = ptr::null_mut();
+                ffi::PyErr_GetExcInfo(&mut ptype, &mut pvalue, &mut ptraceback);
+                if ptype.is_null() || ptype == ffi::Py_None() {
+                    ffi::Py_XDECREF(ptype);
+                    ffi::Py_XDECREF(pvalue);
+                    ffi::Py_XDECREF(ptraceback);
+                    None
+                } else {
+                    Some(PyErr::new_from_ffi_tuple(py, ptype, pvalue, ptraceback))
+                }
+            }
+        };
+        if let Some(mut context) = context {
+            let instance = err.instance(py);
+            let context_instance = context.instance(py);
+            if instance != context_instance {
+                unsafe {
+                    if let Some(ref tb) = context.ptraceback {
+                        ffi::PyException_SetTraceback(context_instance.as_ptr(), tb.as_ptr());
+                    }
+                    // PyException_SetContext steals the reference to context_instance
+                    ffi::PyException_SetContext(instance.as_ptr(), context_instance.steal_ptr());
+                }
+            } else {
+                context_instance.release_ref(py);
+            }
+            instance.release_ref(py);
+            context.release_ref(py);
+        }
+        err
+    }
+
     /// Writes the error back to the Python interpreter's global state.
-    /// This is the opposite of `PyErr::fetch()`.
+    /// This is the opposite of `PyErr::fetch()` and `PyErr::take()`.
     #[inline]
     pub fn restore(self, py: Python) {
         let PyErr { ptype, pvalue, ptraceback } = self;
@@ -360,7 +499,11 @@ impl PyErr {
     }
 
     /// Issue a warning message.
-    /// May return a PyErr if warnings-as-errors is enabled.
+    ///
+    /// `category` is the warning class, e.g. `py.get_type::<exc::DeprecationWarning>()`.
+    /// `stacklevel` has the same meaning as in `warnings.warn()`.
+    /// May return a PyErr if warnings-as-errors is enabled
+    /// (e.g. by `warnings.filterwarnings("error")`).
     pub fn warn(py: Python, category: &PyObject, message: &str, stacklevel: i32) -> PyResult<()> {
         let message = CString::new(message).unwrap();
         unsafe {
@@ -387,6 +530,38 @@ impl PyClone for PyErr {
     }
 }
 
+/// Extension methods for chaining errors that are propagated with `?`.
+pub trait ResultExt<T> {
+    /// Replaces the error with the one returned by `f`,
+    /// keeping the original error (converted to `PyErr`) as its `__cause__`.
+    /// See `PyErr::with_cause()`.
+    ///
+    /// # Example
+    /// ```
+    /// use std::fs::File;
+    /// use cpython::{Python, PyErr, PyResult, ResultExt, exc};
+    ///
+    /// fn load_config(py: Python) -> PyResult<File> {
+    ///     File::open("/does/not/exist").chain_err(py, |py| {
+    ///         PyErr::new::<exc::RuntimeError, _>(py, "cannot load config")
+    ///     })
+    /// }
+    /// ```
+    fn chain_err<F>(self, py: Python, f: F) -> PyResult<T> where F: FnOnce(Python) -> PyErr;
+}
+
+impl <T, E> ResultExt<T> for Result<T, E> where E: Into<PyErr> {
+    fn chain_err<F>(self, py: Python, f: F) -> PyResult<T> where F: FnOnce(Python) -> PyErr {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => {
+                let cause = e.into();
+                Err(f(py).with_cause(py, cause))
+            }
+        }
+    }
+}
+
 /// Converts `PythonObjectDowncastError` to Python `TypeError`.
 impl <'p> std::convert::From<PythonObjectDowncastError<'p>> for PyErr {
     fn from(err: PythonObjectDowncastError<'p>) -> PyErr {
@@ -394,6 +569,47 @@ impl <'p> std::convert::From<PythonObjectDowncastError<'p>> for PyErr {
     }
 }
 
+/// Converts `std::io::Error` to Python `OSError`.
+///
+/// On Python 3, the matching `OSError` subclass is used for common error kinds
+/// (e.g. `NotFound` becomes `FileNotFoundError`).
+/// If the error carries an OS error code, the exception is constructed
+/// as `OSError(errno, message)`, so that `e.errno` is available in Python.
+impl std::convert::From<io::Error> for PyErr {
+    fn from(err: io::Error) -> PyErr {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let ty = io_error_type(py, err.kind());
+        let value = match err.raw_os_error() {
+            Some(errno) => (errno, err.to_string()).to_py_object(py).into_object(),
+            None => err.to_string().to_py_object(py).into_object()
+        };
+        PyErr::new_helper(py, ty, value)
+    }
+}
+
+#[cfg(feature="python3-sys")]
+fn io_error_type(py: Python, kind: io::ErrorKind) -> PyType {
+    match kind {
+        io::ErrorKind::NotFound => py.get_type::<exc::FileNotFoundError>(),
+        io::ErrorKind::PermissionDenied => py.get_type::<exc::PermissionError>(),
+        io::ErrorKind::AlreadyExists => py.get_type::<exc::FileExistsError>(),
+        io::ErrorKind::WouldBlock => py.get_type::<exc::BlockingIOError>(),
+        io::ErrorKind::Interrupted => py.get_type::<exc::InterruptedError>(),
+        io::ErrorKind::TimedOut => py.get_type::<exc::TimeoutError>(),
+        io::ErrorKind::BrokenPipe => py.get_type::<exc::BrokenPipeError>(),
+        io::ErrorKind::ConnectionRefused => py.get_type::<exc::ConnectionRefusedError>(),
+        io::ErrorKind::ConnectionReset => py.get_type::<exc::ConnectionResetError>(),
+        io::ErrorKind::ConnectionAborted => py.get_type::<exc::ConnectionAbortedError>(),
+        _ => py.get_type::<exc::OSError>()
+    }
+}
+
+#[cfg(feature="python27-sys")]
+fn io_error_type(py: Python, _kind: io::ErrorKind) -> PyType {
+    py.get_type::<exc::OSError>()
+}
+
 /// Construct PyObject from the result of a Python FFI call that returns a new reference (owned pointer).
 /// Returns `Err(PyErr)` if the pointer is `null`.
 /// Unsafe because the pointer might be invalid.
@@ -471,7 +687,7 @@ macro_rules! _cpython__err__stringify {
 
 #[cfg(test)]
 mod tests {
-    use {Python, PyErr};
+    use {Python, PyErr, PyResult};
     use objects::exc;
 
     #[test]
@@ -482,6 +698,179 @@ mod tests {
         assert!(PyErr::occurred(py));
         drop(PyErr::fetch(py));
     }
+
+    #[test]
+    fn take_and_restore() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        assert!(PyErr::take(py).is_none());
+        py.eval("1 / 0", None, None).unwrap_err().restore(py);
+        let mut err = PyErr::take(py).unwrap();
+        assert!(!PyErr::occurred(py));
+        assert!(err.matches(py, py.get_type::<exc::ZeroDivisionError>()));
+        // take() normalizes, so the value is an instance of the type
+        assert!(err.instance(py).get_type(py) == py.get_type::<exc::ZeroDivisionError>());
+        err.restore(py);
+        assert!(PyErr::occurred(py));
+        assert!(PyErr::take(py).unwrap().matches(py, py.get_type::<exc::ZeroDivisionError>()));
+        assert!(PyErr::take(py).is_none());
+    }
+
+    py_exception!(mymodule, CustomError);
+    py_exception!(mymodule, CustomKeyError, exc::KeyError);
+
+    #[test]
+    fn custom_exception() {
+        use objects::PyDict;
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let m = ::objects::PyModule::new(py, "mymodule").unwrap();
+        m.add(py, "CustomError", py.get_type::<CustomError>()).unwrap();
+        m.add(py, "CustomKeyError", py.get_type::<CustomKeyError>()).unwrap();
+        assert!(py.get_type::<CustomError>().is_subtype_of(py, &py.get_type::<exc::Exception>()));
+        assert!(py.get_type::<CustomKeyError>().is_subtype_of(py, &py.get_type::<exc::KeyError>()));
+
+        let mut err = CustomError::new(py, "oops");
+        assert!(err.matches(py, py.get_type::<CustomError>()));
+        let locals = PyDict::new(py);
+        locals.set_item(py, "m", &m).unwrap();
+        locals.set_item(py, "e", err.instance(py)).unwrap();
+        py.run("try:\n    raise e\nexcept m.CustomError as x:\n    assert x.args == ('oops',)\n\
+                else:\n    assert False\n", None, Some(&locals)).unwrap();
+        py.run("try:\n    raise m.CustomKeyError('k')\nexcept KeyError:\n    pass\n",
+               None, Some(&locals)).unwrap();
+    }
+
+    #[test]
+    fn warn() {
+        use python::PythonObject;
+        use objectprotocol::ObjectProtocol;
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let warnings = py.import("warnings").unwrap();
+        let all = ::objects::PySlice::new(py, 0, ::std::isize::MAX, 1);
+        let saved = warnings.get(py, "filters").unwrap().get_item(py, &all).unwrap();
+        let category = py.get_type::<exc::DeprecationWarning>();
+
+        warnings.call(py, "simplefilter", ("ignore",), None).unwrap();
+        assert!(PyErr::warn(py, category.as_object(), "ignored", 1).is_ok());
+
+        warnings.call(py, "filterwarnings", ("error",), None).unwrap();
+        let result = PyErr::warn(py, category.as_object(), "deprecated", 1);
+        warnings.get(py, "filters").unwrap().set_item(py, all, saved).unwrap();
+
+        let err = result.unwrap_err();
+        assert!(err.matches(py, py.get_type::<exc::DeprecationWarning>()));
+        assert!(!err.matches(py, py.get_type::<exc::UserWarning>()));
+        assert!(!PyErr::occurred(py));
+    }
+
+    #[test]
+    fn format_traceback() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let err = PyErr::new::<exc::ValueError, _>(py, "no traceback");
+        assert_eq!(err.format_traceback(py).unwrap(), "ValueError: no traceback\n");
+
+        let err = py.run("def f():\n    raise KeyError('deep')\nf()\n", None, None).unwrap_err();
+        let text = err.format_traceback(py).unwrap();
+        assert!(text.starts_with("Traceback (most recent call last):\n"));
+        assert!(text.contains("in f"));
+        assert!(text.ends_with("KeyError: 'deep'\n"));
+    }
+
+    #[test]
+    fn matches() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let err = PyErr::new::<exc::KeyError, _>(py, "key");
+        assert!(err.matches(py, py.get_type::<exc::KeyError>()));
+        assert!(err.matches(py, py.get_type::<exc::LookupError>()));
+        assert!(!err.matches(py, py.get_type::<exc::ValueError>()));
+        assert!(err.matches(py, (py.get_type::<exc::ValueError>(), py.get_type::<exc::KeyError>())));
+        assert!(!err.matches(py, (py.get_type::<exc::ValueError>(), py.get_type::<exc::TypeError>())));
+
+        // matches() must not disturb the interpreter's error state
+        PyErr::new::<exc::TypeError, _>(py, "current").restore(py);
+        assert!(err.matches(py, py.get_type::<exc::KeyError>()));
+        assert!(PyErr::fetch(py).matches(py, py.get_type::<exc::TypeError>()));
+    }
+
+    #[test]
+    fn with_cause() {
+        use objects::PyDict;
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let cause = PyErr::new::<exc::KeyError, _>(py, "inner");
+        let mut err = PyErr::new::<exc::ValueError, _>(py, "outer").with_cause(py, cause);
+        let locals = PyDict::new(py);
+        locals.set_item(py, "e", err.instance(py)).unwrap();
+        py.run("try:\n    raise e\nexcept ValueError as x:\n    \
+                assert isinstance(x.__cause__, KeyError)\n    \
+                assert x.__cause__.args == ('inner',)\n", None, Some(&locals)).unwrap();
+    }
+
+    #[test]
+    fn chain_err() {
+        use std::fs::File;
+        use objectprotocol::ObjectProtocol;
+        use super::ResultExt;
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let mut err = File::open("/this/file/does/not/exist")
+            .chain_err(py, |py| PyErr::new::<exc::RuntimeError, _>(py, "cannot open"))
+            .unwrap_err();
+        assert!(err.matches(py, py.get_type::<exc::RuntimeError>()));
+        let cause = err.instance(py).getattr(py, "__cause__").unwrap();
+        assert!(py.get_type::<exc::OSError>().is_instance(py, &cause));
+        assert_eq!(42, Ok::<i32, PyErr>(42).chain_err(py, |_| panic!("not called")).unwrap());
+    }
+
+    #[test]
+    #[cfg(feature="python3-sys")]
+    fn new_chained() {
+        use objectprotocol::ObjectProtocol;
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        PyErr::new::<exc::KeyError, _>(py, "original").restore(py);
+        let mut err = PyErr::new_chained(py, PyErr::new::<exc::ValueError, _>(py, "handler failed"));
+        assert!(!PyErr::occurred(py));
+        let context = err.instance(py).getattr(py, "__context__").unwrap();
+        assert!(context.get_type(py) == py.get_type::<exc::KeyError>());
+    }
+
+    #[test]
+    fn io_error_not_found() {
+        use std::fs::File;
+        use objectprotocol::ObjectProtocol;
+
+        fn open() -> PyResult<File> {
+            Ok(File::open("/this/file/does/not/exist")?)
+        }
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let mut err = open().unwrap_err();
+        assert!(err.matches(py, py.get_type::<exc::OSError>()));
+        #[cfg(feature="python3-sys")]
+        assert!(err.matches(py, py.get_type::<exc::FileNotFoundError>()));
+        let errno: i32 = err.instance(py).getattr(py, "errno").unwrap().extract(py).unwrap();
+        assert_eq!(errno, ::libc::ENOENT);
+    }
+
+    #[test]
+    fn std_error_to_runtime_error() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let mut err = PyErr::from_std_error(py, "x".parse::<i32>().unwrap_err());
+        assert!(err.matches(py, py.get_type::<exc::RuntimeError>()));
+        assert_eq!(err.instance(py).to_string(), "invalid digit found in string");
+    }
 }
 
 
@@ -29,6 +29,21 @@ use err::{self, PyResult};
 #[macro_export]
 #[doc(hidden)]
 macro_rules! py_method_def {
+    ($name: expr, $flags: expr, $wrap: expr, signature: $sig: expr) => {{
+        let method_def = py_method_def!($name, $flags, $wrap);
+        if $crate::_detail::TEXT_SIGNATURES {
+            (*method_def).ml_doc = concat!($sig, "\0").as_ptr() as *const _;
+        }
+        method_def
+    }};
+    ($name: expr, $flags: expr, $wrap: expr, { }) => {
+        py_method_def!($name, $flags, $wrap)
+    };
+    ($name: expr, $flags: expr, $wrap: expr, { $doc0: expr $(, $doc: expr)* }) => {{
+        let method_def = py_method_def!($name, $flags, $wrap);
+        (*method_def).ml_doc = concat!($doc0, $("\n", $doc,)* "\0").as_ptr() as *const _;
+        method_def
+    }};
     ($name: expr, $flags: expr, $wrap: expr) => {{
         static mut METHOD_DEF: $crate::_detail::ffi::PyMethodDef = $crate::_detail::ffi::PyMethodDef {
             //ml_name: bytes!(stringify!($name), "\0"),
@@ -66,15 +81,22 @@ macro_rules! py_method_def {
 ///  * `py` must be an expression of type `Python`
 ///  * `f` must be the name of a function that is compatible with the specified
 ///    parameter list, except that a single parameter of type `Python` is prepended.
-///    The function must return `PyResult<T>` for some `T` that implements `ToPyObject`.
+///    The function must return `Result<T, E>` for some `T` that implements `ToPyObject`
+///    and some `E` that implements `Into<PyErr>` (usually `PyResult<T>`).
+///    An `Err` return value is raised as a Python exception; for example,
+///    a `std::io::Error` becomes an `OSError`.
 ///
 /// Form 2:
 ///
 ///  * `py` must be an identifier refers to a `Python` value.
 ///   The function body will also have access to a `Python` variable of this name.
 ///  * `f` must be an identifier.
-///  * The function return type must be `PyResult<T>` for some `T` that
-///   implements `ToPyObject`.
+///  * The function return type must be `Result<T, E>` for some `T` that
+///   implements `ToPyObject` and some `E` that implements `Into<PyErr>`.
+///
+/// On Python 3.8 and later, the function has a `__text_signature__` built from the parameter list,
+/// so `inspect.signature()` shows the declared parameter names.
+/// Default values are Rust expressions, so they are shown as `...`.
 ///
 /// # Example
 /// ```
@@ -124,13 +146,15 @@ macro_rules! py_fn_impl {
                     py_argparse_raw!(py, Some(_cpython__function__stringify!($f)), args, kwargs,
                         [ $( { $pname : $ptype = $detail } )* ]
                         {
-                            $f(py $(, $pname )* )
+                            $f(py $(, $pname )* ).map_err(::std::convert::Into::<$crate::PyErr>::into)
                         })
                 })
         }
         unsafe {
             $crate::_detail::py_fn_impl($py,
-                py_method_def!(_cpython__function__stringify!($f), 0, wrap))
+                py_method_def!(_cpython__function__stringify!($f), 0, wrap,
+                    signature: py_argparse_text_signature!(_cpython__function__stringify!($f),
+                        [ $( { $pname : $ptype = $detail } )* ])))
         }
     }};
     // Form 2: inline function definition
@@ -140,6 +164,13 @@ macro_rules! py_fn_impl {
     }}
 }
 
+/// Whether the interpreter reads `__text_signature__` from the start of `ml_doc`.
+/// Python 2 doesn't, and neither does Python 3.3.
+/// Before Python 3.8, `inspect` can't parse a `...` default and silently drops
+/// the parameter, so no signature is set there either.
+#[doc(hidden)]
+pub const TEXT_SIGNATURES: bool = cfg!(all(feature="python3-sys", Py_3_8));
+
 pub unsafe fn py_fn_impl(py: Python, method_def: *mut ffi::PyMethodDef) -> PyObject {
     err::from_owned_ptr_or_panic(py, ffi::PyCFunction_New(method_def, ptr::null_mut()))
 }
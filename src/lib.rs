@@ -97,12 +97,12 @@ extern crate python27_sys as ffi;
 extern crate python3_sys as ffi;
 
 pub use ffi::Py_ssize_t;
-pub use err::{PyErr, PyResult};
+pub use err::{PyErr, PyResult, ResultExt};
 pub use objects::*;
 pub use python::{Python, PythonObject, PythonObjectWithCheckedDowncast, PythonObjectDowncastError, PythonObjectWithTypeObject, PyClone, PyDrop};
-pub use pythonrun::{GILGuard, GILProtected, prepare_freethreaded_python};
-pub use conversion::{FromPyObject, RefFromPyObject, ToPyObject};
-pub use py_class::{CompareOp};
+pub use pythonrun::{GILGuard, ReleaseGIL, GILProtected, GILOnceCell, SubInterpreter, prepare_freethreaded_python};
+pub use conversion::{FromPyObject, RefFromPyObject, ToPyObject, PyConst};
+pub use py_class::{CompareOp, py_iterator};
 pub use objectprotocol::{ObjectProtocol};
 
 #[cfg(feature="python27-sys")]
@@ -206,7 +206,8 @@ pub mod _detail {
         pub use ::libc::{c_char, c_void, c_int};
     }
     pub use err::{from_owned_ptr_or_panic, result_from_owned_ptr};
-    pub use function::{handle_callback, py_fn_impl, AbortOnDrop,
+    pub use conversion::record_items;
+    pub use function::{handle_callback, py_fn_impl, AbortOnDrop, TEXT_SIGNATURES,
         PyObjectCallbackConverter, PythonObjectCallbackConverter};
 }
 
@@ -222,6 +223,21 @@ pub mod _detail {
 ///    This function will be called when the module is imported, and is responsible
 ///    for adding the module's members.
 ///
+/// Alternative syntax: `py_module_initializer!($name, $init_fn)`
+///
+/// 1. `name`: The module name as a Rust identifier.
+///    The `init$name` and `PyInit_$name` entry points are derived from it,
+///    so they can't get out of sync with the module name.
+/// 2. `init_fn`: Path to a function of type `fn(Python, &PyModule) -> PyResult<()>`.
+///
+/// This form requires Rust 1.54 or later (for `#[export_name = concat!(..)]`).
+/// On older compilers, spell out the entry points:
+/// `py_module_initializer!($name, $py2_init, $py3_init, $init_fn)`.
+///
+/// The generated entry point creates the module (`Py_InitModule` on Python 2,
+/// `PyModule_Create` on Python 3) and calls `init_fn`; errors are reported to Python, and
+/// a panic aborts the process instead of unwinding into the interpreter.
+///
 /// # Example
 /// ```
 /// #[macro_use] extern crate cpython;
@@ -279,7 +295,31 @@ macro_rules! py_module_initializer {
             let name = concat!(stringify!($name), "\0").as_ptr() as *const _;
             $crate::py_module_initializer_impl(name, init)
         }
-    }
+    };
+    ($name: ident, $py2: ident, $py3: ident, $init: path) => {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub unsafe extern "C" fn $py2() {
+            let name = concat!(stringify!($name), "\0").as_ptr() as *const _;
+            $crate::py_module_initializer_impl(name, $init)
+        }
+    };
+    ($name: ident, $init: path) => {
+        _cpython__module__entry_point!($name, $init);
+    };
+}
+
+#[macro_export]
+#[doc(hidden)]
+#[cfg(all(feature="python27-sys", export_name_concat))]
+macro_rules! _cpython__module__entry_point {
+    ($name: ident, $init: path) => {
+        #[export_name = concat!("init", stringify!($name))]
+        pub unsafe extern "C" fn __cpython_module_entry_point() {
+            let name = concat!(stringify!($name), "\0").as_ptr() as *const _;
+            $crate::py_module_initializer_impl(name, $init)
+        }
+    };
 }
 
 
@@ -331,7 +371,44 @@ macro_rules! py_module_initializer {
             MODULE_DEF.m_name = concat!(stringify!($name), "\0").as_ptr() as *const _;
             $crate::py_module_initializer_impl(&mut MODULE_DEF, init)
         }
-    }
+    };
+    ($name: ident, $py2: ident, $py3: ident, $init: path) => {
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub unsafe extern "C" fn $py3() -> *mut $crate::_detail::ffi::PyObject {
+            static mut MODULE_DEF: $crate::_detail::ffi::PyModuleDef = $crate::_detail::ffi::PyModuleDef_INIT;
+            MODULE_DEF.m_name = concat!(stringify!($name), "\0").as_ptr() as *const _;
+            $crate::py_module_initializer_impl(&mut MODULE_DEF, $init)
+        }
+    };
+    ($name: ident, $init: path) => {
+        _cpython__module__entry_point!($name, $init);
+    };
+}
+
+#[macro_export]
+#[doc(hidden)]
+#[cfg(all(feature="python3-sys", export_name_concat))]
+macro_rules! _cpython__module__entry_point {
+    ($name: ident, $init: path) => {
+        #[export_name = concat!("PyInit_", stringify!($name))]
+        pub unsafe extern "C" fn __cpython_module_entry_point() -> *mut $crate::_detail::ffi::PyObject {
+            static mut MODULE_DEF: $crate::_detail::ffi::PyModuleDef = $crate::_detail::ffi::PyModuleDef_INIT;
+            MODULE_DEF.m_name = concat!(stringify!($name), "\0").as_ptr() as *const _;
+            $crate::py_module_initializer_impl(&mut MODULE_DEF, $init)
+        }
+    };
+}
+
+#[macro_export]
+#[doc(hidden)]
+#[cfg(not(export_name_concat))]
+macro_rules! _cpython__module__entry_point {
+    ($name: ident, $init: path) => {
+        compile_error!(concat!("py_module_initializer!(", stringify!($name), ", init_fn) requires Rust 1.54; \
+            use py_module_initializer!(", stringify!($name), ", init", stringify!($name),
+            ", PyInit_", stringify!($name), ", init_fn) instead"));
+    };
 }
 
 #[doc(hidden)]
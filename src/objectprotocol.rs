@@ -18,17 +18,22 @@
 
 use std::fmt;
 use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::ptr;
 use ffi;
 use libc;
-use python::{Python, PythonObject, ToPythonPointer};
-use objects::{PyObject, PyTuple, PyDict, PyString};
+use python::{Python, PythonObject, ToPythonPointer, PyClone, PyDrop};
+use objects::{PyObject, PyTuple, PyDict, PyList, PySet, PyString, PySequence, PyMapping};
 use conversion::ToPyObject;
 use err::{PyErr, PyResult, self};
+use exc;
 
 /// Trait that contains methods
 pub trait ObjectProtocol : PythonObject {
     /// Determines whether this object has the given attribute.
     /// This is equivalent to the Python expression 'hasattr(self, attr_name)'.
+    ///
+    /// Errors raised while looking up the attribute are swallowed and result in `Ok(false)`.
     #[inline]
     fn hasattr<N>(&self, py: Python, attr_name: N) -> PyResult<bool> where N: ToPyObject {
         attr_name.with_borrowed_ptr(py, |attr_name| unsafe {
@@ -38,6 +43,8 @@ pub trait ObjectProtocol : PythonObject {
 
     /// Retrieves an attribute value.
     /// This is equivalent to the Python expression 'self.attr_name'.
+    ///
+    /// If the attribute does not exist, an `AttributeError` is returned.
     #[inline]
     fn getattr<N>(&self, py: Python, attr_name: N) -> PyResult<PyObject> where N: ToPyObject {
         attr_name.with_borrowed_ptr(py, |attr_name| unsafe {
@@ -46,6 +53,55 @@ pub trait ObjectProtocol : PythonObject {
         })
     }
 
+    /// Retrieves an attribute by following a path of attribute names.
+    /// This is equivalent to the Python expression 'self.path[0].path[1]...'.
+    ///
+    /// The lookup stops at the first segment that cannot be retrieved.
+    /// An `AttributeError` names that segment and the path up to it;
+    /// other errors are returned unchanged.
+    /// An empty path returns self.
+    fn getattr_path(&self, py: Python, path: &[&str]) -> PyResult<PyObject> {
+        let mut obj = self.as_object().clone_ref(py);
+        for (i, &name) in path.iter().enumerate() {
+            let next = match obj.getattr(py, name) {
+                Ok(next) => next,
+                Err(mut e) => {
+                    obj.release_ref(py);
+                    if !e.matches(py, py.get_type::<exc::AttributeError>()) {
+                        return Err(e);
+                    }
+                    let msg = match e.instance(py).str(py) {
+                        Ok(s) => s.to_string_lossy(py).into_owned(),
+                        Err(_) => String::new()
+                    };
+                    return Err(PyErr::new::<exc::AttributeError, _>(py, format!(
+                        "{} (looking up '{}', failed at '{}')", msg, path[..i + 1].join("."), name)));
+                }
+            };
+            obj.release_ref(py);
+            obj = next;
+        }
+        Ok(obj)
+    }
+
+    /// Retrieves an attribute value, calling it if it is callable and `call_callables` is true.
+    ///
+    /// This is useful for duck-typed protocols where an attribute may either be
+    /// a plain value or a zero-argument callable producing the value.
+    /// If `call_callables` is false, this is equivalent to `getattr()`.
+    fn getattr_resolved<N>(&self, py: Python, attr_name: N, call_callables: bool) -> PyResult<PyObject>
+        where N: ToPyObject
+    {
+        let attr = self.getattr(py, attr_name)?;
+        if call_callables && attr.is_callable(py) {
+            let result = attr.call(py, PyTuple::empty(py), None);
+            attr.release_ref(py);
+            result
+        } else {
+            Ok(attr)
+        }
+    }
+
     /// Sets an attribute value.
     /// This is equivalent to the Python expression 'self.attr_name = value'.
     #[inline]
@@ -69,6 +125,22 @@ pub trait ObjectProtocol : PythonObject {
         })
     }
 
+    /// Retrieves an attribute value from the type of self, bypassing the instance dict.
+    ///
+    /// This searches the MRO of `type(self)` like CPython does when looking up
+    /// special methods, so the result cannot be shadowed by instance attributes.
+    /// The attribute is returned as stored in the class; descriptors are not bound to self.
+    /// See also `PyType::lookup()`.
+    fn get_type_attr<N>(&self, py: Python, attr_name: N) -> PyResult<PyObject> where N: ToPyObject {
+        let attr_name = attr_name.into_py_object(py).into_object();
+        if let Some(attr) = self.as_object().get_type(py).lookup(py, &attr_name) {
+            return Ok(attr);
+        }
+        let msg = format!("type object '{}' has no attribute '{}'",
+            self.as_object().get_type(py).name(py), attr_name.str(py)?.to_string_lossy(py));
+        Err(PyErr::new::<exc::AttributeError, _>(py, msg))
+    }
+
     /// Compares two Python objects.
     ///
     /// On Python 2, this is equivalent to the Python expression 'cmp(self, other)'.
@@ -77,13 +149,29 @@ pub trait ObjectProtocol : PythonObject {
     /// ```ignore
     /// if self == other:
     ///     return Equal
-    /// elif a < b:
+    /// elif self < other:
     ///     return Less
-    /// elif a > b:
+    /// elif self > other:
     ///     return Greater
     /// else:
     ///     raise TypeError("ObjectProtocol::compare(): All comparisons returned false")
     /// ```
+    ///
+    /// Like Python's own containers, an object is always considered equal to itself,
+    /// so this returns `Equal` for two references to the same `float('nan')`.
+    /// On Python 3, for a partial order, where none of the comparisons hold (e.g. a NaN and
+    /// another float), this fails with `TypeError`; so does comparing unorderable
+    /// types like `int` and `str`. Python 2's `cmp()` returns an ordering in both cases
+    /// (unrelated types are ordered by type name).
+    /// This makes it suitable for `sort_by()` on values that are known to be totally ordered:
+    ///
+    /// ```
+    /// use cpython::{Python, ObjectProtocol, ToPyObject, PythonObject};
+    /// let gil = Python::acquire_gil();
+    /// let py = gil.python();
+    /// let mut v: Vec<_> = [3i32, 1, 2].iter().map(|i| i.to_py_object(py).into_object()).collect();
+    /// v.sort_by(|a, b| a.compare(py, b).unwrap());
+    /// ```
     fn compare<O>(&self, py: Python, other: O) -> PyResult<Ordering> where O: ToPyObject {
         #[cfg(feature="python27-sys")]
         unsafe fn do_compare(py: Python, a: *mut ffi::PyObject, b: *mut ffi::PyObject) -> PyResult<Ordering> {
@@ -142,6 +230,61 @@ pub trait ObjectProtocol : PythonObject {
         })
     }
 
+    /// Compares two Python objects and returns the truth value of the result.
+    ///
+    /// This wraps `PyObject_RichCompareBool`, so reflected operations and custom
+    /// comparison methods are respected. Note that for `CompareOp::Eq` and `CompareOp::Ne`,
+    /// identical objects compare equal without calling `__eq__`.
+    fn rich_compare_bool<O>(&self, py: Python, other: O, compare_op: ::CompareOp) -> PyResult<bool> where O: ToPyObject {
+        other.with_borrowed_ptr(py, |other| unsafe {
+            let result = ffi::PyObject_RichCompareBool(self.as_ptr(), other, compare_op as libc::c_int);
+            if result < 0 {
+                Err(PyErr::fetch(py))
+            } else {
+                Ok(result != 0)
+            }
+        })
+    }
+
+    /// This is equivalent to the Python expression 'self == other'.
+    ///
+    /// This is not called `eq` because that would clash with the `PartialEq`
+    /// implementation of `PyObject`, which compares object identity.
+    #[inline]
+    fn equals<O>(&self, py: Python, other: O) -> PyResult<bool> where O: ToPyObject {
+        self.rich_compare_bool(py, other, ::CompareOp::Eq)
+    }
+
+    /// This is equivalent to the Python expression 'self != other'.
+    #[inline]
+    fn not_equals<O>(&self, py: Python, other: O) -> PyResult<bool> where O: ToPyObject {
+        self.rich_compare_bool(py, other, ::CompareOp::Ne)
+    }
+
+    /// This is equivalent to the Python expression 'self < other'.
+    #[inline]
+    fn lt<O>(&self, py: Python, other: O) -> PyResult<bool> where O: ToPyObject {
+        self.rich_compare_bool(py, other, ::CompareOp::Lt)
+    }
+
+    /// This is equivalent to the Python expression 'self <= other'.
+    #[inline]
+    fn le<O>(&self, py: Python, other: O) -> PyResult<bool> where O: ToPyObject {
+        self.rich_compare_bool(py, other, ::CompareOp::Le)
+    }
+
+    /// This is equivalent to the Python expression 'self > other'.
+    #[inline]
+    fn gt<O>(&self, py: Python, other: O) -> PyResult<bool> where O: ToPyObject {
+        self.rich_compare_bool(py, other, ::CompareOp::Gt)
+    }
+
+    /// This is equivalent to the Python expression 'self >= other'.
+    #[inline]
+    fn ge<O>(&self, py: Python, other: O) -> PyResult<bool> where O: ToPyObject {
+        self.rich_compare_bool(py, other, ::CompareOp::Ge)
+    }
+
     /// Compute the string representation of self.
     /// This is equivalent to the Python expression 'repr(self)'.
     #[inline]
@@ -160,6 +303,30 @@ pub trait ObjectProtocol : PythonObject {
         }
     }
 
+    /// Compute the string representation of self as a Rust `String`.
+    /// This is equivalent to the Python expression 'repr(self)'.
+    ///
+    /// Fails if the representation cannot be decoded to UTF-8.
+    #[inline]
+    fn repr_string(&self, py: Python) -> PyResult<String> {
+        let repr = self.repr(py)?;
+        let result = repr.to_string(py).map(|s| s.into_owned());
+        repr.release_ref(py);
+        result
+    }
+
+    /// Compute the string representation of self as a Rust `String`.
+    /// This is equivalent to the Python expression 'str(self)'.
+    ///
+    /// Fails if the string cannot be decoded to UTF-8.
+    #[inline]
+    fn str_string(&self, py: Python) -> PyResult<String> {
+        let s = self.str(py)?;
+        let result = s.to_string(py).map(|s| s.into_owned());
+        s.release_ref(py);
+        result
+    }
+
     /// Compute the unicode string representation of self.
     /// This is equivalent to the Python expression 'unistr(self)'.
     #[inline]
@@ -194,6 +361,68 @@ pub trait ObjectProtocol : PythonObject {
         })
     }
 
+    /// Calls the object without any arguments.
+    /// This is equivalent to the Python expression: 'self()'
+    ///
+    /// Unlike `call()`, this does not need to allocate an argument tuple.
+    #[inline]
+    fn call0(&self, py: Python) -> PyResult<PyObject> {
+        unsafe {
+            err::result_from_owned_ptr(py, ffi::PyObject_CallObject(self.as_ptr(), ptr::null_mut()))
+        }
+    }
+
+    /// Calls the object with a single positional argument.
+    /// This is equivalent to the Python expression: 'self(arg)'
+    ///
+    /// Unlike `call()`, `arg` does not need to be wrapped in a one-element Rust tuple.
+    #[inline]
+    fn call1<A>(&self, py: Python, arg: A) -> PyResult<PyObject> where A: ToPyObject {
+        arg.with_borrowed_ptr(py, |arg| unsafe {
+            err::result_from_owned_ptr(py,
+                ffi::PyObject_CallFunctionObjArgs(self.as_ptr(), arg, ptr::null_mut::<ffi::PyObject>()))
+        })
+    }
+
+    /// Calls the object for its side effects only.
+    /// This is equivalent to the Python statement: 'self(*args, **kwargs)'
+    ///
+    /// The result of the call is released immediately; only errors are returned.
+    #[inline]
+    fn call_discard<A>(&self, py: Python, args: A, kwargs: Option<&PyDict>) -> PyResult<()>
+        where A: ToPyObject<ObjectType=PyTuple>
+    {
+        self.call(py, args, kwargs)?.release_ref(py);
+        Ok(())
+    }
+
+    /// Calls the object with keyword arguments given as `(name, value)` pairs.
+    /// This is equivalent to the Python expression: 'self(*args, name=value, ...)'
+    ///
+    /// The keyword arguments dict is built internally; if there are no pairs,
+    /// no dict is passed at all (as with `call(py, args, None)`).
+    ///
+    /// # Example
+    /// ```
+    /// use cpython::{Python, NoArgs, ObjectProtocol};
+    /// let gil = Python::acquire_gil();
+    /// let py = gil.python();
+    /// let dict = py.eval("dict", None, None).unwrap();
+    /// let d = dict.call_with_kwargs(py, NoArgs, vec![("a", 1), ("b", 2)]).unwrap();
+    /// assert_eq!(d.get_item(py, "b").unwrap().extract::<i32>(py).unwrap(), 2);
+    /// ```
+    fn call_with_kwargs<'a, A, I, V>(&self, py: Python, args: A, kwargs: I) -> PyResult<PyObject>
+        where A: ToPyObject<ObjectType=PyTuple>,
+              I: IntoIterator<Item=(&'a str, V)>,
+              V: ToPyObject
+    {
+        let mut dict = None;
+        for (name, value) in kwargs {
+            dict.get_or_insert_with(|| PyDict::new(py)).set_item(py, name, value)?;
+        }
+        self.call(py, args, dict.as_ref())
+    }
+
     /// Calls a method on the object.
     /// This is equivalent to the Python expression: 'self.name(*args, **kwargs)'
     ///
@@ -202,6 +431,9 @@ pub trait ObjectProtocol : PythonObject {
     ///  * `cpython::NoArgs` when calling a method without any arguments
     ///  * otherwise, a Rust tuple with 1 or more elements
     ///
+    /// `name` is usually a `&str`; when calling the same method repeatedly,
+    /// pass a `PyString` created by `Python::intern()` to avoid re-creating the name.
+    ///
     /// # Example
     /// ```no_run
     /// use cpython::{NoArgs, ObjectProtocol};
@@ -215,14 +447,28 @@ pub trait ObjectProtocol : PythonObject {
     /// obj.call_method(py, "method1", (true,), None).unwrap();
     /// ```
     #[inline]
-    fn call_method<A>(&self, py: Python, name: &str, args: A, kwargs: Option<&PyDict>) -> PyResult<PyObject>
-        where A: ToPyObject<ObjectType=PyTuple>
+    fn call_method<N, A>(&self, py: Python, name: N, args: A, kwargs: Option<&PyDict>) -> PyResult<PyObject>
+        where N: ToPyObject, A: ToPyObject<ObjectType=PyTuple>
     {
         self.getattr(py, name)?.call(py, args, kwargs)
     }
 
+    /// Calls a method on the object without any arguments.
+    /// This is equivalent to the Python expression: 'self.name()'
+    ///
+    /// Unlike `call_method()`, this does not need to allocate an argument tuple.
+    #[inline]
+    fn call_method0<N>(&self, py: Python, name: N) -> PyResult<PyObject> where N: ToPyObject {
+        name.with_borrowed_ptr(py, |name| unsafe {
+            err::result_from_owned_ptr(py,
+                ffi::PyObject_CallMethodObjArgs(self.as_ptr(), name, ptr::null_mut::<ffi::PyObject>()))
+        })
+    }
+
     /// Retrieves the hash code of the object.
     /// This is equivalent to the Python expression: 'hash(self)'
+    ///
+    /// Fails with `TypeError` if the object is unhashable.
     #[inline]
     fn hash(&self, py: Python) -> PyResult<::Py_hash_t> {
         let v = unsafe { ffi::PyObject_Hash(self.as_ptr()) };
@@ -257,7 +503,30 @@ pub trait ObjectProtocol : PythonObject {
         }
     }
 
+    /// Returns the list of attribute names of the object.
+    /// This is equivalent to the Python expression: 'dir(self)'
+    ///
+    /// Works on modules, types, and instances alike, with the semantics of `dir()`:
+    /// the default implementation returns a sorted list that, for a type, includes
+    /// the attributes of its base classes; a custom `__dir__` may return any names.
+    #[inline]
+    fn dir(&self, py: Python) -> PyResult<PyList> {
+        unsafe {
+            err::result_cast_from_owned_ptr(py, ffi::PyObject_Dir(self.as_ptr()))
+        }
+    }
+
+    /// Returns the attribute names of the object as Rust strings.
+    /// This is equivalent to `dir()`, but extracts the names to `Vec<String>`.
+    #[inline]
+    fn get_attr_names(&self, py: Python) -> PyResult<Vec<String>> {
+        self.dir(py)?.into_object().extract(py)
+    }
+
     /// This is equivalent to the Python expression: 'self[key]'
+    ///
+    /// A missing key raises the container's own error,
+    /// e.g. `KeyError` for mappings or `IndexError` for sequences.
     #[inline]
     fn get_item<K>(&self, py: Python, key: K) -> PyResult<PyObject> where K: ToPyObject {
         key.with_borrowed_ptr(py, |key| unsafe {
@@ -297,17 +566,111 @@ pub trait ObjectProtocol : PythonObject {
         }?;
         Ok(::objects::PyIterator::from_object(py, obj)?)
     }
+
+    /// Iterates over the object, extracting each item to `T` as it is retrieved.
+    ///
+    /// Unlike extracting a `Vec<T>`, this does not materialize all items at once.
+    /// If the iteration raises an exception or an item cannot be extracted,
+    /// the iterator yields `Err` and then stops.
+    #[inline]
+    fn iter_extract<'p, T>(&self, py: Python<'p>) -> PyResult<::objects::PyExtractIterator<'p, T>>
+        where T: for<'a> ::conversion::FromPyObject<'a>
+    {
+        Ok(self.iter(py)?.extract_items())
+    }
+
+    /// Gets a buffer view of the memory exported by self through the buffer protocol.
+    ///
+    /// Fails with a `TypeError` if self does not support the buffer protocol.
+    #[inline]
+    fn as_buffer(&self, py: Python) -> PyResult<::buffer::PyBuffer> {
+        ::buffer::PyBuffer::get(py, self.as_object())
+    }
+
+    /// Returns a view of self through the sequence protocol.
+    ///
+    /// Fails with a `TypeError` if self does not support the sequence protocol
+    /// (that is, `PySequence_Check` returns false).
+    #[inline]
+    fn as_sequence(&self, py: Python) -> PyResult<PySequence> {
+        Ok(self.as_object().clone_ref(py).cast_into::<PySequence>(py)?)
+    }
+
+    /// Returns a view of self through the mapping protocol.
+    ///
+    /// Fails with a `TypeError` if self does not support the mapping protocol
+    /// (that is, `PyMapping_Check` returns false).
+    #[inline]
+    fn as_mapping(&self, py: Python) -> PyResult<PyMapping> {
+        Ok(self.as_object().clone_ref(py).cast_into::<PyMapping>(py)?)
+    }
+
+    /// Submits the coroutine self to the asyncio event loop `loop_obj`.
+    /// This is equivalent to the Python expression:
+    /// 'asyncio.run_coroutine_threadsafe(self, loop_obj)'
+    ///
+    /// The loop is expected to be running on another thread.
+    /// Returns a `concurrent.futures.Future` whose `result()` can be waited on
+    /// from the calling thread.
+    fn schedule_on_loop(&self, py: Python, loop_obj: &PyObject) -> PyResult<PyObject> {
+        py.import("asyncio")?.call(py, "run_coroutine_threadsafe", (self.as_object(), loop_obj), None)
+    }
+
+    /// Estimates the memory footprint of self and all objects reachable through it.
+    ///
+    /// This sums up `sys.getsizeof()` over self and, recursively, over the contents
+    /// of lists, tuples, dicts (keys and values), and sets.
+    /// Each object is counted only once, so shared references and cycles are handled.
+    fn deep_size(&self, py: Python) -> PyResult<usize> {
+        let getsizeof = py.import("sys")?.get(py, "getsizeof")?;
+        let mut visited = HashSet::new();
+        deep_size_helper(py, &getsizeof, self.as_object(), &mut visited)
+    }
+}
+
+fn deep_size_helper(py: Python, getsizeof: &PyObject, obj: &PyObject,
+                    visited: &mut HashSet<*mut ffi::PyObject>) -> PyResult<usize> {
+    if !visited.insert(obj.as_ptr()) {
+        return Ok(0);
+    }
+    let mut size: usize = getsizeof.call(py, (obj,), None)?.extract(py)?;
+    if let Ok(list) = obj.cast_as::<PyList>(py) {
+        for item in list.iter(py) {
+            size += deep_size_helper(py, getsizeof, &item, visited)?;
+        }
+    } else if let Ok(tuple) = obj.cast_as::<PyTuple>(py) {
+        for item in tuple.as_slice(py) {
+            size += deep_size_helper(py, getsizeof, item, visited)?;
+        }
+    } else if let Ok(dict) = obj.cast_as::<PyDict>(py) {
+        for (key, value) in dict.items(py) {
+            size += deep_size_helper(py, getsizeof, &key, visited)?;
+            size += deep_size_helper(py, getsizeof, &value, visited)?;
+        }
+    } else if obj.cast_as::<PySet>(py).is_ok() {
+        for item in obj.iter(py)? {
+            size += deep_size_helper(py, getsizeof, &item?, visited)?;
+        }
+    }
+    Ok(size)
 }
 
 impl ObjectProtocol for PyObject {}
 
+/// Formats the object using Python's `repr()`.
+///
+/// The GIL is acquired for the duration of the call (this is a no-op if the
+/// current thread already holds it).
+/// If `repr()` raises an exception, the exception is discarded and the object
+/// is printed as `<typename object at 0x...>` instead.
 impl fmt::Debug for PyObject {
     fn fmt(&self, f : &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        // TODO: we shouldn't use fmt::Error when repr() fails
         let gil_guard = Python::acquire_gil();
         let py = gil_guard.python();
-        let repr_obj = self.repr(py).map_err(|_| fmt::Error)?;
-        f.write_str(&repr_obj.to_string_lossy(py))
+        match self.repr(py) {
+            Ok(repr_obj) => f.write_str(&repr_obj.to_string_lossy(py)),
+            Err(_) => write!(f, "<{} object at {:p}>", self.get_type(py).name(py), self.as_ptr())
+        }
     }
 }
 
@@ -326,7 +689,8 @@ mod test {
     use std;
     use python::{Python, PythonObject};
     use conversion::ToPyObject;
-    use objects::{PyList, PyTuple};
+    use objects::{PyList, PyTuple, PyDict, NoArgs, exc};
+    use err::PyErr;
     use super::ObjectProtocol;
 
     #[test]
@@ -337,6 +701,25 @@ mod test {
         assert_eq!(format!("{:?}", v), "'Hello\\n'");
     }
 
+    #[test]
+    fn test_debug_list() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let list = vec![1, 2, 3].to_py_object(py);
+        assert_eq!(format!("{:?}", list), "[1, 2, 3]");
+        assert_eq!(format!("{:?}", list.into_object()), "[1, 2, 3]");
+    }
+
+    #[test]
+    fn test_debug_repr_fails() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let obj = py.eval("type('BadRepr', (), {'__repr__': lambda s: 1/0})()", None, None).unwrap();
+        let s = format!("{:?}", obj);
+        assert!(s.starts_with("<BadRepr object at 0x"), "{}", s);
+        assert!(!PyErr::occurred(py));
+    }
+
     #[test]
     fn test_display_string() {
         let gil = Python::acquire_gil();
@@ -355,5 +738,344 @@ mod test {
         assert_eq!(one.compare(py, 2).unwrap(), Ordering::Less);
         assert_eq!(one.compare(py, 0).unwrap(), Ordering::Greater);
     }
-}
 
+    #[test]
+    fn test_compare_sort() {
+        use std::cmp::Ordering;
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let mut ints: Vec<_> = [3i32, -1, 2, 10].iter().map(|i| i.to_py_object(py).into_object()).collect();
+        ints.sort_by(|a, b| a.compare(py, b).unwrap());
+        assert_eq!(ints.iter().map(|o| o.extract::<i32>(py).unwrap()).collect::<Vec<_>>(), vec![-1, 2, 3, 10]);
+
+        let mut strs: Vec<_> = ["b", "a", "ab"].iter().map(|s| s.to_py_object(py).into_object()).collect();
+        strs.sort_by(|a, b| a.compare(py, b).unwrap());
+        assert_eq!(strs.iter().map(|o| o.extract::<String>(py).unwrap()).collect::<Vec<_>>(), vec!["a", "ab", "b"]);
+
+        // equal but not identical
+        let big = py.eval("10 ** 30", None, None).unwrap();
+        let big2 = py.eval("10 ** 29 * 10", None, None).unwrap();
+        assert!(big.as_ptr() != big2.as_ptr());
+        assert_eq!(big.compare(py, &big2).unwrap(), Ordering::Equal);
+        assert_eq!(1i32.to_py_object(py).into_object().compare(py, 1.0).unwrap(), Ordering::Equal);
+
+        // partial and missing orders
+        let nan = py.eval("float('nan')", None, None).unwrap();
+        assert_eq!(nan.compare(py, &nan).unwrap(), Ordering::Equal);
+        #[cfg(feature="python3-sys")]
+        {
+            let err = nan.compare(py, 1.0).unwrap_err();
+            assert!(err.matches(py, py.get_type::<exc::TypeError>()));
+            let err = ints[0].compare(py, &strs[0]).unwrap_err();
+            assert!(err.matches(py, py.get_type::<exc::TypeError>()));
+        }
+        #[cfg(feature="python27-sys")]
+        assert_eq!(ints[0].compare(py, &strs[0]).unwrap(), Ordering::Less);
+    }
+
+    #[test]
+    fn test_get_type_attr() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let d = PyDict::new(py);
+        py.run("class C(object):\n    def f(self): return 1\nobj = C()\nobj.f = 2\n", None, Some(&d)).unwrap();
+        let cls = d.get_item(py, "C").unwrap();
+        let obj = d.get_item(py, "obj").unwrap();
+        assert_eq!(2, obj.getattr(py, "f").unwrap().extract::<i32>(py).unwrap());
+        let f = obj.get_type_attr(py, "f").unwrap();
+        assert!(f == cls.getattr(py, "__dict__").unwrap().get_item(py, "f").unwrap());
+        assert!(f.is_callable(py));
+        assert!(obj.get_type_attr(py, "missing").is_err());
+    }
+
+    #[test]
+    fn test_deep_size() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let d = PyDict::new(py);
+        py.run("obj = [[1, 2, 3], {'key': 'x' * 1000}, (4.5,)]\ncycle = [1]\ncycle.append(cycle)\n",
+               Some(&d), None).unwrap();
+        let obj = d.get_item(py, "obj").unwrap();
+        let getsizeof = py.import("sys").unwrap().get(py, "getsizeof").unwrap();
+        let shallow: usize = getsizeof.call(py, (&obj,), None).unwrap().extract(py).unwrap();
+        let deep = obj.deep_size(py).unwrap();
+        assert!(deep > shallow + 1000);
+
+        let cycle = d.get_item(py, "cycle").unwrap();
+        assert!(cycle.deep_size(py).unwrap() > 0);
+    }
+
+    #[test]
+    fn test_call_method() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let list = PyList::new(py, &[]).into_object();
+        list.call_method(py, "append", (1,), None).unwrap();
+        list.call_method(py, "append", (2,), None).unwrap();
+        assert_eq!(vec![1, 2], list.extract::<Vec<i32>>(py).unwrap());
+        assert_eq!(2, list.call_method0(py, "pop").unwrap().extract::<i32>(py).unwrap());
+        assert_eq!(vec![1], list.extract::<Vec<i32>>(py).unwrap());
+        assert!(list.call_method0(py, "missing").is_err());
+        assert!(list.call_method(py, "append", NoArgs, None).is_err());
+    }
+
+    #[test]
+    fn test_call0_call1() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let d = PyDict::new(py);
+        py.run("def zero(): return 42\ndef one(x): return [x]", Some(&d), None).unwrap();
+        let zero = d.get_item(py, "zero").unwrap();
+        let one = d.get_item(py, "one").unwrap();
+        assert_eq!(42, zero.call0(py).unwrap().extract::<i32>(py).unwrap());
+        assert_eq!(vec![7], one.call1(py, 7i32).unwrap().extract::<Vec<i32>>(py).unwrap());
+        // a tuple is passed as a single argument, not unpacked
+        assert_eq!(vec![(1, 2)], one.call1(py, (1, 2)).unwrap().extract::<Vec<(i32, i32)>>(py).unwrap());
+        assert!(zero.call1(py, 1i32).is_err());
+        assert!(one.call0(py).is_err());
+    }
+
+    #[test]
+    fn test_call_discard() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let list = PyList::new(py, &[]);
+        let append = list.as_object().getattr(py, "append").unwrap();
+        append.call_discard(py, (42,), None).unwrap();
+        assert_eq!(vec![42], list.as_object().extract::<Vec<i32>>(py).unwrap());
+        assert!(append.call_discard(py, NoArgs, None).is_err());
+    }
+
+    #[test]
+    fn test_call_with_kwargs() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let dict = py.eval("dict", None, None).unwrap();
+        let d = dict.call_with_kwargs(py, NoArgs, vec![("a", 1), ("b", 2)]).unwrap();
+        assert_eq!(py.eval("dict(a=1, b=2)", None, None).unwrap().compare(py, &d).unwrap(),
+                   std::cmp::Ordering::Equal);
+
+        // without keyword arguments, no dict is passed
+        let len = py.eval("len", None, None).unwrap();
+        let no_kwargs: Vec<(&str, i32)> = Vec::new();
+        let n = len.call_with_kwargs(py, (vec![1, 2, 3],), no_kwargs).unwrap();
+        assert_eq!(n.extract::<usize>(py).unwrap(), 3);
+        assert!(len.call_with_kwargs(py, (vec![1],), vec![("x", 1)]).is_err());
+    }
+
+    #[test]
+    fn test_attr_access() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let d = PyDict::new(py);
+        py.run("class C(object): pass\nobj = C()\n", None, Some(&d)).unwrap();
+        let obj = d.get_item(py, "obj").unwrap();
+        assert!(!obj.hasattr(py, "foo").unwrap());
+        let err = obj.getattr(py, "foo").unwrap_err();
+        assert!(err.matches(py, py.get_type::<exc::AttributeError>()));
+
+        obj.setattr(py, "foo", 42).unwrap();
+        assert!(obj.hasattr(py, "foo").unwrap());
+        assert_eq!(42, obj.getattr(py, "foo").unwrap().extract::<i32>(py).unwrap());
+
+        obj.delattr(py, "foo").unwrap();
+        assert!(!obj.hasattr(py, "foo").unwrap());
+        assert!(obj.delattr(py, "foo").is_err());
+    }
+
+    #[test]
+    fn test_str_repr_string() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let v = "Hello\n".to_py_object(py).into_object();
+        assert_eq!(v.str_string(py).unwrap(), "Hello\n");
+        assert_eq!(v.repr_string(py).unwrap(), "'Hello\\n'");
+        let v = vec![1, 2].to_py_object(py).into_object();
+        assert_eq!(v.str_string(py).unwrap(), "[1, 2]");
+        assert_eq!(v.repr_string(py).unwrap(), "[1, 2]");
+    }
+
+    #[test]
+    fn test_is_true() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        assert!(!py.None().is_true(py).unwrap());
+        assert!(!0i32.to_py_object(py).into_object().is_true(py).unwrap());
+        assert!(1i32.to_py_object(py).into_object().is_true(py).unwrap());
+        assert!(!PyList::new(py, &[]).into_object().is_true(py).unwrap());
+        assert!("x".to_py_object(py).into_object().is_true(py).unwrap());
+    }
+
+    #[test]
+    fn test_getattr_resolved() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let d = PyDict::new(py);
+        py.run("class C(object):\n    plain = 1\n    def computed(self): return 2\nobj = C()\n",
+               None, Some(&d)).unwrap();
+        let obj = d.get_item(py, "obj").unwrap();
+        assert_eq!(1, obj.getattr_resolved(py, "plain", true).unwrap().extract::<i32>(py).unwrap());
+        assert_eq!(2, obj.getattr_resolved(py, "computed", true).unwrap().extract::<i32>(py).unwrap());
+        assert!(obj.getattr_resolved(py, "computed", false).unwrap().is_callable(py));
+        assert!(obj.getattr_resolved(py, "missing", true).is_err());
+    }
+
+    #[test]
+    fn test_getattr_path() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let os = py.import("os").unwrap();
+        let os = os.as_object();
+        let join = os.getattr_path(py, &["path", "join"]).unwrap();
+        assert!(join == py.import("os.path").unwrap().get(py, "join").unwrap());
+        assert!(os.getattr_path(py, &[]).unwrap() == *os);
+
+        let mut err = os.getattr_path(py, &["path", "jion", "x"]).unwrap_err();
+        assert!(err.matches(py, py.get_type::<exc::AttributeError>()));
+        let msg = err.instance(py).str(py).unwrap().to_string_lossy(py).into_owned();
+        assert!(msg.contains("'path.jion'"), "{}", msg);
+        assert!(msg.contains("failed at 'jion'"), "{}", msg);
+
+        // errors other than AttributeError are passed through
+        let d = PyDict::new(py);
+        py.run("class C(object):\n    @property\n    def broken(self): raise KeyError('k')\nobj = C()\n",
+               None, Some(&d)).unwrap();
+        let obj = d.get_item(py, "obj").unwrap();
+        assert!(obj.getattr_path(py, &["broken", "x"]).unwrap_err().matches(py, py.get_type::<exc::KeyError>()));
+    }
+
+    #[test]
+    fn test_rich_compare_bool() {
+        use CompareOp;
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let one = 1i32.to_py_object(py).into_object();
+        let two = 2i32.to_py_object(py).into_object();
+        assert!(one.lt(py, &two).unwrap());
+        assert!(one.le(py, &two).unwrap());
+        assert!(!one.gt(py, &two).unwrap());
+        assert!(two.ge(py, 2i32).unwrap());
+        assert!(one.equals(py, 1.0f64).unwrap());
+        assert!(one.not_equals(py, &two).unwrap());
+        assert!(one.rich_compare_bool(py, &two, CompareOp::Lt).unwrap());
+
+        let d = PyDict::new(py);
+        py.run("class AlwaysEqual(object):\n    def __eq__(self, other): return True\n\
+                class Smaller(object):\n    def __gt__(self, other): return True\n\
+                class Broken(object):\n    def __lt__(self, other): raise ValueError()\n", None, Some(&d)).unwrap();
+        let always_equal = py.eval("AlwaysEqual()", None, Some(&d)).unwrap();
+        assert!(always_equal.equals(py, "anything").unwrap());
+        // the reflected __gt__ of Smaller handles `one < smaller`
+        let smaller = py.eval("Smaller()", None, Some(&d)).unwrap();
+        assert!(one.lt(py, &smaller).unwrap());
+        let broken = py.eval("Broken()", None, Some(&d)).unwrap();
+        assert!(broken.lt(py, &one).unwrap_err().matches(py, py.get_type::<exc::ValueError>()));
+        // Python 2 orders unrelated types by type name instead of raising
+        #[cfg(feature="python3-sys")]
+        assert!(one.lt(py, "a").unwrap_err().matches(py, py.get_type::<exc::TypeError>()));
+        #[cfg(feature="python27-sys")]
+        assert!(one.lt(py, "a").unwrap());
+    }
+
+    #[test]
+    fn test_hash_and_len() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let s = "hello".to_py_object(py).into_object();
+        let expected = py.eval("hash('hello')", None, None).unwrap().extract::<::Py_hash_t>(py).unwrap();
+        assert_eq!(s.hash(py).unwrap(), expected);
+        assert_eq!(s.len(py).unwrap(), 5);
+
+        let list = vec![1i32, 2, 3].to_py_object(py).into_object();
+        assert_eq!(list.len(py).unwrap(), 3);
+        assert!(list.hash(py).unwrap_err().matches(py, py.get_type::<exc::TypeError>()));
+        assert!(1i32.to_py_object(py).into_object().len(py).unwrap_err().matches(py, py.get_type::<exc::TypeError>()));
+    }
+
+    #[test]
+    fn test_item_access() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let dict = PyDict::new(py).into_object();
+        dict.set_item(py, "a", 1i32).unwrap();
+        dict.set_item(py, 2i32, "two").unwrap();
+        assert_eq!(1, dict.get_item(py, "a").unwrap().extract::<i32>(py).unwrap());
+        assert_eq!("two", dict.get_item(py, 2i32).unwrap().extract::<String>(py).unwrap());
+        dict.del_item(py, "a").unwrap();
+        assert!(dict.get_item(py, "a").unwrap_err().matches(py, py.get_type::<exc::KeyError>()));
+        assert!(dict.del_item(py, "a").unwrap_err().matches(py, py.get_type::<exc::KeyError>()));
+        assert_eq!(1, dict.len(py).unwrap());
+
+        let list = vec![1i32, 2, 3].to_py_object(py).into_object();
+        list.set_item(py, 0i32, 10i32).unwrap();
+        assert_eq!(10, list.get_item(py, 0i32).unwrap().extract::<i32>(py).unwrap());
+        assert_eq!(3, list.get_item(py, -1i32).unwrap().extract::<i32>(py).unwrap());
+        list.del_item(py, 1i32).unwrap();
+        assert_eq!(vec![10, 3], list.extract::<Vec<i32>>(py).unwrap());
+        assert!(list.get_item(py, 5i32).unwrap_err().matches(py, py.get_type::<exc::IndexError>()));
+        assert!(list.set_item(py, 5i32, 0i32).unwrap_err().matches(py, py.get_type::<exc::IndexError>()));
+        assert!(list.get_item(py, "a").unwrap_err().matches(py, py.get_type::<exc::TypeError>()));
+    }
+
+    #[test]
+    fn test_as_sequence_and_mapping() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let list = vec![1i32, 2, 1].to_py_object(py).into_object();
+        let seq = list.as_sequence(py).unwrap();
+        assert_eq!(3, seq.len(py).unwrap());
+        assert_eq!(2, seq.get_item(py, 1).unwrap().extract::<i32>(py).unwrap());
+        assert_eq!(2, seq.count(py, 1i32).unwrap());
+        let tuple = (1i32, 2i32, 3i32).to_py_object(py).into_object();
+        let seq = tuple.as_sequence(py).unwrap();
+        assert_eq!(2, seq.index(py, 3i32).unwrap());
+        let dict = PyDict::new(py);
+        dict.set_item(py, "a", 1i32).unwrap();
+        let mapping = dict.into_object().as_mapping(py).unwrap();
+        assert_eq!(1, mapping.get(py, "a").unwrap().unwrap().extract::<i32>(py).unwrap());
+        assert!(mapping.get(py, "b").unwrap().is_none());
+        match 42i32.to_py_object(py).into_object().as_sequence(py) {
+            Err(e) => assert!(e.matches(py, py.get_type::<exc::TypeError>())),
+            Ok(_) => panic!("int should not be a sequence")
+        }
+    }
+
+    #[test]
+    #[cfg(feature="python3-sys")]
+    fn test_schedule_on_loop() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let d = ::python::globals_with_builtins(py).unwrap();
+        py.run("\
+import asyncio, threading
+loop = asyncio.new_event_loop()
+thread = threading.Thread(target=loop.run_forever)
+thread.start()
+async def add(a, b):
+    await asyncio.sleep(0)
+    return a + b
+", Some(&d), None).unwrap();
+        let event_loop = d.get_item(py, "loop").unwrap();
+        let coro = py.eval("add(2, 3)", Some(&d), None).unwrap();
+        let future = coro.schedule_on_loop(py, &event_loop).unwrap();
+        let result = future.call_method(py, "result", (5i32,), None).unwrap();
+        assert_eq!(5, result.extract::<i32>(py).unwrap());
+        py.run("\
+loop.call_soon_threadsafe(loop.stop)
+thread.join()
+loop.close()
+", Some(&d), None).unwrap();
+    }
+
+    #[test]
+    fn test_as_buffer() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let bytes = py.eval("b'abc'", None, None).unwrap();
+        let buffer = bytes.as_buffer(py).unwrap();
+        assert_eq!(3, buffer.len_bytes());
+        assert!(buffer.readonly());
+        assert!(42i32.to_py_object(py).into_object().as_buffer(py).is_err());
+    }
+}
@@ -1,6 +1,6 @@
 use ffi;
 use python::Python;
-use err::PyResult;
+use err::{PyErr, PyResult};
 use super::PyObject;
 use conversion::{ToPyObject};
 
@@ -41,17 +41,28 @@ impl ToPyObject for bool {
     }
 }
 
-/// Converts a Python `bool` to a rust `bool`.
-///
-/// Fails with `TypeError` if the input is not a Python `bool`.
+// Converts the truthiness of a Python object to a rust `bool`.
+//
+// This is equivalent to the Python expression `bool(obj)`, so it accepts
+// any object (e.g. `0`, `""` and empty containers are `false`).
+// Fails only if the object's `__bool__` or `__len__` raises an exception.
+//
+// To accept only actual Python `bool`s, extract a `PyBool` instead and use
+// `PyBool::is_true()`; that fails with `TypeError` for any other object.
 extract!(obj to bool; py => {
-    Ok(obj.cast_as::<PyBool>(py)?.is_true())
+    let v = unsafe { ffi::PyObject_IsTrue(obj.as_ptr()) };
+    if v == -1 {
+        Err(PyErr::fetch(py))
+    } else {
+        Ok(v != 0)
+    }
 });
 
 #[cfg(test)]
 mod test {
     use python::{Python, PythonObject};
     use conversion::ToPyObject;
+    use objects::{PyBool, PyDict, exc};
 
     #[test]
     fn test_true() {
@@ -70,4 +81,42 @@ mod test {
         assert_eq!(false, py.False().as_object().extract(py).unwrap());
         assert!(false.to_py_object(py).as_object() == py.False().as_object());
     }
+
+    #[test]
+    fn test_singletons() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        assert_eq!(true.to_py_object(py).as_object().as_ptr(), unsafe { ::ffi::Py_True() });
+        assert_eq!(false.to_py_object(py).as_object().as_ptr(), unsafe { ::ffi::Py_False() });
+        let d = PyDict::new(py);
+        d.set_item(py, "t", true).unwrap();
+        d.set_item(py, "f", false).unwrap();
+        py.run("assert t is True and f is False", None, Some(&d)).unwrap();
+    }
+
+    #[test]
+    fn test_extract_truthiness() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        for &(code, expected) in &[("1", true), ("0", false), ("'x'", true), ("''", false),
+                                   ("[]", false), ("[0]", true), ("None", false)] {
+            let obj = py.eval(code, None, None).unwrap();
+            assert_eq!(obj.extract::<bool>(py).unwrap(), expected, "{}", code);
+        }
+        // Python 2 calls __nonzero__ instead of __bool__
+        let err = py.eval("type('C', (), {'__bool__': lambda s: 1/0, '__nonzero__': lambda s: 1/0})()",
+                          None, None).unwrap()
+            .extract::<bool>(py).unwrap_err();
+        assert!(err.matches(py, py.get_type::<exc::ZeroDivisionError>()));
+    }
+
+    #[test]
+    fn test_extract_strict() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        assert!(py.True().into_object().extract::<PyBool>(py).unwrap().is_true());
+        let one = 1i32.to_py_object(py).into_object();
+        let err = one.extract::<PyBool>(py).err().unwrap();
+        assert!(err.matches(py, py.get_type::<exc::TypeError>()));
+    }
 }
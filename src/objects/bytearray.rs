@@ -0,0 +1,153 @@
+// Copyright (c) 2015 Daniel Grunwald
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this
+// software and associated documentation files (the "Software"), to deal in the Software
+// without restriction, including without limitation the rights to use, copy, modify, merge,
+// publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons
+// to whom the Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED,
+// INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR
+// PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE
+// FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR
+// OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+use std::{ptr, slice};
+use libc::c_char;
+use ffi;
+use python::{Python, PythonObject, ToPythonPointer};
+use objects::PyObject;
+use err::{self, PyResult};
+
+/// Represents a Python `bytearray`.
+pub struct PyByteArray(PyObject);
+
+pyobject_newtype!(PyByteArray, PyByteArray_Check, PyByteArray_Type);
+
+impl PyByteArray {
+    /// Creates a new Python bytearray object.
+    /// The bytearray is initialized by copying the data from the `&[u8]`.
+    ///
+    /// Panics if out of memory.
+    pub fn new(py: Python, src: &[u8]) -> PyByteArray {
+        let ptr = src.as_ptr() as *const c_char;
+        let len = src.len() as ffi::Py_ssize_t;
+        unsafe {
+            err::cast_from_owned_ptr_or_panic(py,
+                ffi::PyByteArray_FromStringAndSize(ptr, len))
+        }
+    }
+
+    /// Creates a new Python bytearray object from any object that supports the buffer protocol.
+    /// This is equivalent to the Python expression `bytearray(src)`.
+    pub fn from(py: Python, src: &PyObject) -> PyResult<PyByteArray> {
+        unsafe {
+            err::result_cast_from_owned_ptr(py,
+                ffi::PyByteArray_FromObject(src.as_ptr()))
+        }
+    }
+
+    /// Gets the length of the bytearray.
+    #[inline]
+    pub fn len(&self, _py: Python) -> usize {
+        unsafe { ffi::PyByteArray_Size(self.0.as_ptr()) as usize }
+    }
+
+    /// Gets the bytearray contents as a byte slice.
+    ///
+    /// # Safety
+    /// Unlike `bytes`, a `bytearray` is mutable: the returned slice is invalidated
+    /// if the bytearray is resized or modified, including by Python code.
+    /// The caller must not let any Python code run while the slice is in use.
+    #[inline]
+    pub unsafe fn as_slice(&self, py: Python) -> &[u8] {
+        let buffer = ffi::PyByteArray_AsString(self.0.as_ptr()) as *const u8;
+        slice::from_raw_parts(buffer, self.len(py))
+    }
+
+    /// Gets the bytearray contents as a mutable byte slice.
+    ///
+    /// # Safety
+    /// Same requirements as for `as_slice()`. In addition, the caller must ensure
+    /// that no other slice into the same bytearray is alive at the same time.
+    #[inline]
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn data_mut(&self, py: Python) -> &mut [u8] {
+        let buffer = ffi::PyByteArray_AsString(self.0.as_ptr()) as *mut u8;
+        slice::from_raw_parts_mut(buffer, self.len(py))
+    }
+
+    /// Copies the bytearray contents into a new `Vec<u8>`.
+    pub fn to_vec(&self, py: Python) -> Vec<u8> {
+        unsafe { self.as_slice(py) }.to_vec()
+    }
+
+    /// Resizes the bytearray to `len` bytes.
+    /// New bytes are initialized to zero.
+    pub fn resize(&self, py: Python, len: usize) -> PyResult<()> {
+        let old_len = self.len(py);
+        unsafe {
+            err::error_on_minusone(py,
+                ffi::PyByteArray_Resize(self.0.as_ptr(), len as ffi::Py_ssize_t))?;
+            if len > old_len {
+                let buffer = ffi::PyByteArray_AsString(self.0.as_ptr()) as *mut u8;
+                ptr::write_bytes(buffer.offset(old_len as isize), 0, len - old_len);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use python::{Python, PythonObject};
+    use objects::{PyByteArray, PyBytes};
+
+    #[test]
+    fn test_bytearray() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let blob: &[u8] = b"\x00\xff\xfeabc\x80";
+        let array = PyByteArray::new(py, blob);
+        assert_eq!(blob.len(), array.len(py));
+        assert_eq!(blob, unsafe { array.as_slice(py) });
+        unsafe { array.data_mut(py)[0] = b'x'; }
+        assert_eq!(b'x', array.to_vec(py)[0]);
+        array.resize(py, 2).unwrap();
+        assert_eq!(vec![b'x', 0xff], array.to_vec(py));
+        let v: Vec<u8> = array.into_object().extract(py).unwrap();
+        assert_eq!(vec![b'x', 0xff], v);
+    }
+
+    #[test]
+    fn test_resize_zero_fills() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let array = PyByteArray::new(py, b"ab");
+        array.resize(py, 5).unwrap();
+        assert_eq!(b"ab\0\0\0".to_vec(), array.to_vec(py));
+    }
+
+    #[test]
+    fn test_binary_round_trip() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let blob: Vec<u8> = (0..256).map(|i| i as u8).collect();
+        let bytes = PyBytes::new(py, &blob);
+        assert_eq!(&blob[..], bytes.data(py));
+        let array = PyByteArray::from(py, bytes.as_object()).unwrap();
+        assert_eq!(blob, array.to_vec(py));
+        let v: Vec<u8> = array.into_object().extract(py).unwrap();
+        assert_eq!(blob, v);
+        // On Python 2, a `str` is a sequence of 1-character strings, not of integers.
+        #[cfg(feature="python3-sys")]
+        {
+            let v: Vec<u8> = bytes.into_object().extract(py).unwrap();
+            assert_eq!(blob, v);
+        }
+    }
+}
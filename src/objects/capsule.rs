@@ -0,0 +1,185 @@
+// Copyright (c) 2015 Daniel Grunwald
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this
+// software and associated documentation files (the "Software"), to deal in the Software
+// without restriction, including without limitation the rights to use, copy, modify, merge,
+// publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons
+// to whom the Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED,
+// INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR
+// PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE
+// FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR
+// OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+use std::ffi::CStr;
+use std::mem;
+use libc::c_void;
+use ffi;
+use python::Python;
+use err::{self, PyResult, PyErr};
+use function::AbortOnDrop;
+use objects::PyObject;
+
+/// Represents a Python capsule: an opaque C pointer tagged with a name.
+///
+/// Capsules are the standard way for extension modules to share a C API:
+/// one module stores a pointer in a capsule attribute, and other modules
+/// retrieve it with `PyCapsule::import()`.
+pub struct PyCapsule(PyObject);
+
+pyobject_newtype!(PyCapsule, PyCapsule_CheckExact, PyCapsule_Type);
+
+impl PyCapsule {
+    /// Creates a new capsule containing `pointer`.
+    ///
+    /// `name` is used to check that the capsule is the expected one when the
+    /// pointer is retrieved; by convention it is the full dotted path of the
+    /// attribute holding the capsule (e.g. `"module.submodule._C_API"`).
+    ///
+    /// If `destructor` is given, it is called with `pointer` when the capsule is destroyed.
+    /// A panic in the destructor aborts the process.
+    ///
+    /// Fails with `ValueError` if `pointer` is null.
+    ///
+    /// # Safety
+    /// The pointer is handed out to anyone who knows the capsule's name:
+    /// it must stay valid for as long as the capsule exists,
+    /// and `destructor` must be safe to call with it.
+    pub unsafe fn new(py: Python, pointer: *mut c_void, name: &'static CStr,
+                      destructor: Option<fn(*mut c_void)>) -> PyResult<PyCapsule> {
+        let capsule: PyCapsule = err::result_cast_from_owned_ptr(py,
+            ffi::PyCapsule_New(pointer, name.as_ptr(), None))?;
+        if let Some(destructor) = destructor {
+            // The context stores the Rust destructor for the extern "C" shim.
+            err::error_on_minusone(py,
+                ffi::PyCapsule_SetContext(capsule.0.as_ptr(), destructor as *mut c_void))?;
+            err::error_on_minusone(py,
+                ffi::PyCapsule_SetDestructor(capsule.0.as_ptr(), Some(capsule_destructor)))?;
+        }
+        Ok(capsule)
+    }
+
+    /// Imports the capsule stored at the dotted path `name` (e.g. `"module._C_API"`)
+    /// and returns its pointer.
+    ///
+    /// The capsule's name must match `name`.
+    pub fn import(py: Python, name: &CStr) -> PyResult<*mut c_void> {
+        let ptr = unsafe { ffi::PyCapsule_Import(name.as_ptr(), 0) };
+        if ptr.is_null() {
+            Err(PyErr::fetch(py))
+        } else {
+            Ok(ptr)
+        }
+    }
+
+    /// Retrieves the pointer stored in the capsule.
+    ///
+    /// Fails with `ValueError` if `name` does not match the capsule's name.
+    pub fn get_pointer(&self, py: Python, name: &CStr) -> PyResult<*mut c_void> {
+        let ptr = unsafe { ffi::PyCapsule_GetPointer(self.0.as_ptr(), name.as_ptr()) };
+        if ptr.is_null() {
+            Err(PyErr::fetch(py))
+        } else {
+            Ok(ptr)
+        }
+    }
+
+    /// Gets the name of the capsule.
+    pub fn name<'a>(&'a self, _py: Python<'a>) -> Option<&'a CStr> {
+        unsafe {
+            let name = ffi::PyCapsule_GetName(self.0.as_ptr());
+            if name.is_null() {
+                None
+            } else {
+                Some(CStr::from_ptr(name))
+            }
+        }
+    }
+
+    /// Returns true if the capsule is valid and its name matches `name`.
+    pub fn is_valid(&self, _py: Python, name: &CStr) -> bool {
+        unsafe { ffi::PyCapsule_IsValid(self.0.as_ptr(), name.as_ptr()) != 0 }
+    }
+}
+
+unsafe extern "C" fn capsule_destructor(capsule: *mut ffi::PyObject) {
+    let guard = AbortOnDrop("PyCapsule destructor");
+    let pointer = ffi::PyCapsule_GetPointer(capsule, ffi::PyCapsule_GetName(capsule));
+    let destructor = mem::transmute::<*mut c_void, fn(*mut c_void)>(ffi::PyCapsule_GetContext(capsule));
+    destructor(pointer);
+    mem::forget(guard);
+}
+
+#[cfg(test)]
+mod test {
+    use std::ffi::CStr;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use libc::c_void;
+    use python::{Python, PythonObject};
+    use objects::{PyDict, PyCapsule, PyModule};
+    use objectprotocol::ObjectProtocol;
+
+    static DESTRUCTOR_CALLED: AtomicBool = AtomicBool::new(false);
+
+    fn free_box(pointer: *mut c_void) {
+        unsafe { drop(Box::from_raw(pointer as *mut i32)); }
+        DESTRUCTOR_CALLED.store(true, Ordering::SeqCst);
+    }
+
+    fn capsule_name() -> &'static CStr {
+        cstr!("cpython.test.capsule")
+    }
+
+    #[test]
+    fn test_capsule() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let pointer = Box::into_raw(Box::new(42i32)) as *mut c_void;
+        let capsule = unsafe { PyCapsule::new(py, pointer, capsule_name(), Some(free_box)) }.unwrap();
+        assert_eq!(capsule.name(py), Some(capsule_name()));
+        assert!(capsule.is_valid(py, capsule_name()));
+        assert!(!capsule.is_valid(py, cstr!("other")));
+
+        let d = PyDict::new(py);
+        d.set_item(py, "capsule", capsule).unwrap();
+        let capsule = d.get_item(py, "capsule").unwrap().cast_into::<PyCapsule>(py).unwrap();
+        let retrieved = capsule.get_pointer(py, capsule_name()).unwrap();
+        assert_eq!(retrieved, pointer);
+        assert_eq!(unsafe { *(retrieved as *const i32) }, 42);
+        assert!(capsule.get_pointer(py, cstr!("other")).is_err());
+
+        drop(capsule);
+        assert!(!DESTRUCTOR_CALLED.load(Ordering::SeqCst));
+        d.del_item(py, "capsule").unwrap();
+        assert!(DESTRUCTOR_CALLED.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_null_pointer() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        assert!(unsafe { PyCapsule::new(py, ::std::ptr::null_mut(), capsule_name(), None) }.is_err());
+    }
+
+    #[test]
+    fn test_import() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let mut value = 7i32;
+        let pointer = &mut value as *mut i32 as *mut c_void;
+        let module = PyModule::new(py, "capsule_import_test").unwrap();
+        let name = cstr!("capsule_import_test._C_API");
+        let capsule = unsafe { PyCapsule::new(py, pointer, name, None) }.unwrap();
+        module.add(py, "_C_API", capsule).unwrap();
+        let modules = py.import("sys").unwrap().get(py, "modules").unwrap();
+        modules.set_item(py, "capsule_import_test", module.as_object()).unwrap();
+        assert_eq!(PyCapsule::import(py, cstr!("capsule_import_test._C_API")).unwrap(), pointer);
+        assert!(PyCapsule::import(py, cstr!("capsule_import_test.missing")).is_err());
+        modules.del_item(py, "capsule_import_test").unwrap();
+    }
+}
@@ -0,0 +1,129 @@
+// Copyright (c) 2015 Daniel Grunwald
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this
+// software and associated documentation files (the "Software"), to deal in the Software
+// without restriction, including without limitation the rights to use, copy, modify, merge,
+// publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons
+// to whom the Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED,
+// INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR
+// PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE
+// FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR
+// OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+use libc::c_double;
+use python::{Python, PythonObject};
+use err::{self, PyResult, PyErr};
+use super::object::PyObject;
+use ffi;
+use conversion::ToPyObject;
+
+/// Represents a Python `complex` object.
+///
+/// You can usually avoid directly working with this type
+/// by using [ToPyObject](trait.ToPyObject.html)
+/// and [extract](struct.PyObject.html#method.extract)
+/// with `Complex64`.
+pub struct PyComplex(PyObject);
+pyobject_newtype!(PyComplex, PyComplex_Check, PyComplex_Type);
+
+impl PyComplex {
+    /// Creates a new Python `complex` object.
+    pub fn new(py: Python, real: c_double, imag: c_double) -> PyComplex {
+        unsafe {
+            err::cast_from_owned_ptr_or_panic(py, ffi::PyComplex_FromDoubles(real, imag))
+        }
+    }
+
+    /// Gets the real part of this complex number.
+    pub fn real(&self, _py: Python) -> c_double {
+        unsafe { ffi::PyComplex_RealAsDouble(self.0.as_ptr()) }
+    }
+
+    /// Gets the imaginary part of this complex number.
+    pub fn imag(&self, _py: Python) -> c_double {
+        unsafe { ffi::PyComplex_ImagAsDouble(self.0.as_ptr()) }
+    }
+}
+
+/// A complex number with `f64` real and imaginary parts.
+///
+/// Converts to a Python `complex` object.
+/// Extraction accepts any object that Python's `complex()` can convert
+/// without a second argument (e.g. `int` and `float`, which are promoted to a complex number
+/// with zero imaginary part).
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub struct Complex64 {
+    pub re: f64,
+    pub im: f64
+}
+
+impl Complex64 {
+    /// Creates a new complex number.
+    pub fn new(re: f64, im: f64) -> Complex64 {
+        Complex64 { re, im }
+    }
+}
+
+impl ToPyObject for Complex64 {
+    type ObjectType = PyComplex;
+
+    fn to_py_object(&self, py: Python) -> PyComplex {
+        PyComplex::new(py, self.re, self.im)
+    }
+}
+
+extract!(obj to Complex64; py => {
+    // PyComplex_AsCComplex() falls back to __complex__ for other types,
+    // and then to __float__ with an imaginary part of 0.0.
+    let c = unsafe { ffi::PyComplex_AsCComplex(obj.as_ptr()) };
+    if c.real == -1.0 && PyErr::occurred(py) {
+        return Err(PyErr::fetch(py));
+    }
+    Ok(Complex64::new(c.real, c.imag))
+});
+
+#[cfg(test)]
+mod test {
+    use std;
+    use python::{Python, PythonObject};
+    use conversion::ToPyObject;
+    use super::{PyComplex, Complex64};
+
+    #[test]
+    fn test_complex_round_trip() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        for &(re, im) in &[(1.5, -2.25), (0.1, 0.2), (std::f64::MAX, std::f64::MIN_POSITIVE), (0.0, -0.0)] {
+            let obj = Complex64::new(re, im).to_py_object(py);
+            assert_eq!(obj.real(py).to_bits(), re.to_bits());
+            assert_eq!(obj.imag(py).to_bits(), im.to_bits());
+            let c: Complex64 = obj.into_object().extract(py).unwrap();
+            assert_eq!(c.re.to_bits(), re.to_bits());
+            assert_eq!(c.im.to_bits(), im.to_bits());
+        }
+    }
+
+    #[test]
+    fn test_complex_from_python() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let obj = py.eval("complex(3, 4)", None, None).unwrap();
+        assert!(obj.cast_as::<PyComplex>(py).is_ok());
+        assert_eq!(obj.extract::<Complex64>(py).unwrap(), Complex64::new(3.0, 4.0));
+        assert_eq!(py.eval("7", None, None).unwrap().extract::<Complex64>(py).unwrap(), Complex64::new(7.0, 0.0));
+        assert_eq!(py.eval("2.5", None, None).unwrap().extract::<Complex64>(py).unwrap(), Complex64::new(2.5, 0.0));
+        assert!(py.eval("'abc'", None, None).unwrap().extract::<Complex64>(py).is_err());
+        let d = ::objects::PyDict::new(py);
+        py.run("class C(object):\n  def __complex__(self): return 1.5-2j", None, Some(&d)).unwrap();
+        assert_eq!(py.eval("C()", None, Some(&d)).unwrap().extract::<Complex64>(py).unwrap(), Complex64::new(1.5, -2.0));
+        let d = ::objects::PyDict::new(py);
+        d.set_item(py, "c", PyComplex::new(py, 1.0, 2.0)).unwrap();
+        assert!(py.eval("c == 1+2j", None, Some(&d)).unwrap().extract::<bool>(py).unwrap());
+    }
+}
@@ -0,0 +1,247 @@
+// Copyright (c) 2015 Daniel Grunwald
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this
+// software and associated documentation files (the "Software"), to deal in the Software
+// without restriction, including without limitation the rights to use, copy, modify, merge,
+// publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons
+// to whom the Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED,
+// INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR
+// PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE
+// FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR
+// OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! Conversions between `chrono` types and the Python `datetime` module.
+//!
+//! | Rust                  | Python                                   |
+//! |-----------------------|------------------------------------------|
+//! | `NaiveDate`           | `datetime.date`                          |
+//! | `NaiveDateTime`       | naive `datetime.datetime`                |
+//! | `DateTime<Utc>`       | aware `datetime.datetime` (UTC)          |
+//! | `Duration`            | `datetime.timedelta`                     |
+//!
+//! Python only supports microsecond precision, so nanoseconds are truncated
+//! when converting to Python. Converting a value that is outside the range
+//! supported by the `datetime` module to Python panics.
+
+extern crate chrono;
+
+use self::chrono::{NaiveDate, NaiveDateTime, DateTime, Duration, Utc, TimeZone, Datelike, Timelike};
+use python::{Python, PythonObject};
+use err::{self, PyResult, PyErr};
+use objectprotocol::ObjectProtocol;
+use objects::{PyObject, exc};
+use conversion::ToPyObject;
+use ffi;
+use ffi::datetime::*;
+use objects::time::{import_datetime_api, datetime_api, invalid_value, utc_timezone};
+
+/// Reads the date and time fields of a `datetime.datetime` object.
+unsafe fn datetime_fields(py: Python, obj: &PyObject) -> PyResult<NaiveDateTime> {
+    let ptr = obj.as_ptr();
+    NaiveDate::from_ymd_opt(PyDateTime_GET_YEAR(ptr),
+                            PyDateTime_GET_MONTH(ptr) as u32,
+                            PyDateTime_GET_DAY(ptr) as u32)
+        .and_then(|date| date.and_hms_micro_opt(
+            PyDateTime_DATE_GET_HOUR(ptr) as u32,
+            PyDateTime_DATE_GET_MINUTE(ptr) as u32,
+            PyDateTime_DATE_GET_SECOND(ptr) as u32,
+            PyDateTime_DATE_GET_MICROSECOND(ptr) as u32))
+        .ok_or_else(|| invalid_value(py, "invalid datetime"))
+}
+
+unsafe fn new_datetime(py: Python, dt: &NaiveDateTime, tzinfo: *mut ffi::PyObject) -> PyObject {
+    let micros = dt.nanosecond() / 1000;
+    // chrono represents leap seconds as nanosecond values >= 1_000_000_000
+    let micros = if micros >= 1_000_000 { 999_999 } else { micros };
+    err::from_owned_ptr_or_panic(py, ((*PyDateTimeAPI).DateTime_FromDateAndTime)(
+        dt.year(), dt.month() as i32, dt.day() as i32,
+        dt.hour() as i32, dt.minute() as i32, dt.second() as i32, micros as i32,
+        tzinfo, (*PyDateTimeAPI).DateTimeType))
+}
+
+/// Converts to `datetime.date`.
+impl ToPyObject for NaiveDate {
+    type ObjectType = PyObject;
+
+    fn to_py_object(&self, py: Python) -> PyObject {
+        datetime_api(py);
+        unsafe {
+            err::from_owned_ptr_or_panic(py,
+                PyDate_FromDate(self.year(), self.month() as i32, self.day() as i32))
+        }
+    }
+}
+
+// Accepts `datetime.date` objects. As `datetime.datetime` is a subclass of `datetime.date`,
+// the date part of a `datetime.datetime` is accepted as well.
+extract!(obj to NaiveDate; py => {
+    import_datetime_api(py)?;
+    unsafe {
+        let ptr = obj.as_ptr();
+        if PyDate_Check(ptr) == 0 {
+            return Err(PyErr::new::<exc::TypeError, _>(py, "expected datetime.date"));
+        }
+        NaiveDate::from_ymd_opt(PyDateTime_GET_YEAR(ptr),
+                                PyDateTime_GET_MONTH(ptr) as u32,
+                                PyDateTime_GET_DAY(ptr) as u32)
+            .ok_or_else(|| invalid_value(py, "invalid date"))
+    }
+});
+
+/// Converts to a naive `datetime.datetime`.
+impl ToPyObject for NaiveDateTime {
+    type ObjectType = PyObject;
+
+    fn to_py_object(&self, py: Python) -> PyObject {
+        datetime_api(py);
+        unsafe { new_datetime(py, self, ffi::Py_None()) }
+    }
+}
+
+// Accepts only naive `datetime.datetime` objects; aware datetimes raise `ValueError`.
+extract!(obj to NaiveDateTime; py => {
+    import_datetime_api(py)?;
+    unsafe {
+        if PyDateTime_Check(obj.as_ptr()) == 0 {
+            return Err(PyErr::new::<exc::TypeError, _>(py, "expected datetime.datetime"));
+        }
+        if PyDateTime_DATE_GET_TZINFO(obj.as_ptr()) != ffi::Py_None() {
+            return Err(invalid_value(py, "expected a naive datetime.datetime"));
+        }
+        datetime_fields(py, obj)
+    }
+});
+
+/// Converts to an aware `datetime.datetime` with `tzinfo=datetime.timezone.utc`.
+impl ToPyObject for DateTime<Utc> {
+    type ObjectType = PyObject;
+
+    fn to_py_object(&self, py: Python) -> PyObject {
+        datetime_api(py);
+        unsafe {
+            let utc = utc_timezone(py);
+            new_datetime(py, &self.naive_utc(), utc.as_ptr())
+        }
+    }
+}
+
+// Accepts aware `datetime.datetime` objects in any time zone and converts them to UTC.
+// Naive datetimes raise `ValueError`.
+extract!(obj to DateTime<Utc>; py => {
+    import_datetime_api(py)?;
+    unsafe {
+        if PyDateTime_Check(obj.as_ptr()) == 0 {
+            return Err(PyErr::new::<exc::TypeError, _>(py, "expected datetime.datetime"));
+        }
+        if PyDateTime_DATE_GET_TZINFO(obj.as_ptr()) == ffi::Py_None() {
+            return Err(invalid_value(py, "expected an aware datetime.datetime"));
+        }
+        let utc = utc_timezone(py);
+        let in_utc = obj.call_method(py, "astimezone", (utc,), None)?;
+        Ok(Utc.from_utc_datetime(&datetime_fields(py, &in_utc)?))
+    }
+});
+
+/// Converts to `datetime.timedelta`.
+impl ToPyObject for Duration {
+    type ObjectType = PyObject;
+
+    fn to_py_object(&self, py: Python) -> PyObject {
+        datetime_api(py);
+        let days = self.num_days();
+        let rest = *self - Duration::days(days);
+        let seconds = rest.num_seconds();
+        let micros = (rest - Duration::seconds(seconds)).num_microseconds().unwrap();
+        // timedelta.max.days == 999999999
+        if days.abs() > 999_999_999 {
+            panic!("Duration is out of range for datetime.timedelta");
+        }
+        // timedelta normalizes negative seconds and microseconds
+        unsafe {
+            err::from_owned_ptr_or_panic(py, PyDelta_FromDSU(days as i32, seconds as i32, micros as i32))
+        }
+    }
+}
+
+extract!(obj to Duration; py => {
+    import_datetime_api(py)?;
+    unsafe {
+        let ptr = obj.as_ptr();
+        if PyDelta_Check(ptr) == 0 {
+            return Err(PyErr::new::<exc::TypeError, _>(py, "expected datetime.timedelta"));
+        }
+        Ok(Duration::days(PyDateTime_DELTA_GET_DAYS(ptr) as i64)
+            + Duration::seconds(PyDateTime_DELTA_GET_SECONDS(ptr) as i64)
+            + Duration::microseconds(PyDateTime_DELTA_GET_MICROSECONDS(ptr) as i64))
+    }
+});
+
+#[cfg(test)]
+mod test {
+    use super::chrono::{NaiveDate, NaiveDateTime, DateTime, Duration, Utc, TimeZone, Datelike, Timelike};
+    use python::Python;
+    use conversion::ToPyObject;
+    use objects::PyDict;
+
+    #[test]
+    fn test_extract_naive_datetime() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let d = PyDict::new(py);
+        py.run("import datetime\ndt = datetime.datetime(2017, 12, 31, 23, 59, 58, 123456)", None, Some(&d)).unwrap();
+        let dt: NaiveDateTime = d.get_item(py, "dt").unwrap().extract(py).unwrap();
+        assert_eq!((dt.year(), dt.month(), dt.day()), (2017, 12, 31));
+        assert_eq!((dt.hour(), dt.minute(), dt.second()), (23, 59, 58));
+        assert_eq!(dt.nanosecond(), 123456000);
+
+        let date: NaiveDate = d.get_item(py, "dt").unwrap().extract(py).unwrap();
+        assert_eq!(date, NaiveDate::from_ymd_opt(2017, 12, 31).unwrap());
+
+        py.run("aware = dt.replace(tzinfo=datetime.timezone.utc)", None, Some(&d)).unwrap();
+        assert!(d.get_item(py, "aware").unwrap().extract::<NaiveDateTime>(py).is_err());
+        assert!(py.eval("1", None, None).unwrap().extract::<NaiveDateTime>(py).is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let date = NaiveDate::from_ymd_opt(1999, 1, 2).unwrap();
+        assert_eq!(date.to_py_object(py).extract::<NaiveDate>(py).unwrap(), date);
+
+        let dt = date.and_hms_micro_opt(3, 4, 5, 678901).unwrap();
+        let obj = dt.to_py_object(py);
+        assert_eq!(obj.extract::<NaiveDateTime>(py).unwrap(), dt);
+        let d = PyDict::new(py);
+        d.set_item(py, "dt", obj).unwrap();
+        assert_eq!(py.eval("repr(dt)", None, Some(&d)).unwrap().extract::<String>(py).unwrap(),
+                   "datetime.datetime(1999, 1, 2, 3, 4, 5, 678901)");
+
+        for delta in &[Duration::days(3) + Duration::microseconds(17), Duration::seconds(-1),
+                       Duration::microseconds(-1), Duration::days(-10000)] {
+            assert_eq!(delta.to_py_object(py).extract::<Duration>(py).unwrap(), *delta);
+        }
+    }
+
+    #[test]
+    fn test_utc_round_trip() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let dt: DateTime<Utc> = Utc.with_ymd_and_hms(2020, 2, 29, 12, 0, 0).unwrap();
+        let obj = dt.to_py_object(py);
+        assert_eq!(obj.extract::<DateTime<Utc>>(py).unwrap(), dt);
+        assert!(obj.extract::<NaiveDateTime>(py).is_err());
+
+        let d = PyDict::new(py);
+        py.run("import datetime\n\
+                tz = datetime.timezone(datetime.timedelta(hours=2))\n\
+                dt = datetime.datetime(2020, 2, 29, 14, 0, 0, tzinfo=tz)", None, Some(&d)).unwrap();
+        assert_eq!(d.get_item(py, "dt").unwrap().extract::<DateTime<Utc>>(py).unwrap(), dt);
+    }
+}
@@ -17,11 +17,43 @@
 // DEALINGS IN THE SOFTWARE.
 
 use ffi;
-use python::{Python, PythonObject};
+use python::{Python, PythonObject, PyDrop};
 use conversion::ToPyObject;
-use objects::{PyObject, PyList};
+use objects::{PyObject, PyList, KeyedPyObject};
 use err::{self, PyResult, PyErr};
-use std::{mem, collections, hash, cmp};
+use std::{mem, ptr, collections, hash, cmp};
+
+/// Creates a `PyDict` from a list of `key => value` pairs.
+///
+/// Keys and values can be any type implementing `ToPyObject`.
+/// Expands to an expression of type `PyResult<PyDict>`;
+/// the result is an error if one of the keys is unhashable.
+///
+/// # Example
+/// ```
+/// #[macro_use] extern crate cpython;
+/// use cpython::{Python, ObjectProtocol};
+///
+/// # fn main() {
+/// let gil = Python::acquire_gil();
+/// let py = gil.python();
+/// let dict = py_dict!(py, {"a" => 1, "b" => "two"}).unwrap();
+/// assert_eq!(dict.len(py), 2);
+/// assert_eq!(dict.get_item(py, "a").unwrap().extract::<i32>(py).unwrap(), 1);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! py_dict {
+    ($py:expr, { $( $key:expr => $value:expr ),* $(,)* }) => {{
+        let py = $py;
+        let dict = $crate::PyDict::new(py);
+        let result: $crate::PyResult<()> = Ok(());
+        $(
+            let result = result.and_then(|()| dict.set_item(py, $key, $value));
+        )*
+        result.map(|()| dict)
+    }};
+}
 
 /// Represents a Python `dict`.
 pub struct PyDict(PyObject);
@@ -80,8 +112,84 @@ impl PyDict {
         })
     }
 
+    /// Gets an item from the dictionary.
+    ///
+    /// Returns `None` if the item is not present.
+    /// Unlike `get_item()`, errors are propagated;
+    /// e.g. an unhashable key results in a `TypeError`.
+    #[cfg(feature="python3-sys")]
+    pub fn get_item_checked<K>(&self, py: Python, key: K) -> PyResult<Option<PyObject>> where K: ToPyObject {
+        key.with_borrowed_ptr(py, |key| unsafe {
+            match PyObject::from_borrowed_ptr_opt(py, ffi::PyDict_GetItemWithError(self.0.as_ptr(), key)) {
+                Some(value) => Ok(Some(value)),
+                None if ffi::PyErr_Occurred().is_null() => Ok(None),
+                None => Err(PyErr::fetch(py))
+            }
+        })
+    }
+
+    /// Gets an item from the dictionary.
+    ///
+    /// Returns `None` if the item is not present.
+    /// Python 2 does not expose a dict lookup that reports errors,
+    /// so this hashes the key first to detect unhashable keys.
+    /// Errors raised while comparing keys are still swallowed.
+    #[cfg(feature="python27-sys")]
+    pub fn get_item_checked<K>(&self, py: Python, key: K) -> PyResult<Option<PyObject>> where K: ToPyObject {
+        key.with_borrowed_ptr(py, |key| unsafe {
+            if ffi::PyObject_Hash(key) == -1 {
+                Err(PyErr::fetch(py))
+            } else {
+                Ok(PyObject::from_borrowed_ptr_opt(py, ffi::PyDict_GetItem(self.0.as_ptr(), key)))
+            }
+        })
+    }
+
+    /// Gets an item from the dictionary, using the hash cached in `key`.
+    ///
+    /// Returns `None` if the item is not present.
+    /// Unlike `get_item()`, exceptions raised while comparing keys are propagated.
+    #[cfg(feature="python3-sys")]
+    pub fn get_item_keyed(&self, py: Python, key: &KeyedPyObject) -> PyResult<Option<PyObject>> {
+        unsafe {
+            let ptr = ffi::_PyDict_GetItem_KnownHash(self.0.as_ptr(),
+                key.as_object().as_ptr(), key.hash_value());
+            match PyObject::from_borrowed_ptr_opt(py, ptr) {
+                Some(value) => Ok(Some(value)),
+                None if ffi::PyErr_Occurred().is_null() => Ok(None),
+                None => Err(PyErr::fetch(py))
+            }
+        }
+    }
+
+    /// Gets an item from the dictionary, using the hash cached in `key`.
+    ///
+    /// Returns `None` if the item is not present.
+    /// Python 2 does not expose lookups with a precomputed hash,
+    /// so this falls back to hashing the key again.
+    #[cfg(feature="python27-sys")]
+    pub fn get_item_keyed(&self, py: Python, key: &KeyedPyObject) -> PyResult<Option<PyObject>> {
+        Ok(self.get_item(py, key.as_object()))
+    }
+
+    /// Gets an item from the dictionary, or `default` if the item is not present.
+    /// This is equivalent to the Python expression `self.get(key, default)`.
+    pub fn get_item_or<K>(&self, py: Python, key: K, default: PyObject) -> PyObject where K: ToPyObject {
+        match self.get_item(py, key) {
+            Some(value) => {
+                default.release_ref(py);
+                value
+            },
+            None => default
+        }
+    }
+
     /// Sets an item value.
     /// This is equivalent to the Python expression `self[key] = value`.
+    ///
+    /// Both `key` and `value` are converted with `ToPyObject`,
+    /// so Rust values can be passed directly: `dict.set_item(py, "count", 5)`.
+    /// Fails with a `TypeError` if the key is unhashable.
     pub fn set_item<K, V>(&self, py: Python, key: K, value: V) -> PyResult<()> where K: ToPyObject, V: ToPyObject {
         key.with_borrowed_ptr(py, move |key|
             value.with_borrowed_ptr(py, |value| unsafe {
@@ -108,10 +216,10 @@ impl PyDict {
     }
 
     /// Returns the list of (key,value) pairs in this dictionary.
+    ///
+    /// Unlike `iter()`, this takes a snapshot of the dictionary,
+    /// so it is fine to modify the dictionary while processing the items.
     pub fn items(&self, py: Python) -> Vec<(PyObject, PyObject)> {
-        // Note that we don't provide an iterator because
-        // PyDict_Next() is unsafe to use when the dictionary might be changed
-        // by other python code.
         let mut vec = Vec::with_capacity(self.len(py));
         unsafe {
             let mut pos = 0;
@@ -124,6 +232,41 @@ impl PyDict {
         }
         vec
     }
+
+    /// Returns an iterator over the (key,value) pairs in this dictionary.
+    ///
+    /// The dictionary must not be modified during the iteration
+    /// (neither by Rust code nor by Python code called from within the loop);
+    /// otherwise items may be skipped or returned twice.
+    /// Use `items()` if the dictionary might change.
+    #[inline]
+    pub fn iter<'a, 'p>(&'a self, py: Python<'p>) -> PyDictIterator<'a, 'p> {
+        PyDictIterator { py: py, dict: self, pos: 0 }
+    }
+}
+
+/// Used by `PyDict::iter()`.
+pub struct PyDictIterator<'a, 'p> {
+    py: Python<'p>,
+    dict: &'a PyDict,
+    pos: ffi::Py_ssize_t
+}
+
+impl <'a, 'p> Iterator for PyDictIterator<'a, 'p> {
+    type Item = (PyObject, PyObject);
+
+    fn next(&mut self) -> Option<(PyObject, PyObject)> {
+        let mut key: *mut ffi::PyObject = ptr::null_mut();
+        let mut value: *mut ffi::PyObject = ptr::null_mut();
+        unsafe {
+            if ffi::PyDict_Next(self.dict.0.as_ptr(), &mut self.pos, &mut key, &mut value) != 0 {
+                Some((PyObject::from_borrowed_ptr(self.py, key),
+                      PyObject::from_borrowed_ptr(self.py, value)))
+            } else {
+                None
+            }
+        }
+    }
 }
 
 impl <K, V, H> ToPyObject for collections::HashMap<K, V, H>
@@ -161,9 +304,26 @@ impl <K, V> ToPyObject for collections::BTreeMap<K, V>
 mod test {
     use python::{Python, PythonObject};
     use conversion::ToPyObject;
-    use objects::{PyDict, PyTuple};
+    use objects::{PyDict, PyTuple, exc};
+    use err::PyErr;
     use std::collections::HashMap;
 
+    #[test]
+    fn test_py_dict_macro() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let dict = py_dict!(py, {"a" => 1, "b" => "two", 3 => 4.5,}).unwrap();
+        assert_eq!(dict.len(py), 3);
+        assert_eq!(dict.get_item(py, "a").unwrap().extract::<i32>(py).unwrap(), 1);
+        assert_eq!(dict.get_item(py, "b").unwrap().extract::<String>(py).unwrap(), "two");
+        assert_eq!(dict.get_item(py, 3).unwrap().extract::<f64>(py).unwrap(), 4.5);
+        assert_eq!(py_dict!(py, {}).unwrap().len(py), 0);
+
+        // unhashable keys are reported as errors
+        let key = PyDict::new(py);
+        assert!(py_dict!(py, {"a" => 1, key => 2}).is_err());
+    }
+
     #[test]
     fn test_len() {
         let gil = Python::acquire_gil();
@@ -211,6 +371,30 @@ mod test {
         assert_eq!(123i32, dict.get_item(py, 8i32).unwrap().extract::<i32>(py).unwrap());
     }
 
+    #[test]
+    fn test_set_item_from_primitives() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let dict = PyDict::new(py);
+        dict.set_item(py, "count", 5).unwrap();
+        dict.set_item(py, "ratio", 0.5).unwrap();
+        dict.set_item(py, "name", "abc").unwrap();
+        dict.set_item(py, 1u8, true).unwrap();
+        assert_eq!(4, dict.len(py));
+        assert_eq!(5, dict.get_item_checked(py, "count").unwrap().unwrap().extract::<i32>(py).unwrap());
+        assert_eq!(0.5, dict.get_item_checked(py, "ratio").unwrap().unwrap().extract::<f64>(py).unwrap());
+        assert_eq!("abc", dict.get_item_checked(py, "name").unwrap().unwrap().extract::<String>(py).unwrap());
+        assert!(dict.get_item_checked(py, 1i64).unwrap().unwrap().extract::<bool>(py).unwrap());
+        assert!(dict.get_item_checked(py, "missing").unwrap().is_none());
+
+        // unhashable keys are reported instead of being treated as missing
+        let key = vec![1i32];
+        assert!(dict.set_item(py, &key, 1).unwrap_err().matches(py, py.get_type::<exc::TypeError>()));
+        assert!(dict.get_item_checked(py, &key).unwrap_err().matches(py, py.get_type::<exc::TypeError>()));
+        assert!(dict.get_item(py, &key).is_none());
+        assert!(!PyErr::occurred(py));
+    }
+
     #[test]
     fn test_set_item_does_not_update_original_object() {
         let gil = Python::acquire_gil();
@@ -289,4 +473,37 @@ mod test {
         assert_eq!(32 + 42 + 123, value_sum);
     }
 
+
+    #[test]
+    fn test_iter() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let mut v = HashMap::new();
+        v.insert(7, 32);
+        v.insert(8, 42);
+        v.insert(9, 123);
+        let dict = v.to_py_object(py);
+        let mut key_sum = 0;
+        let mut value_sum = 0;
+        for (key, value) in dict.iter(py) {
+            key_sum += key.extract::<i32>(py).unwrap();
+            value_sum += value.extract::<i32>(py).unwrap();
+        }
+        assert_eq!(7 + 8 + 9, key_sum);
+        assert_eq!(32 + 42 + 123, value_sum);
+        assert_eq!(0, PyDict::new(py).iter(py).count());
+    }
+
+    #[test]
+    fn test_get_item_or() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let dict = PyDict::new(py);
+        dict.set_item(py, "present", 1).unwrap();
+        assert!(dict.contains(py, "present").unwrap());
+        assert!(!dict.contains(py, "missing").unwrap());
+        assert_eq!(1, dict.get_item_or(py, "present", 2i32.to_py_object(py).into_object()).extract::<i32>(py).unwrap());
+        assert_eq!(2, dict.get_item_or(py, "missing", 2i32.to_py_object(py).into_object()).extract::<i32>(py).unwrap());
+        assert!(dict.get_item(py, "missing").is_none());
+    }
 }
@@ -103,10 +103,54 @@ exc_type!(ZeroDivisionError, PyExc_ZeroDivisionError);
 
 exc_type!(BufferError, PyExc_BufferError);
 
+#[cfg(feature="python3-sys")]
+exc_type!(BlockingIOError, PyExc_BlockingIOError);
+#[cfg(feature="python3-sys")]
+exc_type!(BrokenPipeError, PyExc_BrokenPipeError);
+#[cfg(feature="python3-sys")]
+exc_type!(ChildProcessError, PyExc_ChildProcessError);
+#[cfg(feature="python3-sys")]
+exc_type!(ConnectionError, PyExc_ConnectionError);
+#[cfg(feature="python3-sys")]
+exc_type!(ConnectionAbortedError, PyExc_ConnectionAbortedError);
+#[cfg(feature="python3-sys")]
+exc_type!(ConnectionRefusedError, PyExc_ConnectionRefusedError);
+#[cfg(feature="python3-sys")]
+exc_type!(ConnectionResetError, PyExc_ConnectionResetError);
+#[cfg(feature="python3-sys")]
+exc_type!(FileExistsError, PyExc_FileExistsError);
+#[cfg(feature="python3-sys")]
+exc_type!(FileNotFoundError, PyExc_FileNotFoundError);
+#[cfg(feature="python3-sys")]
+exc_type!(InterruptedError, PyExc_InterruptedError);
+#[cfg(feature="python3-sys")]
+exc_type!(IsADirectoryError, PyExc_IsADirectoryError);
+#[cfg(feature="python3-sys")]
+exc_type!(NotADirectoryError, PyExc_NotADirectoryError);
+#[cfg(feature="python3-sys")]
+exc_type!(PermissionError, PyExc_PermissionError);
+#[cfg(feature="python3-sys")]
+exc_type!(ProcessLookupError, PyExc_ProcessLookupError);
+#[cfg(feature="python3-sys")]
+exc_type!(TimeoutError, PyExc_TimeoutError);
+
 exc_type!(UnicodeDecodeError, PyExc_UnicodeDecodeError);
 exc_type!(UnicodeEncodeError, PyExc_UnicodeEncodeError);
 exc_type!(UnicodeTranslateError, PyExc_UnicodeTranslateError);
 
+exc_type!(Warning, PyExc_Warning);
+exc_type!(UserWarning, PyExc_UserWarning);
+exc_type!(DeprecationWarning, PyExc_DeprecationWarning);
+exc_type!(PendingDeprecationWarning, PyExc_PendingDeprecationWarning);
+exc_type!(SyntaxWarning, PyExc_SyntaxWarning);
+exc_type!(RuntimeWarning, PyExc_RuntimeWarning);
+exc_type!(FutureWarning, PyExc_FutureWarning);
+exc_type!(ImportWarning, PyExc_ImportWarning);
+exc_type!(UnicodeWarning, PyExc_UnicodeWarning);
+exc_type!(BytesWarning, PyExc_BytesWarning);
+#[cfg(feature="python3-sys")]
+exc_type!(ResourceWarning, PyExc_ResourceWarning);
+
 impl UnicodeDecodeError {
     pub fn new(py: Python, encoding: &CStr, input: &[u8], range: ops::Range<usize>, reason: &CStr) -> PyResult<UnicodeDecodeError> {
         unsafe {
@@ -16,8 +16,9 @@
 // OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
 // DEALINGS IN THE SOFTWARE.
 
+use std::marker::PhantomData;
 use python::{Python, PythonObject, ToPythonPointer, PythonObjectDowncastError};
-use conversion::ToPyObject;
+use conversion::{ToPyObject, FromPyObject};
 use objects::PyObject;
 use err::{PyErr, PyResult};
 use ffi;
@@ -52,6 +53,15 @@ impl <'p> PyIterator<'p> {
     pub fn into_object(self) -> PyObject {
         self.iter
     }
+
+    /// Converts this iterator into one that extracts each item to `T`
+    /// as it is retrieved, without collecting the items first.
+    #[inline]
+    pub fn extract_items<T>(self) -> PyExtractIterator<'p, T>
+        where T: for<'a> FromPyObject<'a>
+    {
+        PyExtractIterator { iter: self, done: false, _marker: PhantomData }
+    }
 }
 
 impl <'p> Iterator for PyIterator<'p> {
@@ -77,10 +87,43 @@ impl <'p> Iterator for PyIterator<'p> {
     }
 }
 
+/// An iterator that extracts the items of a Python iterator to `T` one at a time.
+///
+/// Created by `ObjectProtocol::iter_extract()` or `PyIterator::extract_items()`.
+pub struct PyExtractIterator<'p, T> {
+    iter: PyIterator<'p>,
+    done: bool,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl <'p, T> Iterator for PyExtractIterator<'p, T>
+    where T: for<'a> FromPyObject<'a>
+{
+    type Item = PyResult<T>;
+
+    /// Retrieves and extracts the next item.
+    /// If the Python iterator raises an exception or the item cannot be
+    /// extracted, returns `Some(Err(..))`; all further calls return `None`.
+    fn next(&mut self) -> Option<PyResult<T>> {
+        if self.done {
+            return None;
+        }
+        let py = self.iter.py;
+        let item = self.iter.next().map(|r| r.and_then(|obj| obj.extract::<T>(py)));
+        match item {
+            Some(Ok(_)) => {},
+            _ => self.done = true,
+        }
+        item
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use python::{Python, PythonObject};
     use conversion::ToPyObject;
+    use err::PyResult;
+    use objects::NoArgs;
     use objectprotocol::ObjectProtocol;
 
     #[test]
@@ -93,5 +136,45 @@ mod tests {
         assert_eq!(20, it.next().unwrap().unwrap().extract(py).unwrap());
         assert!(it.next().is_none());
     }
-}
 
+    #[test]
+    fn generator_iter() {
+        use objects::PyDict;
+
+        let gil_guard = Python::acquire_gil();
+        let py = gil_guard.python();
+        let d = PyDict::new(py);
+        py.run("def gen():\n    yield 1\n    yield 2\n    raise ValueError('done')\n", None, Some(&d)).unwrap();
+        let gen = d.get_item(py, "gen").unwrap().call(py, NoArgs, None).unwrap();
+        let mut values = Vec::new();
+        let mut error = None;
+        for item in gen.iter(py).unwrap() {
+            match item {
+                Ok(obj) => values.push(obj.extract::<i32>(py).unwrap()),
+                Err(e) => { error = Some(e); break; }
+            }
+        }
+        assert_eq!(values, vec![1, 2]);
+        assert!(error.unwrap().matches(py, py.get_type::<::objects::exc::ValueError>()));
+
+        let gen = py.eval("(x * x for x in range(4))", None, None).unwrap();
+        let squares: PyResult<Vec<i32>> = gen.iter(py).unwrap().map(|x| x?.extract(py)).collect();
+        assert_eq!(squares.unwrap(), vec![0, 1, 4, 9]);
+    }
+
+    #[test]
+    fn iter_extract() {
+        let gil_guard = Python::acquire_gil();
+        let py = gil_guard.python();
+        let gen = py.eval("(x for x in range(1000000))", None, None).unwrap();
+        let sum: PyResult<u64> = gen.iter_extract::<u64>(py).unwrap().sum();
+        assert_eq!(sum.unwrap(), 499999500000);
+
+        let gen = py.eval("(x for x in [1, 2, 'three', 4])", None, None).unwrap();
+        let mut it = gen.iter_extract::<i32>(py).unwrap();
+        assert_eq!(it.next().unwrap().unwrap(), 1);
+        assert_eq!(it.next().unwrap().unwrap(), 2);
+        assert!(it.next().unwrap().unwrap_err().matches(py, py.get_type::<::objects::exc::TypeError>()));
+        assert!(it.next().is_none());
+    }
+}
@@ -0,0 +1,233 @@
+// Copyright (c) 2015 Daniel Grunwald
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this
+// software and associated documentation files (the "Software"), to deal in the Software
+// without restriction, including without limitation the rights to use, copy, modify, merge,
+// publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons
+// to whom the Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED,
+// INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR
+// PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE
+// FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR
+// OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+use std::hash::{Hash, Hasher};
+use ffi;
+use python::{Python, PythonObject, ToPythonPointer, PyClone, PyDrop};
+use conversion::ToPyObject;
+use objects::PyObject;
+use err::{PyErr, PyResult};
+use Py_hash_t;
+
+/// A Python object together with its hash value, computed once at construction.
+///
+/// `KeyedPyObject` implements `Hash` and `Eq`, so it can be used as a key in
+/// Rust `HashMap`s and `HashSet`s. Hashing uses the cached value and never
+/// calls back into Python; equality only compares via Python `==` if the
+/// cached hashes match and the objects are not identical.
+///
+/// The cached hash is also used by `PyDict::get_item_keyed()`
+/// to skip rehashing the key on repeated dict lookups.
+///
+/// Unhashable objects are rejected by `KeyedPyObject::new()` rather than
+/// causing a panic later, inside `Hash::hash()`.
+///
+/// # Example
+/// ```
+/// use std::collections::HashMap;
+/// use cpython::{Python, PythonObject, ToPyObject, KeyedPyObject};
+///
+/// let gil = Python::acquire_gil();
+/// let py = gil.python();
+/// let mut cache = HashMap::new();
+/// let key = KeyedPyObject::new(py, "key".to_py_object(py).into_object()).unwrap();
+/// cache.insert(key, 1i64);
+/// let key = KeyedPyObject::new(py, "key".to_py_object(py).into_object()).unwrap();
+/// assert_eq!(Some(&1), cache.get(&key));
+/// ```
+pub struct KeyedPyObject {
+    obj: PyObject,
+    hash: Py_hash_t
+}
+
+impl KeyedPyObject {
+    /// Computes the hash of `obj` and wraps both.
+    ///
+    /// Fails with a `TypeError` if the object is unhashable.
+    pub fn new(py: Python, obj: PyObject) -> PyResult<KeyedPyObject> {
+        let hash = unsafe { ffi::PyObject_Hash(obj.as_ptr()) };
+        if hash == -1 {
+            let err = PyErr::fetch(py);
+            obj.release_ref(py);
+            Err(err)
+        } else {
+            Ok(KeyedPyObject { obj: obj, hash: hash })
+        }
+    }
+
+    /// Returns the cached hash value.
+    /// This is the same value as the Python expression `hash(self)`.
+    #[inline]
+    pub fn hash_value(&self) -> Py_hash_t {
+        self.hash
+    }
+
+    /// Gets the wrapped object.
+    #[inline]
+    pub fn as_object(&self) -> &PyObject {
+        &self.obj
+    }
+
+    /// Unwraps the object, discarding the cached hash.
+    #[inline]
+    pub fn into_object(self) -> PyObject {
+        self.obj
+    }
+}
+
+impl Hash for KeyedPyObject {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.hash.hash(state)
+    }
+}
+
+/// Compares using the Python `==` operator.
+/// Acquires the GIL if the cached hashes are equal but the objects are not identical.
+/// If the comparison raises an exception, the objects are considered unequal.
+impl PartialEq for KeyedPyObject {
+    fn eq(&self, other: &KeyedPyObject) -> bool {
+        if self.hash != other.hash {
+            return false;
+        }
+        if self.obj == other.obj {
+            return true;
+        }
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        match unsafe { ffi::PyObject_RichCompareBool(self.obj.as_ptr(), other.obj.as_ptr(), ffi::Py_EQ) } {
+            1 => true,
+            0 => false,
+            _ => {
+                PyErr::fetch(py);
+                false
+            }
+        }
+    }
+}
+
+impl Eq for KeyedPyObject {}
+
+impl PyClone for KeyedPyObject {
+    #[inline]
+    fn clone_ref(&self, py: Python) -> KeyedPyObject {
+        KeyedPyObject { obj: self.obj.clone_ref(py), hash: self.hash }
+    }
+}
+
+impl PyDrop for KeyedPyObject {
+    #[inline]
+    fn release_ref(self, py: Python) {
+        self.obj.release_ref(py);
+    }
+}
+
+impl ToPyObject for KeyedPyObject {
+    type ObjectType = PyObject;
+
+    #[inline]
+    fn to_py_object(&self, py: Python) -> PyObject {
+        self.obj.clone_ref(py)
+    }
+
+    #[inline]
+    fn into_py_object(self, _py: Python) -> PyObject {
+        self.obj
+    }
+
+    #[inline]
+    fn with_borrowed_ptr<F, R>(&self, _py: Python, f: F) -> R
+        where F: FnOnce(*mut ffi::PyObject) -> R
+    {
+        f(self.obj.as_ptr())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+    use python::{Python, PythonObject, PyClone};
+    use conversion::ToPyObject;
+    use objects::{PyDict, KeyedPyObject};
+    use objectprotocol::ObjectProtocol;
+
+    #[test]
+    fn test_unhashable() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let list = vec![1i32].to_py_object(py).into_object();
+        assert!(KeyedPyObject::new(py, list).is_err());
+    }
+
+    #[test]
+    fn test_string_keys() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let key = |s: &str| KeyedPyObject::new(py, s.to_py_object(py).into_object()).unwrap();
+        let mut map: HashMap<KeyedPyObject, i64> = HashMap::new();
+        for (i, s) in ["alpha", "beta", "gamma"].iter().enumerate() {
+            map.insert(key(s), i as i64);
+        }
+        // every call to key() creates a new, but equal, Python string
+        assert!(key("beta").as_object() != key("beta").as_object());
+        assert_eq!(Some(&1), map.get(&key("beta")));
+        assert_eq!(None, map.get(&key("delta")));
+        map.insert(key("alpha"), 10);
+        assert_eq!(3, map.len());
+        assert_eq!(Some(&10), map.get(&key("alpha")));
+    }
+
+    #[test]
+    fn test_hash_computed_once() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let d = ::python::globals_with_builtins(py).unwrap();
+        py.run("\
+class Key(object):
+    calls = 0
+    def __hash__(self):
+        Key.calls += 1
+        return 42
+    def __eq__(self, other):
+        return isinstance(other, Key)
+key = Key()
+", Some(&d), None).unwrap();
+        let obj = d.get_item(py, "key").unwrap();
+        let keyed = KeyedPyObject::new(py, obj.clone_ref(py)).unwrap();
+        assert_eq!(obj.hash(py).unwrap(), keyed.hash_value());
+        let calls = || py.eval("Key.calls", Some(&d), None).unwrap().extract::<i32>(py).unwrap();
+        let before = calls();
+
+        let mut map = HashMap::new();
+        map.insert(keyed.clone_ref(py), 1i32);
+        let lookup = PyDict::new(py);
+        lookup.set_item(py, &obj, 2i32).unwrap();
+        // after the two insertions above, lookups through the keyed object must not rehash
+        let before_lookups = calls();
+        assert_eq!(before + 1, before_lookups);
+        for _ in 0..100 {
+            assert_eq!(Some(&1), map.get(&keyed));
+            assert_eq!(2, lookup.get_item_keyed(py, &keyed).unwrap().unwrap().extract::<i32>(py).unwrap());
+        }
+        #[cfg(feature="python3-sys")]
+        assert_eq!(before_lookups, calls());
+        // Python 2 has no dict lookup with a known hash, so only the HashMap skips rehashing
+        #[cfg(feature="python27-sys")]
+        assert_eq!(before_lookups + 100, calls());
+    }
+}
@@ -19,9 +19,39 @@
 use python::{Python, PythonObject, ToPythonPointer, PyClone, PyDrop};
 use err::{self, PyErr, PyResult};
 use super::object::PyObject;
+use super::exc;
+use super::sequence::{item_index, slice_index};
 use ffi::{self, Py_ssize_t};
 use conversion::{ToPyObject, FromPyObject};
 
+/// Creates a `PyList` from a list of elements.
+///
+/// The elements can be of different types, as long as each implements `ToPyObject`.
+/// Expands to an expression of type `PyResult<PyList>`, for symmetry with `py_dict!`.
+///
+/// # Example
+/// ```
+/// #[macro_use] extern crate cpython;
+/// use cpython::Python;
+///
+/// # fn main() {
+/// let gil = Python::acquire_gil();
+/// let py = gil.python();
+/// let list = py_list!(py, [1, "two", 3.0]).unwrap();
+/// assert_eq!(list.len(py), 3);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! py_list {
+    ($py:expr, [ $( $element:expr ),* $(,)* ]) => {{
+        let py = $py;
+        let result: $crate::PyResult<$crate::PyList> = Ok($crate::PyList::new(py, &[
+            $( $crate::PythonObject::into_object($crate::ToPyObject::into_py_object($element, py)) ),*
+        ]));
+        result
+    }};
+}
+
 /// Represents a Python `list`.
 pub struct PyList(PyObject);
 
@@ -34,7 +64,7 @@ impl PyList {
             let ptr = ffi::PyList_New(elements.len() as Py_ssize_t);
             let t = err::result_from_owned_ptr(py, ptr).unwrap().unchecked_cast_into::<PyList>();
             for (i, e) in elements.iter().enumerate() {
-                ffi::PyList_SetItem(ptr, i as Py_ssize_t, e.steal_ptr(py));
+                ffi::PyList_SET_ITEM(ptr, i as Py_ssize_t, e.steal_ptr(py));
             }
             t
         }
@@ -60,6 +90,55 @@ impl PyList {
         }
     }
 
+    /// Gets the item at the specified index.
+    ///
+    /// Returns an `IndexError` if the index is out of range.
+    pub fn get_item_checked(&self, py: Python, index: usize) -> PyResult<PyObject> {
+        if index < self.len(py) {
+            Ok(self.get_item(py, index))
+        } else {
+            Err(PyErr::new::<exc::IndexError, _>(py, "list index out of range"))
+        }
+    }
+
+    /// Gets the item at the specified index.
+    /// This is equivalent to the Python expression 'self[index]'.
+    ///
+    /// Like in Python, negative indices count from the end of the list.
+    /// Returns an `IndexError` if the index is out of range.
+    pub fn get_item_at(&self, py: Python, index: isize) -> PyResult<PyObject> {
+        match item_index(index, self.len(py)) {
+            Some(index) => Ok(self.get_item(py, index)),
+            None => Err(PyErr::new::<exc::IndexError, _>(py, "list index out of range"))
+        }
+    }
+
+    /// Gets a new list with the items from `low` to `high` (exclusive).
+    /// This is equivalent to the Python expression 'self[low:high]'.
+    ///
+    /// Like in Python, negative indices count from the end of the list,
+    /// and out-of-range indices are clamped to the valid range.
+    pub fn get_slice(&self, py: Python, low: isize, high: isize) -> PyList {
+        let len = self.len(py);
+        unsafe {
+            err::cast_from_owned_ptr_or_panic(py,
+                ffi::PyList_GetSlice(self.0.as_ptr(), slice_index(low, len), slice_index(high, len)))
+        }
+    }
+
+    /// Replaces the items from `low` to `high` (exclusive) with the items of `items`,
+    /// which must be a sequence.
+    /// This is equivalent to the Python statement 'self[low:high] = items'.
+    ///
+    /// Indices are interpreted like in `get_slice()`.
+    pub fn set_slice<V>(&self, py: Python, low: isize, high: isize, items: V) -> PyResult<()> where V: ToPyObject {
+        let len = self.len(py);
+        items.with_borrowed_ptr(py, |items| unsafe {
+            err::error_on_minusone(py,
+                ffi::PyList_SetSlice(self.0.as_ptr(), slice_index(low, len), slice_index(high, len), items))
+        })
+    }
+
     /// Sets the item at the specified index.
     ///
     /// Panics if the index is out of range.
@@ -76,6 +155,39 @@ impl PyList {
         assert!(r == 0);
     }
 
+    /// Inserts an item before the specified index.
+    /// This is equivalent to the Python expression 'self.insert(index, item)'.
+    ///
+    /// Like in Python, indices past the end of the list append the item.
+    pub fn insert<V>(&self, py: Python, index: usize, item: V) -> PyResult<()> where V: ToPyObject {
+        item.with_borrowed_ptr(py, |item| unsafe {
+            err::error_on_minusone(py, ffi::PyList_Insert(self.0.as_ptr(), index as Py_ssize_t, item))
+        })
+    }
+
+    /// Sorts the list in place.
+    /// This is equivalent to the Python expression 'self.sort()'.
+    pub fn sort(&self, py: Python) -> PyResult<()> {
+        unsafe { err::error_on_minusone(py, ffi::PyList_Sort(self.0.as_ptr())) }
+    }
+
+    /// Reverses the list in place.
+    /// This is equivalent to the Python expression 'self.reverse()'.
+    pub fn reverse(&self, py: Python) -> PyResult<()> {
+        unsafe { err::error_on_minusone(py, ffi::PyList_Reverse(self.0.as_ptr())) }
+    }
+
+    /// Extracts all elements of the list into a `Vec`.
+    pub fn to_vec<T>(&self, py: Python) -> PyResult<Vec<T>> where for<'a> T: FromPyObject<'a> {
+        let mut v = Vec::with_capacity(self.len(py));
+        for item in self.iter(py) {
+            let value = item.extract::<T>(py);
+            item.release_ref(py);
+            v.push(value?);
+        }
+        Ok(v)
+    }
+
     #[inline]
     pub fn iter<'a, 'p>(&'a self, py: Python<'p>) -> PyListIterator<'a, 'p> {
         PyListIterator { py: py, list: self, index: 0 }
@@ -107,19 +219,31 @@ impl <'a, 'p> Iterator for PyListIterator<'a, 'p> {
     // might change during the iteration.
 }
 
+/// Converts the elements into a preallocated list.
+///
+/// If an element conversion panics, the partially filled list is released
+/// (the unfilled slots are still NULL, which list deallocation handles).
+fn list_from_iter<I>(py: Python, len: usize, elements: I) -> PyList
+    where I: Iterator<Item=PyObject>
+{
+    unsafe {
+        let ptr = ffi::PyList_New(len as Py_ssize_t);
+        let t = err::cast_from_owned_ptr_or_panic(py, ptr);
+        let mut count = 0;
+        for (i, obj) in elements.take(len).enumerate() {
+            ffi::PyList_SET_ITEM(ptr, i as Py_ssize_t, obj.steal_ptr());
+            count += 1;
+        }
+        assert_eq!(count, len, "iterator produced fewer elements than expected");
+        t
+    }
+}
+
 impl <T> ToPyObject for [T] where T: ToPyObject {
     type ObjectType = PyList;
 
     fn to_py_object(&self, py: Python) -> PyList {
-        unsafe {
-            let ptr = ffi::PyList_New(self.len() as Py_ssize_t);
-            let t = err::cast_from_owned_ptr_or_panic(py, ptr);
-            for (i, e) in self.iter().enumerate() {
-                let obj = e.to_py_object(py).into_object();
-                ffi::PyList_SetItem(ptr, i as Py_ssize_t, obj.steal_ptr());
-            }
-            t
-        }
+        list_from_iter(py, self.len(), self.iter().map(|e| e.to_py_object(py).into_object()))
     }
 }
 
@@ -131,23 +255,44 @@ impl <T> ToPyObject for Vec<T> where T: ToPyObject {
     }
 
     fn into_py_object(self, py: Python) -> PyList {
-        unsafe {
-            let ptr = ffi::PyList_New(self.len() as Py_ssize_t);
-            let t = err::cast_from_owned_ptr_or_panic(py, ptr);
-            for (i, e) in self.into_iter().enumerate() {
-                let obj = e.into_py_object(py).into_object();
-                ffi::PyList_SetItem(ptr, i as Py_ssize_t, obj.steal_ptr());
-            }
-            t
-        }
+        let len = self.len();
+        list_from_iter(py, len, self.into_iter().map(|e| e.into_py_object(py).into_object()))
     }
 }
 
 #[cfg(test)]
 mod test {
+    use std::isize;
     use python::{Python, PythonObject};
     use conversion::ToPyObject;
-    use objects::PyList;
+    use objects::{PyList, exc};
+
+    #[test]
+    fn test_py_list_macro() {
+        use objects::PyDict;
+        use objectprotocol::ObjectProtocol;
+        fn build(py: Python) -> ::PyResult<PyList> {
+            py_list!(py, [
+                1,
+                "two",
+                py_list!(py, [3.0, 4.0])?,
+                py_dict!(py, {"five" => 5, 6 => py_list!(py, [])?})?,
+            ])
+        }
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let list = build(py).unwrap();
+        assert_eq!(list.len(py), 4);
+        assert_eq!(list.get_item(py, 0).extract::<i32>(py).unwrap(), 1);
+        assert_eq!(list.get_item(py, 1).extract::<String>(py).unwrap(), "two");
+        assert_eq!(list.get_item(py, 2).extract::<Vec<f64>>(py).unwrap(), vec![3.0, 4.0]);
+        let dict = list.get_item(py, 3).cast_into::<PyDict>(py).unwrap();
+        assert_eq!(dict.get_item(py, "five").unwrap().extract::<i32>(py).unwrap(), 5);
+        assert_eq!(dict.get_item(py, 6).unwrap().cast_into::<PyList>(py).unwrap().len(py), 0);
+        let expected = py.eval("[1, 'two', [3.0, 4.0], {'five': 5, 6: []}]", None, None).unwrap();
+        assert!(list.as_object().equals(py, expected).unwrap());
+    }
 
     #[test]
     fn test_len() {
@@ -182,6 +327,47 @@ mod test {
         assert_eq!(42, list.get_item(py, 0).extract::<i32>(py).unwrap());
     }
 
+    #[test]
+    fn test_large_vec() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let v: Vec<i32> = (0..100000).collect();
+        let list = v.to_py_object(py);
+        assert_eq!(list.len(py), 100000);
+        assert_eq!(99999, list.get_item(py, 99999).extract::<i32>(py).unwrap());
+        let list = v.clone().into_py_object(py);
+        assert_eq!(list.into_object().extract::<Vec<i32>>(py).unwrap(), v);
+    }
+
+    #[test]
+    fn test_conversion_panics_partway() {
+        use std::panic;
+        use python::PyClone;
+        use objects::PyObject;
+
+        struct Element(PyObject, bool);
+        impl ToPyObject for Element {
+            type ObjectType = PyObject;
+            fn to_py_object(&self, py: Python) -> PyObject {
+                if self.1 { panic!("conversion failed"); }
+                self.0.clone_ref(py)
+            }
+        }
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let obj = py.eval("object()", None, None).unwrap();
+        let refcnt = obj.get_refcnt(py);
+        let v = vec![Element(obj.clone_ref(py), false), Element(obj.clone_ref(py), false),
+                     Element(obj.clone_ref(py), true), Element(obj.clone_ref(py), false)];
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| v.to_py_object(py)));
+        assert!(result.is_err());
+        // the elements that were already converted have been released with the list
+        assert_eq!(obj.get_refcnt(py), refcnt + 4);
+        drop(v);
+        assert_eq!(obj.get_refcnt(py), refcnt);
+    }
+
     #[test]
     fn test_insert_item() {
         let gil = Python::acquire_gil();
@@ -220,4 +406,73 @@ mod test {
         let v2 = list.into_object().extract::<Vec<i32>>(py).unwrap();
         assert_eq!(v, v2);
     }
+
+    #[test]
+    fn test_get_item_checked() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let list = vec![2, 3].to_py_object(py);
+        assert_eq!(3, list.get_item_checked(py, 1).unwrap().extract::<i32>(py).unwrap());
+        let err = list.get_item_checked(py, 2).unwrap_err();
+        assert!(err.matches(py, py.get_type::<::objects::exc::IndexError>()));
+    }
+
+    #[test]
+    fn test_to_vec() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let list = vec![1i64, -2, 3].to_py_object(py);
+        assert_eq!(vec![1i64, -2, 3], list.to_vec::<i64>(py).unwrap());
+        list.insert(py, 0, "x").unwrap();
+        assert!(list.to_vec::<i64>(py).is_err());
+    }
+
+    #[test]
+    fn test_insert_sort_reverse() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let list = vec![5, 1, 4].to_py_object(py);
+        list.insert(py, 1, 3).unwrap();
+        list.insert(py, 100, 2).unwrap();
+        assert_eq!(vec![5, 3, 1, 4, 2], list.to_vec::<i32>(py).unwrap());
+        list.sort(py).unwrap();
+        assert_eq!(vec![1, 2, 3, 4, 5], list.to_vec::<i32>(py).unwrap());
+        list.reverse(py).unwrap();
+        assert_eq!(vec![5, 4, 3, 2, 1], list.to_vec::<i32>(py).unwrap());
+        list.insert(py, 0, "x").unwrap();
+        // Python 2 orders values of different types, Python 3 raises TypeError
+        #[cfg(feature="python3-sys")]
+        assert!(list.sort(py).is_err());
+    }
+
+    #[test]
+    fn test_slice() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let list = (0..10).collect::<Vec<i32>>().to_py_object(py);
+        assert_eq!(vec![2, 3, 4], list.get_slice(py, 2, 5).to_vec::<i32>(py).unwrap());
+        assert_eq!(vec![7, 8, 9], list.get_slice(py, -3, 100).to_vec::<i32>(py).unwrap());
+        assert_eq!(vec![0, 1], list.get_slice(py, -100, 2).to_vec::<i32>(py).unwrap());
+        assert_eq!(0, list.get_slice(py, 5, 2).len(py));
+
+        list.set_slice(py, 2, 5, vec![-1]).unwrap();
+        assert_eq!(vec![0, 1, -1, 5, 6, 7, 8, 9], list.to_vec::<i32>(py).unwrap());
+        list.set_slice(py, -2, isize::MAX, Vec::<i32>::new()).unwrap();
+        assert_eq!(vec![0, 1, -1, 5, 6, 7], list.to_vec::<i32>(py).unwrap());
+        assert!(list.set_slice(py, 0, 1, 5).is_err());
+    }
+
+    #[test]
+    fn test_get_item_at() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let list = vec![2, 3, 5].to_py_object(py);
+        assert_eq!(5, list.get_item_at(py, -1).unwrap().extract::<i32>(py).unwrap());
+        assert_eq!(2, list.get_item_at(py, -3).unwrap().extract::<i32>(py).unwrap());
+        assert_eq!(3, list.get_item_at(py, 1).unwrap().extract::<i32>(py).unwrap());
+        for &index in &[3, -4, -100, isize::MIN, isize::MAX] {
+            let err = list.get_item_at(py, index).unwrap_err();
+            assert!(err.matches(py, py.get_type::<exc::IndexError>()));
+        }
+    }
 }
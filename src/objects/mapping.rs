@@ -0,0 +1,139 @@
+// Copyright (c) 2015 Daniel Grunwald
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this
+// software and associated documentation files (the "Software"), to deal in the Software
+// without restriction, including without limitation the rights to use, copy, modify, merge,
+// publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons
+// to whom the Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED,
+// INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR
+// PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE
+// FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR
+// OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+use ffi;
+use python::{Python, PythonObject, ToPythonPointer};
+use conversion::ToPyObject;
+use objects::{PyObject, PyList};
+use err::{self, PyErr, PyResult};
+use exc;
+
+/// Represents a reference to a python object supporting the mapping protocol.
+pub struct PyMapping(PyObject);
+
+pyobject_newtype!(PyMapping, PyMapping_Check);
+
+impl PyMapping {
+    /// Returns the number of keys in the mapping. This is equivalent to Python `len()`.
+    #[inline]
+    pub fn len(&self, py: Python) -> PyResult<usize> {
+        let v = unsafe { ffi::PyMapping_Size(self.0.as_ptr()) };
+        if v == -1 {
+            Err(PyErr::fetch(py))
+        } else {
+            Ok(v as usize)
+        }
+    }
+
+    /// Determine if the mapping contains the specified key.
+    /// This is equivalent to the Python expression `key in self`.
+    pub fn contains<K>(&self, py: Python, key: K) -> PyResult<bool> where K: ToPyObject {
+        key.with_borrowed_ptr(py, |key| unsafe {
+            match ffi::PySequence_Contains(self.0.as_ptr(), key) {
+                1 => Ok(true),
+                0 => Ok(false),
+                _ => Err(PyErr::fetch(py))
+            }
+        })
+    }
+
+    /// Gets the value for the specified key.
+    /// This is equivalent to the Python expression `self[key]`.
+    #[inline]
+    pub fn get_item<K>(&self, py: Python, key: K) -> PyResult<PyObject> where K: ToPyObject {
+        key.with_borrowed_ptr(py, |key| unsafe {
+            err::result_from_owned_ptr(py, ffi::PyObject_GetItem(self.0.as_ptr(), key))
+        })
+    }
+
+    /// Gets the value for the specified key, or `None` if the key is not present.
+    ///
+    /// Only a `KeyError` is turned into `None`; other exceptions are propagated.
+    pub fn get<K>(&self, py: Python, key: K) -> PyResult<Option<PyObject>> where K: ToPyObject {
+        match self.get_item(py, key) {
+            Ok(v) => Ok(Some(v)),
+            Err(ref e) if e.matches(py, py.get_type::<exc::KeyError>()) => Ok(None),
+            Err(e) => Err(e)
+        }
+    }
+
+    /// Returns the list of keys of the mapping.
+    /// This is equivalent to the Python expression `list(self.keys())`.
+    #[inline]
+    pub fn keys(&self, py: Python) -> PyResult<PyList> {
+        unsafe {
+            err::result_cast_from_owned_ptr(py, ffi::PyMapping_Keys(self.0.as_ptr()))
+        }
+    }
+
+    /// Returns the list of values of the mapping.
+    /// This is equivalent to the Python expression `list(self.values())`.
+    #[inline]
+    pub fn values(&self, py: Python) -> PyResult<PyList> {
+        unsafe {
+            err::result_cast_from_owned_ptr(py, ffi::PyMapping_Values(self.0.as_ptr()))
+        }
+    }
+
+    /// Returns the list of `(key, value)` tuples of the mapping.
+    /// This is equivalent to the Python expression `list(self.items())`.
+    #[inline]
+    pub fn items(&self, py: Python) -> PyResult<PyList> {
+        unsafe {
+            err::result_cast_from_owned_ptr(py, ffi::PyMapping_Items(self.0.as_ptr()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use python::{Python, PythonObject};
+    use conversion::ToPyObject;
+    use objects::{PyDict, PyMapping};
+
+    #[test]
+    fn test_numbers_are_not_mappings() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        assert!(42i32.to_py_object(py).into_object().cast_into::<PyMapping>(py).is_err());
+    }
+
+    #[test]
+    fn test_dict_mapping() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let dict = PyDict::new(py);
+        dict.set_item(py, "a", 1i32).unwrap();
+        dict.set_item(py, "b", 2i32).unwrap();
+        let mapping = dict.into_object().cast_into::<PyMapping>(py).unwrap();
+        assert_eq!(2, mapping.len(py).unwrap());
+        assert!(mapping.contains(py, "a").unwrap());
+        assert!(!mapping.contains(py, "c").unwrap());
+        assert_eq!(1, mapping.get_item(py, "a").unwrap().extract::<i32>(py).unwrap());
+        assert!(mapping.get_item(py, "c").is_err());
+        assert_eq!(2, mapping.get(py, "b").unwrap().unwrap().extract::<i32>(py).unwrap());
+        assert!(mapping.get(py, "c").unwrap().is_none());
+        let mut keys: Vec<String> = mapping.keys(py).unwrap().into_object().extract(py).unwrap();
+        keys.sort();
+        assert_eq!(vec!["a", "b"], keys);
+        let mut values: Vec<i32> = mapping.values(py).unwrap().into_object().extract(py).unwrap();
+        values.sort();
+        assert_eq!(vec![1, 2], values);
+        assert_eq!(2, mapping.items(py).unwrap().len(py));
+    }
+}
@@ -20,24 +20,30 @@ pub use self::object::PyObject;
 pub use self::typeobject::PyType;
 pub use self::module::PyModule;
 
-pub use self::string::{PyBytes, PyString, PyStringData};
+pub use self::string::{PyBytes, PyString, PyStringData, PyStringBuilder};
+pub use self::bytearray::PyByteArray;
 #[cfg(feature="python27-sys")]
 pub use self::string::PyUnicode;
 #[cfg(feature="python3-sys")]
 pub use self::string::PyString as PyUnicode;
 
-pub use self::iterator::PyIterator;
+pub use self::iterator::{PyIterator, PyExtractIterator};
 pub use self::boolobject::PyBool;
 pub use self::tuple::{PyTuple, NoArgs};
 pub use self::dict::PyDict;
-pub use self::set::PySet;
+pub use self::set::{PySet, PyFrozenSet};
 pub use self::list::PyList;
 #[cfg(feature="python27-sys")]
 pub use self::num::PyInt;
 #[cfg(feature="python3-sys")]
 pub use self::num::PyLong as PyInt;
 pub use self::num::{PyLong, PyFloat};
+pub use self::complex::{PyComplex, Complex64};
+pub use self::slice::PySlice;
+pub use self::capsule::PyCapsule;
 pub use self::sequence::PySequence;
+pub use self::mapping::PyMapping;
+pub use self::keyed::KeyedPyObject;
 
 #[macro_export(local_inner_macros)]
 macro_rules! pyobject_newtype(
@@ -70,6 +76,13 @@ macro_rules! pyobject_newtype(
                 ::std::mem::transmute(obj)
             }
         }
+
+        impl ::std::fmt::Debug for $name {
+            #[inline]
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                ::std::fmt::Debug::fmt(&self.0, f)
+            }
+        }
     );
     ($name: ident, $checkfunction: ident) => (
         pyobject_newtype!($name);
@@ -126,14 +139,24 @@ mod object;
 mod typeobject;
 mod module;
 mod string;
-mod dict;
+mod bytearray;
+#[macro_use] mod dict;
 mod set;
 mod iterator;
 mod boolobject;
 mod tuple;
 mod list;
 mod num;
+mod complex;
+mod slice;
+mod capsule;
+#[cfg(feature="python3-sys")]
+mod time;
+#[cfg(all(feature="chrono", feature="python3-sys"))]
+mod datetime;
 mod sequence;
+mod mapping;
+mod keyed;
 pub mod exc;
 
 #[cfg(feature="python27-sys")]
@@ -17,12 +17,13 @@
 // DEALINGS IN THE SOFTWARE.
 
 use std;
+use std::ptr;
 use ffi;
 use libc::c_char;
 use python::{Python, PythonObject, PyDrop};
 use objectprotocol::ObjectProtocol;
 use conversion::ToPyObject;
-use objects::{PyObject, PyTuple, PyDict, exc};
+use objects::{PyObject, PyTuple, PyDict, PyType, exc};
 use py_class::PythonObjectFromPyClassMacro;
 use err::{self, PyResult, PyErr};
 use std::ffi::{CStr, CString};
@@ -42,6 +43,9 @@ impl PyModule {
     }
 
     /// Import the Python module with the specified name.
+    ///
+    /// For a dotted name like `os.path`, this returns the submodule (`os.path`),
+    /// not the top-level package.
     pub fn import(py: Python, name: &str) -> PyResult<PyModule> {
         let name = CString::new(name).unwrap();
         unsafe {
@@ -49,6 +53,34 @@ impl PyModule {
         }
     }
 
+    /// Import the Python module with the specified name, together with the
+    /// names in `fromlist`.
+    ///
+    /// This is equivalent to the Python statement `from name import fromlist...`,
+    /// except that the module `name` itself is returned.
+    /// Submodules listed in `fromlist` are imported as well and can be accessed
+    /// as attributes of the returned module.
+    /// Fails with `ImportError` if a name in `fromlist` can't be found.
+    pub fn import_from(py: Python, name: &str, fromlist: &[&str]) -> PyResult<PyModule> {
+        if fromlist.is_empty() {
+            // An empty fromlist would make Python return the top-level package.
+            return PyModule::import(py, name);
+        }
+        let c_name = CString::new(name).unwrap();
+        let list = fromlist.to_py_object(py);
+        let module: PyModule = unsafe {
+            err::result_cast_from_owned_ptr(py, ffi::PyImport_ImportModuleEx(
+                c_name.as_ptr() as *mut _, ptr::null_mut(), ptr::null_mut(), list.as_object().as_ptr()))?
+        };
+        for item in fromlist {
+            if !module.as_object().hasattr(py, item)? {
+                return Err(PyErr::new::<exc::ImportError, _>(py,
+                    format!("cannot import name '{}' from '{}'", item, name)));
+            }
+        }
+        Ok(module)
+    }
+
     /// Return the dictionary object that implements module's namespace;
     /// this object is the same as the `__dict__` attribute of the module object.
     pub fn dict(&self, py: Python) -> PyDict {
@@ -84,6 +116,11 @@ impl PyModule {
         unsafe { self.str_from_ptr(py, ffi::PyModule_GetFilename(self.0.as_ptr())) }
     }
 
+    /// Sets the module filename (the `__file__` attribute).
+    pub fn set_filename(&self, py: Python, filename: &str) -> PyResult<()> {
+        self.as_object().setattr(py, "__file__", filename)
+    }
+
     /// Gets a member from the module.
     /// This is equivalent to the Python expression: `getattr(module, name)`
     pub fn get(&self, py: Python, name: &str) -> PyResult<PyObject> {
@@ -123,6 +160,28 @@ impl PyModule {
         self.as_object().setattr(py, name, value)
     }
 
+    /// Adds a function to the module.
+    ///
+    /// This is a convenience function that sets `function.__module__` to this module's name
+    /// and adds the function to this module.
+    /// It is intended to be used with functions created by `py_fn!()`.
+    pub fn add_function(&self, py: Python, name: &str, function: PyObject) -> PyResult<()> {
+        function.setattr(py, "__module__", self.name(py)?)?;
+        self.add(py, name, function)
+    }
+
+    /// Adds a type object to the module.
+    ///
+    /// For heap types, this also sets `ty.__module__` to this module's name.
+    /// Statically allocated types don't allow changing `__module__`, so they are added unchanged.
+    /// To add a `py_class!()` type, use `add_class()` instead.
+    pub fn add_type(&self, py: Python, name: &str, ty: &PyType) -> PyResult<()> {
+        if unsafe { ffi::PyType_HasFeature(ty.as_type_ptr(), ffi::Py_TPFLAGS_HEAPTYPE) } != 0 {
+            ty.as_object().setattr(py, "__module__", self.name(py)?)?;
+        }
+        self.add(py, name, ty)
+    }
+
     /// Adds a new extension type to the module.
     ///
     /// This is a convenience function that initializes the `py_class!()`,
@@ -133,6 +192,130 @@ impl PyModule {
     {
         T::add_to_module(py, self)
     }
+
+    /// Makes the module read-only for Python code.
+    ///
+    /// This changes the module's class to a subclass of `types.ModuleType`
+    /// whose `__setattr__` and `__delattr__` raise `AttributeError`,
+    /// so that plain attribute assignment (including `m.__class__ = ...`)
+    /// can no longer rebind or remove module-level names.
+    /// `m.__dict__` and `vars(m)` return a read-only view of the module dict.
+    /// The module dict itself is unaffected, so Rust code can still modify it
+    /// via `PyModule::dict()`.
+    ///
+    /// This is a guard against accidental modification, not a security boundary.
+    /// Python code can still modify the module:
+    ///
+    ///  * by calling the base class methods directly, e.g.
+    ///    `types.ModuleType.__setattr__(m, 'x', 1)` or
+    ///    `object.__setattr__(m, '__class__', types.ModuleType)`;
+    ///  * through `object.__getattribute__(m, '__dict__')`, which returns the real dict;
+    ///  * from functions defined in the module, using `global x; x = 1`,
+    ///    since their globals are the module dict.
+    ///
+    /// Requires Python 3.5 or later, which allows assigning `__class__` on modules.
+    #[cfg(feature="python3-sys")]
+    pub fn freeze(&self, py: Python) -> PyResult<()> {
+        let locals = PyDict::new(py);
+        py.run(FROZEN_MODULE_TYPE, None, Some(&locals))?;
+        let ty = locals.as_object().get_item(py, "FrozenModule")?;
+        self.as_object().setattr(py, "__class__", ty)
+    }
+}
+
+#[cfg(feature="python3-sys")]
+const FROZEN_MODULE_TYPE: &'static str = "
+import types
+class FrozenModule(types.ModuleType):
+    __slots__ = ()
+    def __setattr__(self, name, value):
+        raise AttributeError('cannot set attribute %r of frozen module %r' % (name, self.__name__))
+    def __delattr__(self, name):
+        raise AttributeError('cannot delete attribute %r of frozen module %r' % (name, self.__name__))
+    def __getattribute__(self, name):
+        value = super().__getattribute__(name)
+        if name == '__dict__':
+            return __import__('types').MappingProxyType(value)
+        return value
+    def __dir__(self):
+        return sorted(super().__getattribute__('__dict__'))
+";
+
+#[cfg(test)]
+mod test {
+    use python::{Python, PythonObject};
+    use objectprotocol::ObjectProtocol;
+    use objects::{exc, PyModule, PyDict, PyType};
+
+    #[test]
+    #[cfg(feature="python3-sys")]
+    fn test_freeze() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let m = PyModule::new(py, "frozen_test").unwrap();
+        m.add(py, "x", 1).unwrap();
+        m.freeze(py).unwrap();
+        let locals = PyDict::new(py);
+        locals.set_item(py, "m", &m).unwrap();
+        assert!(py.run("m.y = 2", None, Some(&locals)).is_err());
+        assert!(py.run("del m.x", None, Some(&locals)).is_err());
+        assert!(py.run("m.x = 3", None, Some(&locals)).is_err());
+        let raises = |code: &str, exc: PyType| {
+            py.run(code, None, Some(&locals)).unwrap_err().matches(py, exc)
+        };
+        assert!(raises("import types; m.__class__ = types.ModuleType", py.get_type::<exc::AttributeError>()));
+        assert!(raises("m.__dict__['x'] = 4", py.get_type::<exc::TypeError>()));
+        assert!(raises("vars(m)['x'] = 5", py.get_type::<exc::TypeError>()));
+        assert!(raises("del m.__dict__['x']", py.get_type::<exc::TypeError>()));
+        assert!(py.eval("'x' in dir(m) and m.__dict__['x'] == 1", None, Some(&locals))
+            .unwrap().extract::<bool>(py).unwrap());
+        assert_eq!(1, m.get(py, "x").unwrap().extract::<i32>(py).unwrap());
+        assert!(!m.as_object().hasattr(py, "y").unwrap());
+    }
+
+
+    #[test]
+    fn test_import_dotted() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let path = PyModule::import(py, "os.path").unwrap();
+        let joined: String = path.call(py, "join", ("a", "b"), None).unwrap().extract(py).unwrap();
+        let sep: String = PyModule::import(py, "os").unwrap().get(py, "sep").unwrap().extract(py).unwrap();
+        assert_eq!(joined, format!("a{}b", sep));
+    }
+
+    #[test]
+    fn test_import_from() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let os = PyModule::import_from(py, "os", &["path"]).unwrap();
+        assert_eq!(os.name(py).unwrap(), "os");
+        let path = os.get(py, "path").unwrap();
+        assert!(path.call_method(py, "join", ("a", "b"), None).is_ok());
+
+        let xml = py.import_from("xml", &["dom"]).unwrap();
+        assert_eq!(xml.name(py).unwrap(), "xml");
+        assert!(xml.as_object().hasattr(py, "dom").unwrap());
+        assert!(py.import_from("os", &["no_such_name_in_os"]).is_err());
+    }
+
+    #[test]
+    fn test_metadata() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let json = PyModule::import(py, "json").unwrap();
+        assert_eq!(json.name(py).unwrap(), "json");
+        // on Python 2, this is the path of the compiled __init__.pyc
+        assert!(json.filename(py).unwrap().contains("__init__.py"));
+        assert!(json.get(py, "dumps").unwrap().is_callable(py));
+        assert!(json.dict(py).get_item(py, "dumps").is_some());
+        assert!(json.get(py, "no_such_attribute").is_err());
+
+        let m = PyModule::new(py, "plugin").unwrap();
+        assert!(m.filename(py).is_err());
+        m.set_filename(py, "/plugins/plugin.py").unwrap();
+        assert_eq!(m.filename(py).unwrap(), "/plugins/plugin.py");
+    }
 }
 
 
@@ -102,6 +102,41 @@ impl PyFloat {
     }
 }
 
+/// Returns true if `ptr` is a Python integer (`int` or `long`).
+#[cfg(feature="python27-sys")]
+#[inline]
+unsafe fn is_integer(ptr: *mut ffi::PyObject) -> bool {
+    ffi::PyLong_Check(ptr) != 0 || ffi::PyInt_Check(ptr) != 0
+}
+
+/// Returns true if `ptr` is a Python integer.
+#[cfg(feature="python3-sys")]
+#[inline]
+unsafe fn is_integer(ptr: *mut ffi::PyObject) -> bool {
+    ffi::PyLong_Check(ptr) != 0
+}
+
+/// Converts `obj` to a Python integer by calling `obj.__index__()`.
+///
+/// Raises `TypeError` if the object can't be interpreted as an integer.
+/// Unlike `int(obj)`, this does not accept floats or strings.
+#[cfg(feature="python3-sys")]
+fn index(py: Python, obj: &PyObject) -> PyResult<PyObject> {
+    unsafe { err::result_from_owned_ptr(py, ffi::PyNumber_Index(obj.as_ptr())) }
+}
+
+/// Converts `obj` to a Python `long` by calling `obj.__index__()`.
+///
+/// `__index__()` may return an `int`, which the `PyLong_As*` functions don't all accept,
+/// so the result is passed through `long()`.
+#[cfg(feature="python27-sys")]
+fn index(py: Python, obj: &PyObject) -> PyResult<PyObject> {
+    unsafe {
+        let num: PyObject = err::result_from_owned_ptr(py, ffi::PyNumber_Index(obj.as_ptr()))?;
+        err::result_from_owned_ptr(py, ffi::PyNumber_Long(num.as_ptr()))
+    }
+}
+
 macro_rules! int_fits_c_long(
     ($rust_type:ty) => (
         #[cfg(feature="python27-sys")]
@@ -129,7 +164,13 @@ macro_rules! int_fits_c_long(
         }
 
         extract!(obj to $rust_type; py => {
-            let val = unsafe { ffi::PyLong_AsLong(obj.as_ptr()) };
+            let val = unsafe {
+                if is_integer(obj.as_ptr()) {
+                    ffi::PyLong_AsLong(obj.as_ptr())
+                } else {
+                    ffi::PyLong_AsLong(index(py, obj)?.as_ptr())
+                }
+            };
             if val == -1 && PyErr::occurred(py) {
                 return Err(PyErr::fetch(py));
             }
@@ -197,7 +238,12 @@ macro_rules! int_convert_u64_or_i64 (
             #[cfg(feature="python3-sys")]
             fn to_py_object(&self, py: Python) -> PyLong {
                 unsafe {
-                    err::cast_from_owned_ptr_or_panic(py, $pylong_from_ll_or_ull(*self))
+                    // PyLong_FromLong hits the interpreter's small int cache
+                    let ptr = match cast::<$rust_type, c_long>(*self) {
+                        Some(v) => ffi::PyLong_FromLong(v),
+                        None => $pylong_from_ll_or_ull(*self)
+                    };
+                    err::cast_from_owned_ptr_or_panic(py, ptr)
                 }
             }
         }
@@ -216,7 +262,7 @@ macro_rules! int_convert_u64_or_i64 (
                             None => Err(overflow_error(py))
                         }
                     } else {
-                        let num = err::result_from_owned_ptr(py, ffi::PyNumber_Long(ptr))?;
+                        let num = index(py, obj)?;
                         err_if_invalid_value(py, !0, $pylong_as_ull_or_ull(num.as_ptr()))
                     }
                 }
@@ -229,7 +275,7 @@ macro_rules! int_convert_u64_or_i64 (
                     if ffi::PyLong_Check(ptr) != 0 {
                         err_if_invalid_value(py, !0, $pylong_as_ull_or_ull(ptr))
                     } else {
-                        let num = err::result_from_owned_ptr(py, ffi::PyNumber_Long(ptr))?;
+                        let num = index(py, obj)?;
                         err_if_invalid_value(py, !0, $pylong_as_ull_or_ull(num.as_ptr()))
                     }
                 }
@@ -276,6 +322,9 @@ impl ToPyObject for f64 {
     }
 }
 
+// Extracts a float the way Python's `float()` does (via `PyFloat_AsDouble`):
+// `int` and objects implementing `__float__` are accepted, and special values
+// such as `NaN` and infinities are preserved. Other objects raise `TypeError`.
 extract!(obj to f64; py => {
     let v = unsafe { ffi::PyFloat_AsDouble(obj.as_ptr()) };
     if v == -1.0 && PyErr::occurred(py) {
@@ -306,6 +355,7 @@ mod test {
     use std;
     use python::{Python, PythonObject};
     use conversion::ToPyObject;
+    use objects::exc;
 
     macro_rules! num_to_py_object_and_back (
         ($func_name:ident, $t1:ty, $t2:ty) => (
@@ -332,12 +382,56 @@ mod test {
     num_to_py_object_and_back!(to_from_u64, u64, u64);
     num_to_py_object_and_back!(to_from_isize, isize, isize);
     num_to_py_object_and_back!(to_from_usize, usize, usize);
-    num_to_py_object_and_back!(float_to_i32, f64, i32);
-    num_to_py_object_and_back!(float_to_u32, f64, u32);
-    num_to_py_object_and_back!(float_to_i64, f64, i64);
-    num_to_py_object_and_back!(float_to_u64, f64, u64);
     num_to_py_object_and_back!(int_to_float, i32, f64);
 
+    macro_rules! float_to_int_is_type_error (
+        ($func_name:ident, $t:ty) => (
+            #[test]
+            fn $func_name() {
+                let gil = Python::acquire_gil();
+                let py = gil.python();
+                let obj = 123f64.to_py_object(py).into_object();
+                let err = obj.extract::<$t>(py).unwrap_err();
+                assert!(err.matches(py, py.get_type::<exc::TypeError>()));
+            }
+        )
+    );
+
+    float_to_int_is_type_error!(float_to_i32, i32);
+    float_to_int_is_type_error!(float_to_u32, u32);
+    float_to_int_is_type_error!(float_to_i64, i64);
+    float_to_int_is_type_error!(float_to_u64, u64);
+
+    #[test]
+    fn test_overflow_vs_type_error() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let big = py.eval("2**70", None, None).unwrap();
+        assert!(big.extract::<i64>(py).unwrap_err().matches(py, py.get_type::<exc::OverflowError>()));
+        assert!(big.extract::<u64>(py).unwrap_err().matches(py, py.get_type::<exc::OverflowError>()));
+        assert!(big.extract::<i32>(py).unwrap_err().matches(py, py.get_type::<exc::OverflowError>()));
+        let negative = (-1i32).to_py_object(py).into_object();
+        assert!(negative.extract::<u64>(py).unwrap_err().matches(py, py.get_type::<exc::OverflowError>()));
+        assert!(negative.extract::<u8>(py).unwrap_err().matches(py, py.get_type::<exc::OverflowError>()));
+        let s = py.eval("'123'", None, None).unwrap();
+        assert!(s.extract::<i64>(py).unwrap_err().matches(py, py.get_type::<exc::TypeError>()));
+        assert!(s.extract::<u64>(py).unwrap_err().matches(py, py.get_type::<exc::TypeError>()));
+    }
+
+    #[test]
+    fn test_extract_via_index() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        // Integer-like objects such as numpy integer scalars implement `__index__`.
+        let d = ::objects::PyDict::new(py);
+        py.run("class Index(object):\n    def __index__(self): return 42\nobj = Index()", None, Some(&d)).unwrap();
+        let obj = d.get_item(py, "obj").unwrap();
+        assert_eq!(obj.extract::<i32>(py).unwrap(), 42);
+        assert_eq!(obj.extract::<i64>(py).unwrap(), 42);
+        assert_eq!(obj.extract::<u64>(py).unwrap(), 42);
+        assert_eq!(obj.extract::<u8>(py).unwrap(), 42);
+    }
+
     #[test]
     fn test_u32_max() {
         let gil = Python::acquire_gil();
@@ -381,4 +475,49 @@ mod test {
         assert_eq!(v, obj.extract::<u64>(py).unwrap());
         assert!(obj.extract::<i64>(py).is_err());
     }
+
+    #[test]
+    fn test_small_ints_use_cache() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let cached = |obj: ::objects::PyObject| {
+            let d = ::objects::PyDict::new(py);
+            d.set_item(py, "obj", obj).unwrap();
+            py.eval("obj is int(str(obj))", None, Some(&d)).unwrap().extract::<bool>(py).unwrap()
+        };
+        assert!(cached(5i64.to_py_object(py).into_object()));
+        assert!(cached((-5i64).to_py_object(py).into_object()));
+        assert!(cached(256u64.to_py_object(py).into_object()));
+        assert!(cached(0usize.to_py_object(py).into_object()));
+        assert!(cached(100isize.to_py_object(py).into_object()));
+
+        // values outside the cache and negative values still convert correctly
+        let values: Vec<i64> = vec![-6, -1, 257, 1 << 40, std::i64::MIN];
+        for &v in &values {
+            assert_eq!(v, v.to_py_object(py).into_object().extract::<i64>(py).unwrap());
+        }
+        let list: Vec<usize> = (0..1000).collect();
+        let obj = list.to_py_object(py).into_object();
+        assert_eq!(list, obj.extract::<Vec<usize>>(py).unwrap());
+    }
+
+    #[test]
+    fn test_float_special_values() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let nan = std::f64::NAN.to_py_object(py).into_object();
+        assert!(nan.extract::<f64>(py).unwrap().is_nan());
+        assert!(nan.extract::<f32>(py).unwrap().is_nan());
+        for &v in &[std::f64::INFINITY, std::f64::NEG_INFINITY, -0.0, std::f64::MAX, std::f64::MIN_POSITIVE] {
+            let r = v.to_py_object(py).into_object().extract::<f64>(py).unwrap();
+            assert_eq!(v.to_bits(), r.to_bits());
+        }
+        assert!(py.eval("float('inf')", None, None).unwrap().extract::<f64>(py).unwrap() == std::f64::INFINITY);
+
+        assert_eq!(py.eval("2**53", None, None).unwrap().extract::<f64>(py).unwrap(), 9007199254740992.0);
+        let s = py.eval("'1.5'", None, None).unwrap();
+        assert!(s.extract::<f64>(py).unwrap_err().matches(py, py.get_type::<exc::TypeError>()));
+        let none = py.None();
+        assert!(none.extract::<f64>(py).unwrap_err().matches(py, py.get_type::<exc::TypeError>()));
+    }
 }
@@ -20,7 +20,7 @@ use std::{mem, ptr};
 use ffi;
 use python::{Python, PythonObject, PythonObjectWithCheckedDowncast, PythonObjectWithTypeObject, PythonObjectDowncastError};
 use objects::PyType;
-use err::PyResult;
+use err::{PyErr, PyResult};
 
 /// Represents a reference to a Python object.
 ///
@@ -134,6 +134,8 @@ impl PyObject {
         }
     }
 
+    /// Creates a PyObject instance for the given FFI pointer.
+    /// Calls Py_INCREF() on the ptr.
     /// Returns None for null pointers; undefined behavior if the pointer is invalid.
     #[inline]
     pub unsafe fn from_borrowed_ptr_opt(py: Python, ptr: *mut ffi::PyObject) -> Option<PyObject> {
@@ -176,6 +178,14 @@ impl PyObject {
         mem::transmute(ptr)
     }
 
+    /// Returns whether this object is the Python `None` singleton.
+    ///
+    /// This is equivalent to the Python expression: `self is None`.
+    #[inline]
+    pub fn is_none(&self, _py: Python) -> bool {
+        self.as_ptr() == unsafe { ffi::Py_None() }
+    }
+
     /// Gets the reference count of this Python object.
     #[inline]
     pub fn get_refcnt(&self, _py: Python) -> usize {
@@ -189,6 +199,18 @@ impl PyObject {
         }
     }
 
+    /// Return true if `self` is an instance of `ty`, or of a subclass of `ty`.
+    ///
+    /// `ty` may be a type object or a tuple of type objects.
+    /// This is equivalent to the Python expression: `isinstance(self, ty)`.
+    #[inline]
+    pub fn is_instance_of<T>(&self, py: Python, ty: &T) -> PyResult<bool>
+        where T: PythonObject
+    {
+        let r = unsafe { ffi::PyObject_IsInstance(self.as_ptr(), ty.as_object().as_ptr()) };
+        if r < 0 { Err(PyErr::fetch(py)) } else { Ok(r != 0) }
+    }
+
     /// Casts the PyObject to a concrete Python object type.
     /// Causes undefined behavior if the object is not of the expected type.
     /// This is a wrapper function around `PythonObject::unchecked_downcast_from()`.
@@ -237,6 +259,16 @@ impl PyObject {
     {
         ::conversion::FromPyObject::extract(py, self)
     }
+
+    /// Borrows the contents of a `bytes` object without copying.
+    ///
+    /// The slice is valid for as long as the `PyObject` is, because `bytes` objects are immutable.
+    /// Fails with `TypeError` for any other type, including `bytearray`,
+    /// whose buffer can be resized (see `PyByteArray::as_slice()`).
+    /// Unlike `&[u8]` parameters of `py_fn!`, this never copies the data.
+    pub fn extract_bytes_ref<'a>(&'a self, py: Python) -> PyResult<&'a [u8]> {
+        Ok(self.cast_as::<::objects::PyBytes>(py)?.data(py))
+    }
 }
 
 /// PyObject implements the `==` operator using reference equality:
@@ -260,3 +292,79 @@ fn test_sizeof() {
     assert_eq!(mem::size_of::<PyType>(), mem::size_of::<*mut ffi::PyTypeObject>());
 }
 
+
+#[test]
+fn test_cast_as() {
+    use objects::{PyDict, PyList, PySequence};
+    use python::PyClone;
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let obj = py.eval("[1, 2, 3]", None, None).unwrap();
+
+    assert_eq!(obj.cast_as::<PyList>(py).unwrap().len(py), 3);
+    assert!(obj.cast_as::<PySequence>(py).is_ok());
+    assert!(obj.cast_as::<PyDict>(py).is_err());
+    let err: ::err::PyErr = obj.cast_as::<PyDict>(py).unwrap_err().into();
+    assert!(err.matches(py, py.get_type::<::objects::exc::TypeError>()));
+
+    let list = obj.clone_ref(py).cast_into::<PyList>(py).unwrap();
+    assert!(list.as_object() == &obj);
+    assert!(obj.cast_into::<PyDict>(py).is_err());
+}
+
+#[test]
+fn test_get_type() {
+    use objects::{PyDict, PyList};
+    use objects::exc;
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let obj = py.eval("[1, 2, 3]", None, None).unwrap();
+
+    let ty = obj.get_type(py);
+    assert!(ty == py.get_type::<PyList>());
+    assert!(ty != py.get_type::<PyDict>());
+    assert_eq!(ty.name(py), "list");
+    let refcnt = ty.as_object().get_refcnt(py);
+    let ty2 = obj.get_type(py);
+    assert_eq!(ty2.as_object().get_refcnt(py), refcnt + 1);
+
+    let mut err = py.eval("int('x')", None, None).unwrap_err();
+    let value = err.instance(py);
+    assert!(value.get_type(py) == py.get_type::<exc::ValueError>());
+}
+
+#[test]
+fn test_wrap_ffi_pointers() {
+    use std::ffi::CString;
+    use objects::PyDict;
+    use python::PyClone;
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let d = PyDict::new(py);
+    let value = py.eval("object()", None, None).unwrap();
+    d.set_item(py, "key", &value).unwrap();
+    let refcnt = value.get_refcnt(py);
+
+    // PyDict_GetItemString returns a borrowed pointer, or NULL without setting an exception
+    let get = |key: &str| unsafe {
+        let key = CString::new(key).unwrap();
+        PyObject::from_borrowed_ptr_opt(py, ffi::PyDict_GetItemString(d.as_object().as_ptr(), key.as_ptr()))
+    };
+    let borrowed = get("key").unwrap();
+    assert!(borrowed == value);
+    assert_eq!(value.get_refcnt(py), refcnt + 1);
+    drop(borrowed);
+    assert_eq!(value.get_refcnt(py), refcnt);
+    assert!(get("missing").is_none());
+    assert!(unsafe { ffi::PyErr_Occurred() }.is_null());
+
+    let clone = value.clone_ref(py);
+    assert!(clone == value);
+    assert_eq!(value.get_refcnt(py), refcnt + 1);
+    drop(clone);
+
+    // PyObject_Repr returns a new reference
+    let repr = unsafe { PyObject::from_owned_ptr_opt(py, ffi::PyObject_Repr(value.as_ptr())) }.unwrap();
+    assert_eq!(repr.get_refcnt(py), 1);
+    assert!(unsafe { PyObject::from_owned_ptr_opt(py, ptr::null_mut()) }.is_none());
+}
@@ -20,7 +20,7 @@ use std::mem;
 use ffi;
 use python::{Python, PythonObject, ToPythonPointer, PyClone, PyDrop};
 use conversion::{FromPyObject, ToPyObject};
-use objects::{PyObject, PyList, PyTuple, PyIterator};
+use objects::{PyObject, PyList, PyTuple, PyIterator, exc};
 use ffi::Py_ssize_t;
 use err;
 use err::{PyErr, PyResult, result_from_owned_ptr, result_cast_from_owned_ptr};
@@ -247,6 +247,32 @@ impl <'source, T> FromPyObject<'source> for Vec<T>
     }
 }
 
+/// Converts a Python-style slice bound (negative values count from the end)
+/// for the concrete `PyList_GetSlice()`-style functions, which only clamp to `0..len`.
+pub fn slice_index(index: isize, len: usize) -> Py_ssize_t {
+    if index < 0 {
+        index.saturating_add(len as isize) as Py_ssize_t
+    } else {
+        index as Py_ssize_t
+    }
+}
+
+/// Converts a Python-style item index (negative values count from the end)
+/// to an index into a sequence of length `len`.
+/// Returns `None` if the index is out of range.
+pub fn item_index(index: isize, len: usize) -> Option<usize> {
+    let index = if index < 0 {
+        index.checked_add(len as isize)?
+    } else {
+        index
+    };
+    if index >= 0 && (index as usize) < len {
+        Some(index as usize)
+    } else {
+        None
+    }
+}
+
 fn extract_sequence<T>(py: Python, obj: &PyObject) -> PyResult<Vec<T>>
     where for<'a> T: FromPyObject<'a>
 {
@@ -260,12 +286,123 @@ fn extract_sequence<T>(py: Python, obj: &PyObject) -> PyResult<Vec<T>>
     Ok(v)
 }
 
+/// Checks that `obj` is a sequence with exactly `len` elements.
+fn array_sequence<'a>(py: Python, obj: &'a PyObject, len: usize) -> PyResult<&'a PySequence> {
+    let seq = obj.cast_as::<PySequence>(py)?;
+    let actual = seq.len(py)?;
+    if actual != len as isize {
+        return Err(PyErr::new::<exc::ValueError, _>(py,
+            format!("expected a sequence of length {}, got length {}", len, actual)));
+    }
+    Ok(seq)
+}
+
+/// Extracts the element at `index`; on failure, the error message names the index.
+fn array_element<T>(py: Python, seq: &PySequence, index: usize) -> PyResult<T>
+    where for<'a> T: FromPyObject<'a>
+{
+    use objectprotocol::ObjectProtocol;
+
+    let item = seq.get_item(py, index as isize)?;
+    let result = match T::extract(py, &item) {
+        Ok(value) => Ok(value),
+        Err(mut e) => {
+            let msg = format!("element {}: {}", index, e.instance(py).str(py)?.to_string_lossy(py));
+            Err(PyErr::new_lazy_init(e.get_type(py), Some(msg.to_py_object(py).into_object())))
+        }
+    };
+    item.release_ref(py);
+    result
+}
+
+macro_rules! array_extract_impl {
+    ($len:expr; $($index:expr)*) => {
+        /// Extracts a sequence with exactly as many elements as the array into an array.
+        ///
+        /// Fails with `ValueError` if the sequence has a different length.
+        /// If an element cannot be extracted, the error message names its index.
+        impl <'source, T> FromPyObject<'source> for [T; $len]
+            where for<'a> T: FromPyObject<'a>
+        {
+            #[allow(unused_variables)]
+            fn extract(py: Python, obj: &'source PyObject) -> PyResult<Self> {
+                let seq = array_sequence(py, obj, $len)?;
+                Ok([$( array_element(py, seq, $index)? ),*])
+            }
+        }
+    }
+}
+
+// Implemented for the same array lengths as the standard library's trait impls.
+array_extract_impl!(0;);
+array_extract_impl!(1; 0);
+array_extract_impl!(2; 0 1);
+array_extract_impl!(3; 0 1 2);
+array_extract_impl!(4; 0 1 2 3);
+array_extract_impl!(5; 0 1 2 3 4);
+array_extract_impl!(6; 0 1 2 3 4 5);
+array_extract_impl!(7; 0 1 2 3 4 5 6);
+array_extract_impl!(8; 0 1 2 3 4 5 6 7);
+array_extract_impl!(9; 0 1 2 3 4 5 6 7 8);
+array_extract_impl!(10; 0 1 2 3 4 5 6 7 8 9);
+array_extract_impl!(11; 0 1 2 3 4 5 6 7 8 9 10);
+array_extract_impl!(12; 0 1 2 3 4 5 6 7 8 9 10 11);
+array_extract_impl!(13; 0 1 2 3 4 5 6 7 8 9 10 11 12);
+array_extract_impl!(14; 0 1 2 3 4 5 6 7 8 9 10 11 12 13);
+array_extract_impl!(15; 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14);
+array_extract_impl!(16; 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15);
+array_extract_impl!(17; 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16);
+array_extract_impl!(18; 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17);
+array_extract_impl!(19; 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18);
+array_extract_impl!(20; 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19);
+array_extract_impl!(21; 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20);
+array_extract_impl!(22; 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21);
+array_extract_impl!(23; 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22);
+array_extract_impl!(24; 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23);
+array_extract_impl!(25; 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24);
+array_extract_impl!(26; 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25);
+array_extract_impl!(27; 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26);
+array_extract_impl!(28; 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26 27);
+array_extract_impl!(29; 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26
+    27 28);
+array_extract_impl!(30; 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26
+    27 28 29);
+array_extract_impl!(31; 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26
+    27 28 29 30);
+array_extract_impl!(32; 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26
+    27 28 29 30 31);
+
 #[cfg(test)]
 mod test {
     use std;
     use python::{Python, PythonObject};
     use conversion::ToPyObject;
     use objects::{PySequence, PyList, PyTuple, PyIterator};
+    use objectprotocol::ObjectProtocol;
+
+    #[test]
+    fn test_extract_array() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let list = vec![1.0f64, 2.5, -3.0].to_py_object(py).into_object();
+        let p: [f64; 3] = list.extract(py).unwrap();
+        assert_eq!(p, [1.0, 2.5, -3.0]);
+        let t = (1i32, 2i32).to_py_object(py).into_object();
+        assert_eq!(t.extract::<[i32; 2]>(py).unwrap(), [1, 2]);
+        assert_eq!(PyTuple::empty(py).into_object().extract::<[i32; 0]>(py).unwrap(), []);
+
+        let short = vec![1.0f64, 2.0].to_py_object(py).into_object();
+        let err = short.extract::<[f64; 3]>(py).unwrap_err();
+        assert!(err.matches(py, py.get_type::<::objects::exc::ValueError>()));
+
+        let bad = py.eval("[1.0, 'x', 3.0]", None, None).unwrap();
+        let mut err = bad.extract::<[f64; 3]>(py).unwrap_err();
+        assert!(err.matches(py, py.get_type::<::objects::exc::TypeError>()));
+        let msg = err.instance(py).str(py).unwrap().to_string_lossy(py).into_owned();
+        assert!(msg.starts_with("element 1: "), "{}", msg);
+
+        assert!(42i32.to_py_object(py).into_object().extract::<[i32; 1]>(py).is_err());
+    }
 
     #[test]
     fn test_numbers_are_not_sequences() {
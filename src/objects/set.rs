@@ -19,7 +19,7 @@
 use ffi;
 use python::{Python, PythonObject};
 use conversion::ToPyObject;
-use objects::PyObject;
+use objects::{PyObject, PyIterator};
 use err::{self, PyResult, PyErr};
 use std::{mem, collections, hash, cmp, ptr};
 
@@ -107,6 +107,70 @@ impl PySet {
             Some(obj) => Ok(obj)
         }
     }
+
+    /// Returns an iterator over the values in the set.
+    /// This is equivalent to the Python expression `iter(self)`.
+    pub fn iter<'p>(&self, py: Python<'p>) -> PyResult<PyIterator<'p>> {
+        let obj = unsafe {
+            err::result_from_owned_ptr(py, ffi::PyObject_GetIter(self.0.as_ptr()))
+        }?;
+        Ok(PyIterator::from_object(py, obj)?)
+    }
+}
+
+/// Represents a Python `frozenset`.
+pub struct PyFrozenSet(PyObject);
+
+pyobject_newtype!(PyFrozenSet, PyFrozenSet_Check, PyFrozenSet_Type);
+
+impl PyFrozenSet {
+    /// Creates a new frozenset from any iterable
+    ///
+    /// Corresponds to `frozenset(iterable)` in Python.
+    pub fn new<I>(py: Python, iterable: I) -> PyResult<PyFrozenSet> where I: ToPyObject {
+        iterable.with_borrowed_ptr(py, |iterable| unsafe {
+            err::result_cast_from_owned_ptr(py, ffi::PyFrozenSet_New(iterable))
+        })
+    }
+
+    /// Creates an empty frozenset
+    ///
+    /// Corresponds to `frozenset()` in Python
+    #[inline]
+    pub fn empty(py: Python) -> PyResult<PyFrozenSet> {
+        unsafe {
+            err::result_cast_from_owned_ptr(py,
+                ffi::PyFrozenSet_New(ptr::null_mut()))
+        }
+    }
+
+    /// Return the number of items in the frozenset
+    /// This is equivalent to Python `len(self)`
+    #[inline]
+    pub fn len(&self, _py: Python) -> usize {
+        unsafe { ffi::PySet_Size(self.0.as_ptr()) as usize }
+    }
+
+    /// Determine if the frozenset contains the specified value.
+    /// This is equivalent to the Python expression `value in self`.
+    pub fn contains<V>(&self, py: Python, value: V) -> PyResult<bool> where V: ToPyObject {
+        value.with_borrowed_ptr(py, |key| unsafe {
+            match ffi::PySet_Contains(self.0.as_ptr(), key) {
+                1 => Ok(true),
+                0 => Ok(false),
+                _ => Err(PyErr::fetch(py))
+            }
+        })
+    }
+
+    /// Returns an iterator over the values in the set.
+    /// This is equivalent to the Python expression `iter(self)`.
+    pub fn iter<'p>(&self, py: Python<'p>) -> PyResult<PyIterator<'p>> {
+        let obj = unsafe {
+            err::result_from_owned_ptr(py, ffi::PyObject_GetIter(self.0.as_ptr()))
+        }?;
+        Ok(PyIterator::from_object(py, obj)?)
+    }
 }
 
 impl <V, H> ToPyObject for collections::HashSet<V, H>
@@ -143,7 +207,7 @@ impl <V> ToPyObject for collections::BTreeSet<V>
 mod test {
     use python::{Python, PythonObject};
     use conversion::ToPyObject;
-    use objects::PySet;
+    use objects::{PySet, PyFrozenSet};
     use std::collections::{HashSet, BTreeSet};
 
     #[test]
@@ -266,4 +330,34 @@ mod test {
         // original object not updated
         assert!(!v.contains(&31));
     }
+
+    #[test]
+    fn test_set_iter() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let mut v = HashSet::new();
+        v.insert(1i32);
+        v.insert(2i32);
+        let set = v.to_py_object(py);
+        let mut items: Vec<i32> = set.iter(py).unwrap()
+            .map(|item| item.unwrap().extract(py).unwrap()).collect();
+        items.sort();
+        assert_eq!(vec![1, 2], items);
+    }
+
+    #[test]
+    fn test_frozenset() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let frozen = PyFrozenSet::new(py, vec![1i32, 2, 2, 3]).unwrap();
+        assert_eq!(3, frozen.len(py));
+        assert!(frozen.contains(py, 2i32).unwrap());
+        assert!(!frozen.contains(py, 4i32).unwrap());
+        let mut items: Vec<i32> = frozen.iter(py).unwrap()
+            .map(|item| item.unwrap().extract(py).unwrap()).collect();
+        items.sort();
+        assert_eq!(vec![1, 2, 3], items);
+        assert_eq!(0, PyFrozenSet::empty(py).unwrap().len(py));
+        assert!(frozen.into_object().cast_into::<PySet>(py).is_err());
+    }
 }
@@ -0,0 +1,97 @@
+// Copyright (c) 2015 Daniel Grunwald
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this
+// software and associated documentation files (the "Software"), to deal in the Software
+// without restriction, including without limitation the rights to use, copy, modify, merge,
+// publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons
+// to whom the Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED,
+// INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR
+// PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE
+// FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR
+// OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+use ffi::{self, Py_ssize_t};
+use python::{Python, PythonObject};
+use err::{self, PyResult, PyErr};
+use conversion::ToPyObject;
+use objects::PyObject;
+
+/// Represents a Python `slice` object.
+///
+/// Implementations of `__getitem__` can use `key.cast_as::<PySlice>(py)`
+/// to detect slice arguments like `obj[1:10:2]`.
+pub struct PySlice(PyObject);
+
+pyobject_newtype!(PySlice, PySlice_Check, PySlice_Type);
+
+impl PySlice {
+    /// Creates a new slice object, equivalent to `slice(start, stop, step)`.
+    pub fn new(py: Python, start: isize, stop: isize, step: isize) -> PySlice {
+        let start = start.to_py_object(py);
+        let stop = stop.to_py_object(py);
+        let step = step.to_py_object(py);
+        unsafe {
+            err::cast_from_owned_ptr_or_panic(py,
+                ffi::PySlice_New(start.as_object().as_ptr(),
+                                 stop.as_object().as_ptr(),
+                                 step.as_object().as_ptr()))
+        }
+    }
+
+    /// Retrieves the `(start, stop, step)` indices of this slice,
+    /// normalized for a sequence of the given length.
+    ///
+    /// The result follows the same rules as Python's `slice.indices(length)`:
+    /// missing and negative values are resolved relative to `length`,
+    /// and out-of-range values are clipped.
+    /// Fails with `ValueError` if the step is zero.
+    pub fn indices(&self, py: Python, length: usize) -> PyResult<(isize, isize, isize)> {
+        let mut start: Py_ssize_t = 0;
+        let mut stop: Py_ssize_t = 0;
+        let mut step: Py_ssize_t = 0;
+        let mut slicelength: Py_ssize_t = 0;
+        let r = unsafe {
+            ffi::PySlice_GetIndicesEx(self.0.as_ptr(), length as Py_ssize_t,
+                &mut start, &mut stop, &mut step, &mut slicelength)
+        };
+        if r == 0 {
+            Ok((start as isize, stop as isize, step as isize))
+        } else {
+            Err(PyErr::fetch(py))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use python::Python;
+    use objects::{PySlice, PyDict};
+
+    #[test]
+    fn test_indices() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        assert_eq!(PySlice::new(py, 1, 10, 2).indices(py, 5).unwrap(), (1, 5, 2));
+        assert_eq!(PySlice::new(py, -3, -1, 1).indices(py, 10).unwrap(), (7, 9, 1));
+        assert!(PySlice::new(py, 0, 1, 0).indices(py, 10).is_err());
+
+        let slice = py.eval("slice(None, None, -1)", None, None).unwrap().cast_into::<PySlice>(py).unwrap();
+        assert_eq!(slice.indices(py, 4).unwrap(), (3, -1, -1));
+    }
+
+    #[test]
+    fn test_new() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let d = PyDict::new(py);
+        d.set_item(py, "s", PySlice::new(py, 1, 10, 2)).unwrap();
+        assert!(py.eval("s == slice(1, 10, 2)", None, Some(&d)).unwrap().extract::<bool>(py).unwrap());
+        assert!(py.eval("(1, 2)", None, None).unwrap().cast_as::<PySlice>(py).is_err());
+    }
+}
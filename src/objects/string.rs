@@ -19,6 +19,7 @@
 use std;
 use std::{mem, str, char};
 use std::borrow::Cow;
+use std::ffi::CString;
 use libc::c_char;
 use ffi;
 use python::{Python, PythonObject, PyClone, ToPythonPointer, PythonObjectDowncastError};
@@ -204,6 +205,8 @@ impl <'a> PyStringData<'a> {
 impl PyString {
     /// Creates a new Python string object.
     ///
+    /// On Python 3, the UTF-8 data is decoded into a `str` object
+    /// using `PyUnicode_FromStringAndSize()`.
     /// On Python 2.7, this function will create a byte string if the
     /// input string is ASCII-only; and a unicode string otherwise.
     /// Use `PyUnicode::new()` to always create a unicode string.
@@ -230,6 +233,26 @@ impl PyString {
         new_impl(py, s)
     }
 
+    /// Creates a new Python unicode string by decoding `s` with the codec `encoding`
+    /// (e.g. `"latin-1"` or `"utf-16"`), using strict error handling.
+    ///
+    /// Unlike `PyString::new()`, the input does not need to be UTF-8.
+    /// Fails with `UnicodeDecodeError` if the bytes are not valid in the given encoding,
+    /// and with `LookupError` if the encoding is unknown.
+    pub fn from_bytes_with_encoding(py: Python, s: &[u8], encoding: &str) -> PyResult<PyString> {
+        let encoding = match CString::new(encoding) {
+            Ok(encoding) => encoding,
+            Err(_) => return Err(PyErr::new::<exc::ValueError, _>(py, "encoding must not contain NUL bytes"))
+        };
+        let ptr = s.as_ptr() as *const c_char;
+        let len = s.len() as ffi::Py_ssize_t;
+        unsafe {
+            let obj = err::result_from_owned_ptr(py,
+                ffi::PyUnicode_Decode(ptr, len, encoding.as_ptr(), std::ptr::null()))?;
+            Ok(obj.unchecked_cast_into::<PyString>())
+        }
+    }
+
     /// Gets the python string data in its underlying representation.
     ///
     /// For Python 2 byte strings, this function always returns `PyStringData::Utf8`,
@@ -289,6 +312,70 @@ impl PyString {
     }
 }
 
+/// Builds a Python string incrementally using Rust formatting.
+///
+/// # Example
+/// ```
+/// use std::fmt::Write;
+/// use cpython::{Python, PyStringBuilder};
+/// let gil = Python::acquire_gil();
+/// let py = gil.python();
+/// let mut builder = PyStringBuilder::new();
+/// write!(builder, "{} + {} = {}", 1, 2, 1 + 2).unwrap();
+/// let s = builder.finish(py);
+/// assert_eq!(s.to_string(py).unwrap(), "1 + 2 = 3");
+/// ```
+#[derive(Debug, Default)]
+pub struct PyStringBuilder {
+    buf: String
+}
+
+impl PyStringBuilder {
+    /// Creates a new, empty string builder.
+    pub fn new() -> PyStringBuilder {
+        PyStringBuilder { buf: String::new() }
+    }
+
+    /// Creates a new, empty string builder with space for at least `capacity` bytes of UTF-8.
+    pub fn with_capacity(capacity: usize) -> PyStringBuilder {
+        PyStringBuilder { buf: String::with_capacity(capacity) }
+    }
+
+    /// Appends a string slice.
+    pub fn push_str(&mut self, s: &str) {
+        self.buf.push_str(s)
+    }
+
+    /// Gets the number of bytes of UTF-8 written so far.
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Returns true if nothing was written yet.
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// Creates the Python string from the accumulated contents.
+    ///
+    /// This creates a string object like `PyString::new()`.
+    pub fn finish(self, py: Python) -> PyString {
+        PyString::new(py, &self.buf)
+    }
+}
+
+impl std::fmt::Write for PyStringBuilder {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.buf.push_str(s);
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> std::fmt::Result {
+        self.buf.push(c);
+        Ok(())
+    }
+}
+
 impl PyBytes {
     /// Creates a new Python byte string object.
     /// The byte string is initialized by copying the data from the `&[u8]`.
@@ -304,6 +391,10 @@ impl PyBytes {
     }
 
     /// Gets the Python string data as byte slice.
+    ///
+    /// This does not copy: the slice points into the `bytes` object and is valid
+    /// for as long as the `PyBytes` is, because `bytes` objects are immutable.
+    /// To borrow from a `&PyObject`, use `PyObject::extract_bytes_ref()`.
     pub fn data(&self, _py: Python) -> &[u8] {
         unsafe {
             let buffer = ffi::PyBytes_AsString(self.as_ptr()) as *const u8;
@@ -417,6 +508,17 @@ impl ToPyObject for String {
     }
 }
 
+/// Converts Rust `char` to a Python string of length 1.
+impl ToPyObject for char {
+    type ObjectType = PyString;
+
+    #[inline]
+    fn to_py_object(&self, py: Python) -> PyString {
+        let mut buf = [0u8; 4];
+        PyString::new(py, self.encode_utf8(&mut buf))
+    }
+}
+
 /// Allows extracting strings from Python objects.
 /// Accepts Python `str` and `unicode` objects.
 /// In Python 2.7, `str` is expected to be UTF-8 encoded.
@@ -435,6 +537,23 @@ impl <'source> FromPyObject<'source> for String {
     }
 }
 
+/// Allows extracting a `char` from a Python string of length 1.
+///
+/// The length is counted in code points, so characters outside the Basic
+/// Multilingual Plane are accepted even on narrow Python 2.7 builds
+/// (where they are stored as a surrogate pair).
+/// Fails with `ValueError` if the string is empty or longer than one character.
+impl <'source> FromPyObject<'source> for char {
+    fn extract(py: Python, obj: &'source PyObject) -> PyResult<Self> {
+        let s = obj.extract::<Cow<str>>(py)?;
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(PyErr::new::<exc::ValueError, _>(py, "expected a string of length 1"))
+        }
+    }
+}
+
 /// Allows extracting byte arrays from Python objects.
 /// For Python `bytes`, returns a reference to the existing immutable string data.
 /// For other types, converts to an owned `Vec<u8>`.
@@ -470,6 +589,42 @@ impl RefFromPyObject for [u8] {
 mod test {
     use python::{Python, PythonObject};
     use conversion::{ToPyObject, RefFromPyObject};
+    use objectprotocol::ObjectProtocol;
+
+    #[test]
+    fn test_from_bytes_with_encoding() {
+        use super::PyString;
+        use objects::exc;
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let s = PyString::from_bytes_with_encoding(py, b"caf\xe9", "latin-1").unwrap();
+        assert_eq!(s.to_string(py).unwrap(), "caf\u{e9}");
+        let s = PyString::from_bytes_with_encoding(py, "caf\u{e9}".as_bytes(), "utf-8").unwrap();
+        assert_eq!(s.to_string(py).unwrap(), "caf\u{e9}");
+
+        let err = PyString::from_bytes_with_encoding(py, b"caf\xe9", "utf-8").unwrap_err();
+        assert!(err.matches(py, py.get_type::<exc::UnicodeDecodeError>()));
+        let err = PyString::from_bytes_with_encoding(py, b"abc", "no-such-codec").unwrap_err();
+        assert!(err.matches(py, py.get_type::<exc::LookupError>()));
+    }
+
+    #[test]
+    fn test_string_builder() {
+        use std::fmt::Write;
+        use objectprotocol::ObjectProtocol;
+        use super::PyStringBuilder;
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let mut builder = PyStringBuilder::new();
+        write!(builder, "abc-{:03}", 7).unwrap();
+        builder.write_char('\u{e9}').unwrap();
+        builder.push_str("!");
+        let s = builder.finish(py).into_object();
+        assert_eq!("abc-007\u{e9}!", s.extract::<String>(py).unwrap());
+        let expected = py.eval("u'abc-007\\xe9!'", None, None).unwrap();
+        assert_eq!(s.compare(py, expected).unwrap(), ::std::cmp::Ordering::Equal);
+    }
 
     #[test]
     fn test_non_bmp() {
@@ -480,6 +635,52 @@ mod test {
         assert_eq!(s, py_string.extract::<String>(py).unwrap());
     }
 
+    #[test]
+    fn test_non_ascii_round_trip() {
+        use std::borrow::Cow;
+        use objects::PyDict;
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let s = "Gr\u{fc}\u{df}e, \u{4e16}\u{754c}";
+        let owned = String::from(s);
+        let objects = [
+            s.to_py_object(py).into_object(),
+            owned.to_py_object(py).into_object(),
+            Cow::Borrowed(s).to_py_object(py).into_object(),
+            Cow::Owned::<str>(owned.clone()).to_py_object(py).into_object(),
+        ];
+        let d = PyDict::new(py);
+        for obj in &objects {
+            assert_eq!(obj.extract::<String>(py).unwrap(), s);
+            d.set_item(py, "s", obj).unwrap();
+            // Python must see the characters, not the UTF-8 bytes
+            assert!(py.eval("s == u'Gr\\xfc\\xdfe, \\u4e16\\u754c' and len(s) == 9", None, Some(&d))
+                .unwrap().extract::<bool>(py).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_char() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        for &c in &['a', 'λ', '\u{1F600}'] {
+            let obj = c.to_py_object(py).into_object();
+            assert_eq!(obj.len(py).unwrap(), 1);
+            assert_eq!(obj.extract::<char>(py).unwrap(), c);
+            assert_eq!(obj.extract::<String>(py).unwrap(), c.to_string());
+        }
+        // the u prefix makes this a unicode literal on Python 2 as well
+        let emoji = py.eval("u'\\U0001F600'", None, None).unwrap();
+        assert_eq!(emoji.extract::<char>(py).unwrap(), '\u{1F600}');
+
+        for s in &["", "ab", "λλ"] {
+            let err = s.to_py_object(py).into_object().extract::<char>(py).unwrap_err();
+            assert!(err.matches(py, py.get_type::<::objects::exc::ValueError>()));
+        }
+        assert!(1i32.to_py_object(py).into_object().extract::<char>(py).is_err());
+    }
+
     #[test]
     fn test_extract_str() {
         let gil = Python::acquire_gil();
@@ -509,6 +710,33 @@ mod test {
         assert!(called);
     }
 
+    #[test]
+    fn test_borrow_bytes() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::Hasher;
+        use objects::{exc, PyBytes};
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let data: Vec<u8> = (0..4 * 1024 * 1024).map(|i| i as u8).collect();
+        let py_bytes = PyBytes::new(py, &data).into_object();
+        let borrowed: &[u8] = py_bytes.extract_bytes_ref(py).unwrap();
+        // the slice points into the bytes object
+        let buffer = unsafe { ::ffi::PyBytes_AsString(py_bytes.as_ptr()) } as *const u8;
+        assert_eq!(borrowed.as_ptr(), buffer);
+        let mut h1 = DefaultHasher::new();
+        h1.write(borrowed);
+        let mut h2 = DefaultHasher::new();
+        h2.write(&data);
+        assert_eq!(h1.finish(), h2.finish());
+
+        let array = py.eval("bytearray(b'abc')", None, None).unwrap();
+        let err = array.extract_bytes_ref(py).unwrap_err();
+        assert!(err.matches(py, py.get_type::<exc::TypeError>()));
+        let text = py.eval("u'abc'", None, None).unwrap();
+        let err = text.extract_bytes_ref(py).unwrap_err();
+        assert!(err.matches(py, py.get_type::<exc::TypeError>()));
+    }
+
     #[test]
     #[cfg(feature="nightly")] // only works with specialization
     fn test_extract_byte_str_to_vec() {
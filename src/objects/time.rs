@@ -0,0 +1,241 @@
+// Copyright (c) 2015 Daniel Grunwald
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this
+// software and associated documentation files (the "Software"), to deal in the Software
+// without restriction, including without limitation the rights to use, copy, modify, merge,
+// publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons
+// to whom the Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED,
+// INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR
+// PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE
+// FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR
+// OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! Conversions between `std::time` types and the Python `datetime` module.
+//!
+//! | Rust                  | Python                                   |
+//! |-----------------------|------------------------------------------|
+//! | `Duration`            | `datetime.timedelta`                     |
+//! | `SystemTime`          | aware `datetime.datetime` (UTC)          |
+//!
+//! Python only supports microsecond precision, so sub-microsecond precision is
+//! truncated when converting to Python (for `SystemTime`, towards the Unix epoch).
+//! Converting a value that is outside the range supported by the `datetime`
+//! module to Python panics.
+//!
+//! `Duration` cannot be negative, so extracting a negative `datetime.timedelta`
+//! raises `ValueError`.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use python::{Python, PythonObject};
+use err::{self, PyResult, PyErr};
+use objectprotocol::ObjectProtocol;
+use objects::{PyObject, exc};
+use conversion::ToPyObject;
+use ffi;
+use ffi::datetime::*;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Ensures `PyDateTimeAPI` is initialized.
+pub fn import_datetime_api(py: Python) -> PyResult<()> {
+    unsafe {
+        if PyDateTimeAPI.is_null() {
+            PyDateTime_IMPORT();
+            if PyDateTimeAPI.is_null() {
+                return Err(PyErr::fetch(py));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Like `import_datetime_api`, but panics on failure.
+pub fn datetime_api(py: Python) {
+    if let Err(e) = import_datetime_api(py) {
+        e.print(py);
+        panic!("failed to import the datetime C API");
+    }
+}
+
+/// Returns `datetime.timezone.utc`.
+/// The datetime C API must already be initialized.
+#[cfg(Py_3_7)]
+pub unsafe fn utc_timezone(py: Python) -> PyObject {
+    PyObject::from_borrowed_ptr(py, (*PyDateTimeAPI).TimeZone_UTC)
+}
+
+/// Returns `datetime.timezone.utc`.
+/// Before Python 3.7, the datetime C API does not provide it, so it is looked up by name.
+#[cfg(not(Py_3_7))]
+pub unsafe fn utc_timezone(py: Python) -> PyObject {
+    let utc = py.import("datetime")
+        .and_then(|m| m.get(py, "timezone"))
+        .and_then(|tz| tz.getattr(py, "utc"));
+    match utc {
+        Ok(utc) => utc,
+        Err(e) => {
+            e.print(py);
+            panic!("failed to look up datetime.timezone.utc");
+        }
+    }
+}
+
+pub fn invalid_value(py: Python, msg: &str) -> PyErr {
+    PyErr::new::<exc::ValueError, _>(py, msg)
+}
+
+unsafe fn new_delta(py: Python, d: &Duration) -> PyObject {
+    let days = d.as_secs() / SECONDS_PER_DAY;
+    // timedelta.max.days == 999999999
+    if days > 999_999_999 {
+        panic!("Duration is out of range for datetime.timedelta");
+    }
+    let seconds = d.as_secs() % SECONDS_PER_DAY;
+    let micros = d.subsec_nanos() / 1000;
+    err::from_owned_ptr_or_panic(py, PyDelta_FromDSU(days as i32, seconds as i32, micros as i32))
+}
+
+/// Returns `(is_negative, magnitude)` of a `datetime.timedelta`.
+unsafe fn delta_fields(ptr: *mut ffi::PyObject) -> (bool, Duration) {
+    // timedelta normalizes seconds and microseconds to be non-negative;
+    // only the days can be negative.
+    let days = PyDateTime_DELTA_GET_DAYS(ptr) as i64;
+    let seconds = PyDateTime_DELTA_GET_SECONDS(ptr) as i64;
+    let micros = PyDateTime_DELTA_GET_MICROSECONDS(ptr) as u32;
+    let total_seconds = days * SECONDS_PER_DAY as i64 + seconds;
+    if total_seconds >= 0 {
+        (false, Duration::new(total_seconds as u64, micros * 1000))
+    } else if micros == 0 {
+        (true, Duration::new((-total_seconds) as u64, 0))
+    } else {
+        (true, Duration::new((-total_seconds - 1) as u64, (1_000_000 - micros) * 1000))
+    }
+}
+
+unsafe fn utc_epoch(py: Python) -> PyObject {
+    err::from_owned_ptr_or_panic(py, ((*PyDateTimeAPI).DateTime_FromDateAndTime)(
+        1970, 1, 1, 0, 0, 0, 0, utc_timezone(py).as_ptr(), (*PyDateTimeAPI).DateTimeType))
+}
+
+/// Converts to `datetime.timedelta`.
+impl ToPyObject for Duration {
+    type ObjectType = PyObject;
+
+    fn to_py_object(&self, py: Python) -> PyObject {
+        datetime_api(py);
+        unsafe { new_delta(py, self) }
+    }
+}
+
+// Accepts non-negative `datetime.timedelta` objects.
+extract!(obj to Duration; py => {
+    import_datetime_api(py)?;
+    unsafe {
+        if PyDelta_Check(obj.as_ptr()) == 0 {
+            return Err(PyErr::new::<exc::TypeError, _>(py, "expected datetime.timedelta"));
+        }
+        match delta_fields(obj.as_ptr()) {
+            (false, d) => Ok(d),
+            (true, _) => Err(invalid_value(py, "negative timedelta cannot be converted to Duration"))
+        }
+    }
+});
+
+/// Converts to an aware `datetime.datetime` with `tzinfo=datetime.timezone.utc`.
+impl ToPyObject for SystemTime {
+    type ObjectType = PyObject;
+
+    fn to_py_object(&self, py: Python) -> PyObject {
+        datetime_api(py);
+        unsafe {
+            let epoch = utc_epoch(py);
+            let result = match self.duration_since(UNIX_EPOCH) {
+                Ok(d) => ffi::PyNumber_Add(epoch.as_ptr(), new_delta(py, &d).as_ptr()),
+                Err(e) => ffi::PyNumber_Subtract(epoch.as_ptr(), new_delta(py, &e.duration()).as_ptr())
+            };
+            err::from_owned_ptr_or_panic(py, result)
+        }
+    }
+}
+
+// Accepts aware `datetime.datetime` objects in any time zone.
+// Naive datetimes raise `ValueError`.
+extract!(obj to SystemTime; py => {
+    import_datetime_api(py)?;
+    unsafe {
+        if PyDateTime_Check(obj.as_ptr()) == 0 {
+            return Err(PyErr::new::<exc::TypeError, _>(py, "expected datetime.datetime"));
+        }
+        if PyDateTime_DATE_GET_TZINFO(obj.as_ptr()) == ffi::Py_None() {
+            return Err(invalid_value(py, "expected an aware datetime.datetime"));
+        }
+        let delta = err::result_from_owned_ptr(py,
+            ffi::PyNumber_Subtract(obj.as_ptr(), utc_epoch(py).as_ptr()))?;
+        Ok(match delta_fields(delta.as_ptr()) {
+            (false, d) => UNIX_EPOCH + d,
+            (true, d) => UNIX_EPOCH - d
+        })
+    }
+});
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, UNIX_EPOCH};
+    use python::Python;
+    use conversion::ToPyObject;
+    use objects::PyDict;
+
+    #[test]
+    fn test_duration_round_trip() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let d = Duration::from_millis(1500);
+        let obj = d.to_py_object(py);
+        assert_eq!(obj.extract::<Duration>(py).unwrap(), d);
+        let dict = PyDict::new(py);
+        dict.set_item(py, "d", obj).unwrap();
+        assert_eq!(py.eval("(d.days, d.seconds, d.microseconds)", None, Some(&dict)).unwrap()
+                       .extract::<(i32, i32, i32)>(py).unwrap(),
+                   (0, 1, 500_000));
+
+        // sub-microsecond precision is truncated
+        let d = Duration::new(3 * 86400 + 5, 123_456_789);
+        assert_eq!(d.to_py_object(py).extract::<Duration>(py).unwrap(), Duration::new(3 * 86400 + 5, 123_456_000));
+
+        let max = py.eval("__import__('datetime').timedelta.max", None, None).unwrap();
+        assert_eq!(max.extract::<Duration>(py).unwrap(), Duration::new(1_000_000_000 * 86400 - 1, 999_999_000));
+
+        let negative = py.eval("__import__('datetime').timedelta(microseconds=-1)", None, None).unwrap();
+        assert!(negative.extract::<Duration>(py).is_err());
+        assert!(py.eval("1.5", None, None).unwrap().extract::<Duration>(py).is_err());
+    }
+
+    #[test]
+    fn test_system_time_round_trip() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        for &t in &[UNIX_EPOCH, UNIX_EPOCH + Duration::new(1_500_000_000, 250_000_000),
+                    UNIX_EPOCH - Duration::new(86400 * 365, 1000)] {
+            assert_eq!(t.to_py_object(py).extract::<::std::time::SystemTime>(py).unwrap(), t);
+        }
+
+        let d = PyDict::new(py);
+        d.set_item(py, "t", (UNIX_EPOCH + Duration::from_secs(86400)).to_py_object(py)).unwrap();
+        assert_eq!(py.eval("repr(t)", None, Some(&d)).unwrap().extract::<String>(py).unwrap(),
+                   "datetime.datetime(1970, 1, 2, 0, 0, tzinfo=datetime.timezone.utc)");
+
+        py.run("import datetime\n\
+                tz = datetime.timezone(datetime.timedelta(hours=2))\n\
+                aware = datetime.datetime(1970, 1, 2, 2, 0, 0, tzinfo=tz)\n\
+                naive = datetime.datetime(1970, 1, 2)", None, Some(&d)).unwrap();
+        assert_eq!(d.get_item(py, "aware").unwrap().extract::<::std::time::SystemTime>(py).unwrap(),
+                   UNIX_EPOCH + Duration::from_secs(86400));
+        assert!(d.get_item(py, "naive").unwrap().extract::<::std::time::SystemTime>(py).is_err());
+    }
+}
@@ -20,6 +20,7 @@ use python::{Python, PythonObject, ToPythonPointer, PyDrop};
 use err::{self, PyErr, PyResult};
 use super::object::PyObject;
 use super::exc;
+use super::sequence::{item_index, slice_index};
 use ffi::{self, Py_ssize_t};
 use conversion::{FromPyObject, ToPyObject};
 use std::slice;
@@ -71,6 +72,31 @@ impl PyTuple {
         }
     }
 
+    /// Gets the item at the specified index.
+    /// This is equivalent to the Python expression 'self[index]'.
+    ///
+    /// Like in Python, negative indices count from the end of the tuple.
+    /// Returns an `IndexError` if the index is out of range.
+    pub fn get_item_at(&self, py: Python, index: isize) -> PyResult<PyObject> {
+        match item_index(index, self.len(py)) {
+            Some(index) => Ok(self.get_item(py, index)),
+            None => Err(PyErr::new::<exc::IndexError, _>(py, "tuple index out of range"))
+        }
+    }
+
+    /// Gets a new tuple with the items from `low` to `high` (exclusive).
+    /// This is equivalent to the Python expression 'self[low:high]'.
+    ///
+    /// Like in Python, negative indices count from the end of the tuple,
+    /// and out-of-range indices are clamped to the valid range.
+    pub fn get_slice(&self, py: Python, low: isize, high: isize) -> PyTuple {
+        let len = self.len(py);
+        unsafe {
+            err::cast_from_owned_ptr_or_panic(py,
+                ffi::PyTuple_GetSlice(self.0.as_ptr(), slice_index(low, len), slice_index(high, len)))
+        }
+    }
+
     #[inline]
     pub fn as_slice<'a>(&'a self, py: Python) -> &'a [PyObject] {
         // This is safe because PyObject has the same memory layout as *mut ffi::PyObject,
@@ -143,6 +169,14 @@ tuple_conversion!(8, (ref0, 0, A), (ref1, 1, B), (ref2, 2, C), (ref3, 3, D),
   (ref4, 4, E), (ref5, 5, F), (ref6, 6, G), (ref7, 7, H));
 tuple_conversion!(9, (ref0, 0, A), (ref1, 1, B), (ref2, 2, C), (ref3, 3, D),
   (ref4, 4, E), (ref5, 5, F), (ref6, 6, G), (ref7, 7, H), (ref8, 8, I));
+tuple_conversion!(10, (ref0, 0, A), (ref1, 1, B), (ref2, 2, C), (ref3, 3, D),
+  (ref4, 4, E), (ref5, 5, F), (ref6, 6, G), (ref7, 7, H), (ref8, 8, I), (ref9, 9, J));
+tuple_conversion!(11, (ref0, 0, A), (ref1, 1, B), (ref2, 2, C), (ref3, 3, D),
+  (ref4, 4, E), (ref5, 5, F), (ref6, 6, G), (ref7, 7, H), (ref8, 8, I), (ref9, 9, J),
+  (ref10, 10, K));
+tuple_conversion!(12, (ref0, 0, A), (ref1, 1, B), (ref2, 2, C), (ref3, 3, D),
+  (ref4, 4, E), (ref5, 5, F), (ref6, 6, G), (ref7, 7, H), (ref8, 8, I), (ref9, 9, J),
+  (ref10, 10, K), (ref11, 11, L));
 
 // Empty tuple:
 
@@ -185,6 +219,7 @@ extract!(obj to NoArgs; py => {
 mod test {
     use python::{Python, PythonObject};
     use conversion::ToPyObject;
+    use objects::{PyTuple, exc};
 
     #[test]
     fn test_len() {
@@ -194,5 +229,62 @@ mod test {
         assert_eq!(3, tuple.len(py));
         assert_eq!((1, 2, 3), tuple.into_object().extract(py).unwrap());
     }
-}
 
+    #[test]
+    fn test_slice() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let tuple = py.eval("tuple(range(10))", None, None).unwrap().cast_into::<PyTuple>(py).unwrap();
+        let slice = tuple.get_slice(py, 2, 5);
+        assert_eq!((2, 3, 4), slice.into_object().extract(py).unwrap());
+        assert_eq!((8, 9), tuple.get_slice(py, -2, 100).into_object().extract(py).unwrap());
+        assert_eq!(0, tuple.get_slice(py, 7, -5).len(py));
+    }
+
+    #[test]
+    fn test_get_item_at() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let tuple = (1i32, 2i32, 3i32).to_py_object(py);
+        assert_eq!(3, tuple.get_item_at(py, -1).unwrap().extract::<i32>(py).unwrap());
+        assert_eq!(1, tuple.get_item_at(py, 0).unwrap().extract::<i32>(py).unwrap());
+        let err = tuple.get_item_at(py, -100).unwrap_err();
+        assert!(err.matches(py, py.get_type::<exc::IndexError>()));
+        assert!(tuple.get_item_at(py, 3).is_err());
+    }
+
+    #[test]
+    fn test_mixed_round_trip() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let tuple = (1i64, "a", 3.0f64, true).to_py_object(py);
+        assert_eq!(4, tuple.len(py));
+        let (a, b, c, d): (i64, String, f64, bool) = tuple.into_object().extract(py).unwrap();
+        assert_eq!((1, "a".to_owned(), 3.0, true), (a, b, c, d));
+    }
+
+    #[test]
+    fn test_arity_12() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let v = (0i32, 1i32, 2i32, 3i32, 4i32, 5i32, 6i32, 7i32, 8i32, 9i32, 10i32, "eleven");
+        let tuple = v.to_py_object(py);
+        assert_eq!(12, tuple.len(py));
+        let back: (i32, i32, i32, i32, i32, i32, i32, i32, i32, i32, i32, String) =
+            tuple.into_object().extract(py).unwrap();
+        assert_eq!(10, back.10);
+        assert_eq!("eleven", back.11);
+    }
+
+    #[test]
+    fn test_extract_errors() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let tuple = PyTuple::new(py, &[1i32.to_py_object(py).into_object(),
+                                       "x".to_py_object(py).into_object()]).into_object();
+        let err = tuple.extract::<(i32, String, i32)>(py).err().unwrap();
+        assert!(err.matches(py, py.get_type::<exc::ValueError>()));
+        let err = tuple.extract::<(i32, i32)>(py).err().unwrap();
+        assert!(err.matches(py, py.get_type::<exc::TypeError>()));
+    }
+}
@@ -19,7 +19,7 @@
 use python::{Python, PythonObject, ToPythonPointer};
 use conversion::ToPyObject;
 use objects::{PyObject, PyTuple, PyDict};
-use err::{PyResult, result_from_owned_ptr};
+use err::{PyErr, PyResult, result_from_owned_ptr};
 use ffi;
 use std::ffi::CStr;
 use std::borrow::Cow;
@@ -63,6 +63,46 @@ impl PyType {
         unsafe { ffi::PyObject_TypeCheck(obj.as_ptr(), self.as_type_ptr()) != 0 }
     }
 
+    /// Return true if `self` is a subclass of `other`.
+    ///
+    /// `other` may be a type object or a tuple of type objects.
+    /// Unlike `is_subtype_of`, this honors `__subclasscheck__`;
+    /// it is equivalent to the Python expression: `issubclass(self, other)`.
+    #[inline]
+    pub fn is_subclass_of<T>(&self, py: Python, other: &T) -> PyResult<bool>
+        where T: PythonObject
+    {
+        let r = unsafe { ffi::PyObject_IsSubclass(self.0.as_ptr(), other.as_object().as_ptr()) };
+        if r < 0 { Err(PyErr::fetch(py)) } else { Ok(r != 0) }
+    }
+
+    /// Gets the method resolution order of the type (`tp_mro`, i.e. `self.__mro__`).
+    ///
+    /// The tuple starts with the type itself and ends with `object`.
+    pub fn mro(&self, py: Python) -> PyTuple {
+        unsafe {
+            let mro = (*self.as_type_ptr()).tp_mro;
+            if mro.is_null() {
+                // the type has not been initialized by PyType_Ready yet
+                PyTuple::empty(py)
+            } else {
+                PyObject::from_borrowed_ptr(py, mro).unchecked_cast_into::<PyTuple>()
+            }
+        }
+    }
+
+    /// Looks up the attribute `name` in the dictionaries of the types in the MRO,
+    /// without consulting instance attributes or invoking descriptors.
+    ///
+    /// This is a wrapper around `_PyType_Lookup`; it returns the raw class attribute
+    /// (e.g. the unbound function or method descriptor) if it is found.
+    /// For repeated lookups, pass a name created by `Python::intern()`.
+    pub fn lookup<N>(&self, py: Python, name: N) -> Option<PyObject> where N: ToPyObject {
+        name.with_borrowed_ptr(py, |name| unsafe {
+            PyObject::from_borrowed_ptr_opt(py, ffi::_PyType_Lookup(self.as_type_ptr(), name))
+        })
+    }
+
     /// Calls the type object, thus creating a new instance.
     /// This is equivalent to the Python expression: `self(*args, **kwargs)`
     #[inline]
@@ -0,0 +1,209 @@
+// Copyright (c) 2016 Daniel Grunwald
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this
+// software and associated documentation files (the "Software"), to deal in the Software
+// without restriction, including without limitation the rights to use, copy, modify, merge,
+// publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons
+// to whom the Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED,
+// INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR
+// PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE
+// FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR
+// OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! Support for exporting the memory of `py_class!` instances via the buffer protocol.
+
+use libc;
+use ffi;
+use std::{mem, ptr};
+use std::ffi::CStr;
+use python::{Python, PythonObject, PyClone, PyDrop, ToPythonPointer};
+use objects::{PyObject, exc};
+use err::{PyErr, PyResult};
+use function;
+use py_class::slots::UnitCallbackConverter;
+
+/// Element types that can be exported through the buffer protocol.
+///
+/// # Safety
+/// `format()` must return the `struct` module format code
+/// that matches the memory layout of `Self`.
+pub unsafe trait BufferFormat: Copy {
+    /// Gets the format string describing the element type.
+    fn format() -> &'static CStr;
+}
+
+macro_rules! impl_buffer_format {
+    ($t:ty, $f:expr) => {
+        unsafe impl BufferFormat for $t {
+            #[inline]
+            fn format() -> &'static CStr {
+                CStr::from_bytes_with_nul($f).unwrap()
+            }
+        }
+    }
+}
+
+impl_buffer_format!(u8, b"B\0");
+impl_buffer_format!(i8, b"b\0");
+impl_buffer_format!(u16, b"H\0");
+impl_buffer_format!(i16, b"h\0");
+impl_buffer_format!(u32, b"I\0");
+impl_buffer_format!(i32, b"i\0");
+impl_buffer_format!(u64, b"Q\0");
+impl_buffer_format!(i64, b"q\0");
+impl_buffer_format!(usize, b"N\0");
+impl_buffer_format!(isize, b"n\0");
+impl_buffer_format!(f32, b"f\0");
+impl_buffer_format!(f64, b"d\0");
+
+/// Describes the one-dimensional, contiguous memory exported by a `__buffer__` method.
+pub struct BufferInfo {
+    buf: *mut libc::c_void,
+    len: usize,
+    itemsize: usize,
+    format: &'static CStr,
+    readonly: bool
+}
+
+impl BufferInfo {
+    /// Describes `len` items of `itemsize` bytes each, starting at `buf`.
+    ///
+    /// # Safety
+    /// The memory must stay valid (and must not be moved) as long as the exporting
+    /// object is alive; a buffer view keeps a reference to the exporting object.
+    /// If `readonly` is false, consumers may write to the memory.
+    pub unsafe fn new(buf: *mut libc::c_void, len: usize, itemsize: usize,
+                      format: &'static CStr, readonly: bool) -> BufferInfo {
+        BufferInfo { buf: buf, len: len, itemsize: itemsize, format: format, readonly: readonly }
+    }
+
+    /// Describes the memory of a slice as a read-only buffer.
+    ///
+    /// # Safety
+    /// The slice must stay valid (and must not be moved or modified) as long as the
+    /// exporting object is alive. This is the case for slices into immutable `data`
+    /// members of the class, e.g. a `Vec<f64>` that is never replaced.
+    pub unsafe fn from_slice<T: BufferFormat>(slice: &[T]) -> BufferInfo {
+        BufferInfo::new(slice.as_ptr() as *mut libc::c_void, slice.len(),
+                        mem::size_of::<T>(), T::format(), true)
+    }
+
+    unsafe fn fill(self, py: Python, exporter: &PyObject, view: *mut ffi::Py_buffer,
+                   flags: libc::c_int) -> PyResult<()> {
+        if self.readonly && (flags & ffi::PyBUF_WRITABLE) == ffi::PyBUF_WRITABLE {
+            return Err(PyErr::new::<exc::BufferError, _>(py, "Object is not writable."));
+        }
+        // shape and strides; freed in bf_releasebuffer
+        let internal = Box::into_raw(Box::new([
+            self.len as ffi::Py_ssize_t,
+            self.itemsize as ffi::Py_ssize_t
+        ]));
+        let view = &mut *view;
+        view.buf = self.buf;
+        view.obj = exporter.clone_ref(py).steal_ptr();
+        view.len = (self.len * self.itemsize) as ffi::Py_ssize_t;
+        view.itemsize = self.itemsize as ffi::Py_ssize_t;
+        view.readonly = self.readonly as libc::c_int;
+        view.ndim = 1;
+        view.format = if (flags & ffi::PyBUF_FORMAT) == ffi::PyBUF_FORMAT {
+            self.format.as_ptr() as *mut libc::c_char
+        } else {
+            ptr::null_mut()
+        };
+        view.shape = if (flags & ffi::PyBUF_ND) == ffi::PyBUF_ND {
+            &mut (*internal)[0]
+        } else {
+            ptr::null_mut()
+        };
+        view.strides = if (flags & ffi::PyBUF_STRIDES) == ffi::PyBUF_STRIDES {
+            &mut (*internal)[1]
+        } else {
+            ptr::null_mut()
+        };
+        view.suboffsets = ptr::null_mut();
+        view.internal = internal as *mut libc::c_void;
+        Ok(())
+    }
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! py_class_buffer_slot {
+    ($class:ident :: $f:ident) => {{
+        extern "C" fn bf_getbuffer(
+            slf: *mut $crate::_detail::ffi::PyObject,
+            view: *mut $crate::_detail::ffi::Py_buffer,
+            flags: $crate::_detail::libc::c_int
+        ) -> $crate::_detail::libc::c_int
+        {
+            unsafe {
+                $crate::py_class::buffer::bf_getbuffer::<$class, _>(
+                    concat!(stringify!($class), ".", stringify!($f), "()"),
+                    slf, view, flags, $class::$f)
+            }
+        }
+        Some(bf_getbuffer)
+    }}
+}
+
+#[doc(hidden)]
+pub unsafe fn bf_getbuffer<C, F>(
+    location: &str,
+    slf: *mut ffi::PyObject,
+    view: *mut ffi::Py_buffer,
+    flags: libc::c_int,
+    callback: F
+) -> libc::c_int
+where C: PythonObject,
+      F: FnOnce(&C, Python) -> PyResult<BufferInfo>,
+      F: ::std::panic::UnwindSafe
+{
+    if view.is_null() {
+        return 0;
+    }
+    (*view).obj = ptr::null_mut();
+    function::handle_callback(location, UnitCallbackConverter, |py| {
+        let slf = PyObject::from_borrowed_ptr(py, slf).unchecked_cast_into::<C>();
+        let ret = match callback(&slf, py) {
+            Ok(info) => info.fill(py, slf.as_object(), view, flags),
+            Err(e) => Err(e)
+        };
+        slf.release_ref(py);
+        ret
+    })
+}
+
+#[doc(hidden)]
+// releasebufferproc is a safe fn pointer type; the pointer comes from the interpreter.
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn bf_releasebuffer(_slf: *mut ffi::PyObject, view: *mut ffi::Py_buffer) {
+    unsafe {
+        let internal = (*view).internal as *mut [ffi::Py_ssize_t; 2];
+        if !internal.is_null() {
+            drop(Box::from_raw(internal));
+            (*view).internal = ptr::null_mut();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::ffi::CStr;
+    use super::{BufferFormat, BufferInfo};
+
+    #[test]
+    fn test_buffer_format() {
+        assert_eq!(CStr::from_bytes_with_nul(b"d\0").unwrap(), f64::format());
+        let data = [1u16, 2, 3];
+        let info = unsafe { BufferInfo::from_slice(&data) };
+        assert_eq!(3, info.len);
+        assert_eq!(2, info.itemsize);
+        assert!(info.readonly);
+    }
+}
@@ -0,0 +1,143 @@
+// Copyright (c) 2016 Daniel Grunwald
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this
+// software and associated documentation files (the "Software"), to deal in the Software
+// without restriction, including without limitation the rights to use, copy, modify, merge,
+// publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons
+// to whom the Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED,
+// INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR
+// PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE
+// FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR
+// OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+use python::Python;
+use objects::PyObject;
+use err::PyResult;
+
+#[allow(unknown_lints, bare_trait_objects)] // `dyn` requires Rust 1.27
+type NextFn = Box<FnMut(Python) -> Option<PyResult<PyObject>> + Send>;
+
+// The code generated by py_class! triggers these lints; in user crates they are
+// reported at the macro call site, here they would be reported for the crate itself.
+#[allow(unknown_lints, static_mut_refs, non_local_definitions)]
+mod rust_iterator {
+    use std::cell::RefCell;
+    use py_class;
+    use py_class_impl;
+    use python::{Python, PythonObject, PyClone};
+    use objects::{PyObject, exc};
+    use err::{PyErr, PyResult};
+    use super::NextFn;
+
+    py_class!(pub class RustIterator |py| {
+        data next: RefCell<NextFn>;
+
+        def __iter__(&self) -> PyResult<RustIterator> {
+            Ok(self.clone_ref(py))
+        }
+
+        def __next__(&self) -> PyResult<Option<PyObject>> {
+            let mut next = match self.next(py).try_borrow_mut() {
+                Ok(next) => next,
+                Err(_) => return Err(PyErr::new::<exc::ValueError, _>(py, "iterator already executing"))
+            };
+            (*next)(py).map_or(Ok(None), |r| r.map(Some))
+        }
+    });
+
+    pub fn create(py: Python, next: NextFn) -> PyResult<PyObject> {
+        Ok(RustIterator::create_instance(py, RefCell::new(next))?.into_object())
+    }
+}
+
+/// Wraps a rust closure into a Python iterator object.
+///
+/// The returned object implements `__iter__` and `__next__`, so Python code
+/// can consume it with `for` loops or `next()`.
+/// Each call to `__next__` calls `next` with the `Python` token, so items are produced lazily.
+/// An `Err` item is raised as exception; when `next` returns `None`,
+/// `StopIteration` is raised.
+///
+/// A rust iterator can be wrapped by calling its `next()` method from the closure.
+///
+/// # Example
+/// ```
+/// use cpython::{Python, PyDict, PythonObject, ToPyObject, py_iterator};
+///
+/// let gil = Python::acquire_gil();
+/// let py = gil.python();
+/// let mut numbers = 0..4;
+/// let squares = py_iterator(py, move |py| {
+///     numbers.next().map(|i: i32| Ok((i * i).to_py_object(py).into_object()))
+/// }).unwrap();
+/// let locals = PyDict::new(py);
+/// locals.set_item(py, "squares", squares).unwrap();
+/// py.run("assert list(squares) == [0, 1, 4, 9]", None, Some(&locals)).unwrap();
+/// ```
+pub fn py_iterator<F>(py: Python, next: F) -> PyResult<PyObject>
+    where F: FnMut(Python) -> Option<PyResult<PyObject>> + Send + 'static
+{
+    rust_iterator::create(py, Box::new(next))
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use python::{Python, PythonObject};
+    use conversion::ToPyObject;
+    use objects::{PyDict, exc};
+    use err::PyErr;
+    use objectprotocol::ObjectProtocol;
+    use super::py_iterator;
+
+    #[test]
+    fn test_lazy_squares() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let produced = Arc::new(AtomicUsize::new(0));
+        let counter = produced.clone();
+        let mut numbers = 0..5;
+        let it = py_iterator(py, move |py| {
+            numbers.next().map(|i: i64| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Ok((i * i).to_py_object(py).into_object())
+            })
+        }).unwrap();
+        assert_eq!(produced.load(Ordering::SeqCst), 0);
+
+        let d = PyDict::new(py);
+        d.set_item(py, "it", it).unwrap();
+        py.run("assert iter(it) is it\nassert next(it) == 0", None, Some(&d)).unwrap();
+        assert_eq!(produced.load(Ordering::SeqCst), 1);
+        py.run("assert [x for x in it] == [1, 4, 9, 16]", None, Some(&d)).unwrap();
+        py.run("assert next(it, 'done') == 'done'", None, Some(&d)).unwrap();
+    }
+
+    #[test]
+    fn test_error_propagates() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let mut numbers = 0..3;
+        let it = py_iterator(py, move |py| {
+            numbers.next().map(|i: i32| {
+                if i == 1 {
+                    Err(PyErr::new::<exc::ValueError, _>(py, "bad item"))
+                } else {
+                    Ok(i.to_py_object(py).into_object())
+                }
+            })
+        }).unwrap();
+        let mut iter = it.iter(py).unwrap();
+        assert_eq!(iter.next().unwrap().unwrap().extract::<i32>(py).unwrap(), 0);
+        assert!(iter.next().unwrap().unwrap_err().matches(py, py.get_type::<exc::ValueError>()));
+        assert_eq!(iter.next().unwrap().unwrap().extract::<i32>(py).unwrap(), 2);
+        assert!(iter.next().is_none());
+    }
+}
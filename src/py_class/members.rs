@@ -67,7 +67,7 @@ macro_rules! py_class_init_members {
 #[macro_export(local_inner_macros)]
 #[doc(hidden)]
 macro_rules! py_class_instance_method {
-    ($py:ident, $class:ident :: $f:ident [ $( { $pname:ident : $ptype:ty = $detail:tt } )* ]) => {{
+    ($py:ident, $class:ident :: $f:ident, { $($doc:expr),* } [ $( { $pname:ident : $ptype:ty = $detail:tt } )* ]) => {{
         unsafe extern "C" fn wrap_instance_method(
             slf: *mut $crate::_detail::ffi::PyObject,
             args: *mut $crate::_detail::ffi::PyObject,
@@ -89,7 +89,7 @@ macro_rules! py_class_instance_method {
                 })
         }
         unsafe {
-            let method_def = py_method_def!(_cpython__py_class__members__stringify!($f), 0, wrap_instance_method);
+            let method_def = py_method_def!(_cpython__py_class__members__stringify!($f), 0, wrap_instance_method, { $($doc),* });
             $crate::py_class::members::create_instance_method_descriptor::<$class>(method_def)
         }
     }}
@@ -114,7 +114,7 @@ impl <T> TypeMember<T> for InstanceMethodDescriptor<T> where T: PythonObject {
 #[macro_export]
 #[doc(hidden)]
 macro_rules! py_class_class_method {
-    ($py:ident, $class:ident :: $f:ident [ $( { $pname:ident : $ptype:ty = $detail:tt } )* ]) => {{
+    ($py:ident, $class:ident :: $f:ident, { $($doc:expr),* } [ $( { $pname:ident : $ptype:ty = $detail:tt } )* ]) => {{
         unsafe extern "C" fn wrap_class_method(
             cls: *mut $crate::_detail::ffi::PyObject,
             args: *mut $crate::_detail::ffi::PyObject,
@@ -138,7 +138,7 @@ macro_rules! py_class_class_method {
         unsafe {
             let method_def = py_method_def!(_cpython__py_class__members__stringify!($f),
                 $crate::_detail::ffi::METH_CLASS,
-                wrap_class_method);
+                wrap_class_method, { $($doc),* });
             $crate::py_class::members::create_class_method_descriptor(method_def)
         }
     }}
@@ -164,7 +164,7 @@ impl <T> TypeMember<T> for ClassMethodDescriptor where T: PythonObject {
 #[macro_export]
 #[doc(hidden)]
 macro_rules! py_class_static_method {
-    ($py:ident, $class:ident :: $f:ident [ $( { $pname:ident : $ptype:ty = $detail:tt } )* ]) => {{
+    ($py:ident, $class:ident :: $f:ident, { $($doc:expr),* } [ $( { $pname:ident : $ptype:ty = $detail:tt } )* ]) => {{
         unsafe extern "C" fn wrap_static_method(
             _slf: *mut $crate::_detail::ffi::PyObject,
             args: *mut $crate::_detail::ffi::PyObject,
@@ -185,7 +185,7 @@ macro_rules! py_class_static_method {
         unsafe {
             let method_def = py_method_def!(_cpython__py_class__members__stringify!($f),
                 $crate::_detail::ffi::METH_STATIC,
-                wrap_static_method);
+                wrap_static_method, { $($doc),* });
             $crate::_detail::py_fn_impl($py, method_def)
         }
     }}
@@ -24,6 +24,10 @@ mod py_class_impl3;
 #[doc(hidden)] pub mod slots;
 #[doc(hidden)] pub mod members;
 pub mod gc;
+pub mod buffer;
+mod iterator;
+
+pub use self::iterator::py_iterator;
 
 use libc;
 use std::{mem, ptr, cell};
@@ -56,10 +60,23 @@ pub trait PythonObjectFromPyClassMacro : python::PythonObjectWithTypeObject {
     fn add_to_module(py: Python, module: &PyModule) -> PyResult<()>;
 }
 
+/// Returns true if data of type `T` cannot be stored inline in the Python object.
+///
+/// `tp_alloc` only guarantees the alignment required for `ffi::PyObject`,
+/// so over-aligned types (e.g. `#[repr(align(64))]`) are stored behind a `Box` instead.
+#[inline]
+fn data_is_boxed<T>() -> bool {
+    mem::align_of::<T>() > mem::align_of::<ffi::PyObject>()
+}
+
 #[inline]
 #[doc(hidden)]
 pub fn data_offset<T>(base_size: usize) -> usize {
-    let align = mem::align_of::<T>();
+    let align = if data_is_boxed::<T>() {
+        mem::align_of::<Box<T>>()
+    } else {
+        mem::align_of::<T>()
+    };
     // round base_size up to next multiple of align
     (base_size + align - 1) / align * align
 }
@@ -67,14 +84,24 @@ pub fn data_offset<T>(base_size: usize) -> usize {
 #[inline]
 #[doc(hidden)]
 pub fn data_new_size<T>(base_size: usize) -> usize {
-    data_offset::<T>(base_size) + mem::size_of::<T>()
+    let size = if data_is_boxed::<T>() {
+        mem::size_of::<Box<T>>()
+    } else {
+        mem::size_of::<T>()
+    };
+    data_offset::<T>(base_size) + size
 }
 
 #[inline]
 #[doc(hidden)]
 pub unsafe fn data_get<'a, T>(_py: Python<'a>, obj: &'a PyObject, offset: usize) -> &'a T {
-    let ptr = (obj.as_ptr() as *const u8).offset(offset as isize) as *const T;
-    &*ptr
+    let ptr = (obj.as_ptr() as *const u8).offset(offset as isize);
+    if data_is_boxed::<T>() {
+        // a Box<T> of a sized T has the same representation as a pointer to T
+        &**(ptr as *const *const T)
+    } else {
+        &*(ptr as *const T)
+    }
 }
 
 #[inline]
@@ -82,15 +109,23 @@ pub unsafe fn data_get<'a, T>(_py: Python<'a>, obj: &'a PyObject, offset: usize)
 pub unsafe fn data_init<'a, T>(_py: Python<'a>, obj: &'a PyObject, offset: usize, value: T)
     where T: Send + 'static
 {
-    let ptr = (obj.as_ptr() as *mut u8).offset(offset as isize) as *mut T;
-    ptr::write(ptr, value)
+    let ptr = (obj.as_ptr() as *mut u8).offset(offset as isize);
+    if data_is_boxed::<T>() {
+        ptr::write(ptr as *mut Box<T>, Box::new(value))
+    } else {
+        ptr::write(ptr as *mut T, value)
+    }
 }
 
 #[inline]
 #[doc(hidden)]
 pub unsafe fn data_drop<'a, T>(_py: Python<'a>, obj: *mut ffi::PyObject, offset: usize) {
-    let ptr = (obj as *mut u8).offset(offset as isize) as *mut T;
-    ptr::drop_in_place(ptr)
+    let ptr = (obj as *mut u8).offset(offset as isize);
+    if data_is_boxed::<T>() {
+        ptr::drop_in_place(ptr as *mut Box<T>)
+    } else {
+        ptr::drop_in_place(ptr as *mut T)
+    }
 }
 
 #[inline]
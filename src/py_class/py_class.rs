@@ -111,6 +111,17 @@ Because Python object instances can be freely shared (Python has no concept of "
 data fields cannot be declared as `mut`.
 If mutability is required, you have to use interior mutability (`Cell` or `RefCell`).
 
+Data types that require a larger alignment than `PyObject` (e.g. `#[repr(align(64))]`)
+are transparently stored behind a `Box`, because the Python allocator does not
+guarantee such alignment for the object itself.
+
+Each data member is stored inline after the previous ones, and its size is included
+in the `tp_basicsize` of the type, so there is no need for separately allocated storage.
+For example, a fixed number of slots for caching Python objects can be declared as
+`data cache: [RefCell<Option<PyObject>>; 4];`. The slots are initialized by
+`create_instance`, and any stored references are released when the instance
+is deallocated.
+
 If data members are used to store references to other Python objects, make sure
 to read the section "Garbage Collector Integration".
 
@@ -151,6 +162,50 @@ Declares a static method callable from Python.
 * For details on `parameter-list`, see the documentation of `py_argparse!()`.
 * The return type must be `PyResult<T>` for some `T` that implements `ToPyObject`.
 
+## Docstrings
+Doc comments on instance methods, class methods and static methods become
+the `__doc__` of the method, so they show up in `help(MyType.method_name)`.
+Each `///` line becomes one line of the docstring.
+Doc comments on other items (data declarations, special methods) are allowed,
+but are not visible from Python.
+
+```
+# #[macro_use] extern crate cpython;
+# use cpython::PyResult;
+py_class!(class MyType |py| {
+    /// Returns the answer.
+    def answer(&self) -> PyResult<i32> {
+        Ok(42)
+    }
+});
+# fn main() {}
+```
+
+## Static data
+`static data_name = value;`
+
+Declares a class attribute, a constant shared by all instances of the class.
+
+* `value` is evaluated once, when the type object is initialized.
+  It may use the `py` token and must be of some type that implements `ToPyObject`.
+* The attribute is stored in the type dict, so it can be read both from the class
+  (`MyType.data_name`) and from its instances.
+* Python code cannot assign to the attribute on the type object.
+
+## Metaclass
+`metaclass = value;`
+
+Sets the type of the type object; by default this is `type`.
+
+* `value` is evaluated once, when the type object is initialized.
+  It may use the `py` token and the `?` operator, and must be of type `PyType`.
+* The metaclass must be a subclass of `type` that does not add instance fields
+  (for example, through `__slots__`). Otherwise, initializing the class fails with `TypeError`.
+* Attributes and methods of the metaclass are available on the class
+  (`MyType.attr`), but not on its instances.
+* The metaclass' `__new__` and `__init__` are not called for the class,
+  so metaclasses that rely on them to set up state (like `abc.ABCMeta`) are not supported.
+
 ## __new__
 `def __new__(cls, parameter-list) -> PyResult<...> { ... }`
 
@@ -165,6 +220,22 @@ Declares a constructor method callable from Python.
 * The return type must be `PyResult<T>` for some `T` that implements `ToPyObject`.
   Usually, `T` will be `MyType`.
 
+## __init__
+`def __init__(&self, parameter-list) -> PyResult<()> { ... }`
+
+Declares an initializer, which runs on an already-allocated object (the `tp_init` slot).
+
+* When the class is called from Python, `__new__` is invoked first, followed by `__init__`
+  with the same arguments. Both methods must therefore accept the same `parameter-list`.
+* `__init__` may be called again on an existing object (`obj.__init__(...)`).
+  Since data members are only accessible by shared reference, state that `__init__`
+  overwrites must be stored in a `Cell` or `RefCell`.
+* A class that declares `__init__` but not `__new__` cannot be instantiated from Python,
+  so `__init__` only runs when called explicitly. Initializing such a class emits a `RuntimeWarning`.
+* `py_class!` types cannot be subclassed in Python (they don't set `Py_TPFLAGS_BASETYPE`),
+  so there is no Python subclass whose `__init__` could call `super().__init__(...)`.
+* For details on `parameter-list`, see the documentation of `py_argparse!()`.
+
 ## Garbage Collector Integration
 
 If your type owns references to other python objects, you will need to
@@ -223,6 +294,30 @@ statement, after the mutable borrow on the `RefCell` has expired.
 Note that this restriction applies not only to `__clear__`, but to all methods
 that use `RefCell::borrow_mut`.
 
+## Deallocation
+
+When the last reference to an instance goes away, CPython calls the `tp_dealloc` slot
+while holding the GIL. The data members are then dropped in declaration order,
+so `Drop` impls of data members may use `Python::assume_gil_acquired()`.
+
+To run code that needs a `Python` token and a fully intact object,
+implement the special member function `__dealloc__`:
+
+`def __dealloc__(&self) { ... }`
+
+* `__dealloc__` runs before any data member is dropped, so it can still access them.
+* Any exception that was pending when the object was deallocated is saved and
+  restored around the call; `__dealloc__` has no way to report errors.
+* A panic in `__dealloc__` aborts the process.
+* `__dealloc__` is not called for instances that are deallocated while the
+  interpreter is shutting down (e.g. instances stored in module globals),
+  because the Python state it might use may already have been torn down.
+  The data members are still dropped.
+* If `__dealloc__` stores a new reference to `self`, the object is resurrected
+  and neither the data members nor the memory are freed.
+
+For most resources, a data member with a `Drop` impl is sufficient.
+
 ## Iterator Types
 
 Iterators can be defined using the Python special methods `__iter__` and `__next__`:
@@ -319,6 +414,9 @@ py_class!(class MyIterator |py| {
 
     Called by Python `del self[key]`.
 
+    Defining `__setitem__` or `__delitem__` without `__getitem__` emits a `RuntimeWarning`
+    when the class is initialized.
+
   * `def __reversed__(&self) -> PyResult<impl ToPyObject>`
 
     Called by the `reversed()` built-in.
@@ -381,6 +479,20 @@ py_class!(class MyIterator |py| {
   * `def __enter__(&self) -> PyResult<impl ToPyObject>`
   * `def __exit__(&self, ty: Option<PyType>, value: PyObject, traceback: PyObject) -> PyResult<bool>`
 
+These are normal methods, so the `with` statement finds them in the type dict.
+On a normal exit, `__exit__` receives `None` for all three arguments.
+Returning `Ok(true)` suppresses the exception raised in the `with` block.
+
+## Buffer Protocol
+
+  * `def __buffer__(&self) -> PyResult<BufferInfo>`
+
+    Exports the memory described by the returned `py_class::buffer::BufferInfo`
+    through the buffer protocol, so that `memoryview`, `bytes`, `array.array` or numpy
+    can access it without copying.
+    The buffer views keep a reference to the object, so the memory must stay valid
+    as long as the object is alive; see `BufferInfo::from_slice()`.
+
 ## Other Special Methods
 
   * `def __bool__(&self) -> PyResult<bool>`
@@ -411,6 +523,8 @@ macro_rules! py_class {
                     /* traverse_proc: */ None,
                     /* traverse_data: */ [ /*name*/ ]
                 },
+                /* dealloc: */ None,
+                /* metaclass: */ None,
                 /* data: */ [ /* { offset, name, type } */ ]
                 // TODO: base type, documentation, ...
             }
@@ -423,6 +537,7 @@ macro_rules! py_class {
                     sdi_setitem: {},
                     sdi_delitem: {},
                 ]
+                /* as_buffer */   [ /* slot: expr, */ ]
             }
             /* impls: */ { /* impl body */ }
             /* members: */ { /* ident = expr; */ }
@@ -440,6 +555,8 @@ macro_rules! py_class {
                     /* traverse_proc: */ None,
                     /* traverse_data: */ [ /*name*/ ]
                 },
+                /* dealloc: */ None,
+                /* metaclass: */ None,
                 /* data: */ [ /* { offset, name, type } */ ]
                 // TODO: base type, documentation, ...
             }
@@ -452,6 +569,7 @@ macro_rules! py_class {
                     sdi_setitem: {},
                     sdi_delitem: {},
                 ]
+                /* as_buffer */   [ /* slot: expr, */ ]
             }
             /* impls: */ { /* impl body */ }
             /* members: */ { /* ident = expr; */ }
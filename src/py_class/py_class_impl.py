@@ -45,6 +45,8 @@ base_case = '''
             $size:expr,
             { $( $class_visibility:tt )* },
             $gc:tt,
+            /* dealloc: */ $dealloc:tt,
+            /* metaclass: */ $metaclass:tt,
             /* data: */ [ $( { $data_offset:expr, $data_name:ident, $data_ty:ty } )* ]
         }
         $slots:tt { $( $imp:item )* } $members:tt
@@ -82,6 +84,13 @@ base_case = '''
             }
         }
 
+        impl ::std::fmt::Debug for $class {
+            #[inline]
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                ::std::fmt::Debug::fmt(&self._unsafe_inner, f)
+            }
+        }
+
         impl $crate::PythonObjectWithCheckedDowncast for $class {
             #[inline]
             fn downcast_from<'p>(py: $crate::Python<'p>, obj: $crate::PyObject) -> Result<$class, $crate::PythonObjectDowncastError<'p>> {
@@ -123,6 +132,9 @@ base_case = '''
                 }
 
                 unsafe fn dealloc(py: $crate::Python, obj: *mut $crate::_detail::ffi::PyObject) {
+                    if !py_class_call_dealloc_hook!($class, py, obj, $dealloc) {
+                        return; // object was resurrected by __dealloc__
+                    }
                     $( $crate::py_class::data_drop::<$data_ty>(py, obj, $data_offset); )*
                     <$base_type as $crate::py_class::BaseObject>::dealloc(py, obj)
                 }
@@ -182,9 +194,10 @@ base_case = '''
                     }
 
                     fn init($py: $crate::Python, module_name: Option<&str>) -> $crate::PyResult<$crate::PyType> {
-                        py_class_type_object_dynamic_init!($class, $py, TYPE_OBJECT, module_name, $slots);
+                        py_class_type_object_dynamic_init!($class, $py, TYPE_OBJECT, module_name, $metaclass, $slots);
                         py_class_init_members!($class, $py, TYPE_OBJECT, $members);
                         unsafe {
+                            $crate::py_class::slots::validate_type_object($py, &TYPE_OBJECT)?;
                             if $crate::_detail::ffi::PyType_Ready(&mut TYPE_OBJECT) == 0 {
                                 Ok($crate::PyType::from_type_ptr($py, &mut TYPE_OBJECT))
                             } else {
@@ -239,7 +252,8 @@ slot_groups = (
     ('nb', 'as_number', None),
     ('sq', 'as_sequence', None),
     ('mp', 'as_mapping', None),
-    ('sdi', 'setdelitem', ['sdi_setitem', 'sdi_delitem'])
+    ('sdi', 'setdelitem', ['sdi_setitem', 'sdi_delitem']),
+    ('bf', 'as_buffer', None)
 )
 
 def generate_case(pattern, old_info=None, new_info=None, new_impl=None, new_slots=None, new_members=None):
@@ -253,6 +267,8 @@ def generate_case(pattern, old_info=None, new_info=None, new_impl=None, new_slot
         write('$size: expr,\n')
         write('$class_visibility: tt,\n')
         write('$gc: tt,\n')
+        write('$dealloc: tt,\n')
+        write('$metaclass: tt,\n')
         write('[ $( $data:tt )* ]\n')
         write('}\n')
     else:
@@ -337,6 +353,8 @@ def data_decl():
             /* size: */ $crate::py_class::data_new_size::<$data_type>($size),
             $class_visibility,
             $gc,
+            $dealloc,
+            $metaclass,
             /* data: */ [
                 $($data)*
                 {
@@ -402,6 +420,8 @@ def traverse_and_clear():
                 /* traverse_proc: */ None,
                 $traverse_data: tt
             },
+            $dealloc: tt,
+            $metaclass: tt,
             $datas: tt
         }
         ''',
@@ -414,6 +434,8 @@ def traverse_and_clear():
                 /* traverse_proc: */ $class::__traverse__,
                 $traverse_data
             },
+            $dealloc,
+            $metaclass,
             $datas
         }
         ''',
@@ -438,6 +460,63 @@ def traverse_and_clear():
             }
         ''')
 
+def dealloc():
+    generate_case('def __dealloc__ (&$slf:ident) $body:block',
+        old_info = '''
+        /* info: */ {
+            $base_type: ty,
+            $size: expr,
+            $class_visibility: tt,
+            $gc: tt,
+            /* dealloc: */ None,
+            $metaclass: tt,
+            $datas: tt
+        }
+        ''',
+        new_info='''
+        /* info: */ {
+            $base_type,
+            $size,
+            $class_visibility,
+            $gc,
+            /* dealloc: */ { $class::__dealloc__ },
+            $metaclass,
+            $datas
+        }
+        ''',
+        new_impl='''
+            py_coerce_item!{
+                impl $class {
+                    fn __dealloc__(&$slf, $py: $crate::Python) $body
+                }
+            }
+        ''')
+
+def metaclass_decl():
+    generate_case('metaclass = $metaclass:expr;',
+        old_info = '''
+        /* info: */ {
+            $base_type: ty,
+            $size: expr,
+            $class_visibility: tt,
+            $gc: tt,
+            $dealloc: tt,
+            /* metaclass: */ None,
+            $datas: tt
+        }
+        ''',
+        new_info='''
+        /* info: */ {
+            $base_type,
+            $size,
+            $class_visibility,
+            $gc,
+            $dealloc,
+            /* metaclass: */ { $metaclass },
+            $datas
+        }
+        ''')
+
 def generate_instance_method(special_name=None, decoration='',
         slot=None, add_member=False, value_macro=None, value_args=None):
     name_pattern = special_name or '$name:ident'
@@ -468,9 +547,9 @@ def generate_instance_method(special_name=None, decoration='',
     impl(False) # without parameters
     impl(True) # with parameters
 
-def static_method():
+def static_method(decoration='', docs='{}'):
     generate_case(
-        '@staticmethod def $name:ident ($($p:tt)*) -> $res_type:ty { $( $body:tt )* }',
+        '%s @staticmethod def $name:ident ($($p:tt)*) -> $res_type:ty { $( $body:tt )* }' % decoration,
         new_impl='''
             py_argparse_parse_plist!{
                 py_class_impl_item { $class, $py, $name() $res_type; { $($body)* } }
@@ -479,15 +558,33 @@ def static_method():
         ''',
         new_members=[('$name', '''
             py_argparse_parse_plist!{
-                py_class_static_method {$py, $class::$name}
+                py_class_static_method {$py, $class::$name, %s}
                 ($($p)*)
             }
-        ''')])
+        ''' % docs)])
 
 def static_data():
     generate_case('static $name:ident = $init:expr;',
         new_members=[('$name', '$init')])
 
+doc_attributes = '$(#[doc=$doc:expr])+'
+doc_strings = '{ $($doc),+ }'
+
+def documented_special_method(special_name):
+    # Doc comments on special methods are ignored;
+    # Python generates the docstrings of slot wrappers.
+    print('''
+    { { %s def %s $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def %s $($tail)* } $( $stuff )* }
+    };''' % (doc_attributes, special_name, special_name))
+
+def undocumented_item():
+    # Doc comments on other items (e.g. data declarations) are ignored.
+    print('''
+    { { #[doc=$doc:expr] $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { $($tail)* } $( $stuff )* }
+    };''')
+
 macro_end = '''
 }
 
@@ -596,6 +693,19 @@ def operator(special_name, slot,
     # when using the wrong method signature
     error('Invalid signature for operator %s' % special_name)(special_name)
 
+@special_method
+def buffer_operator(special_name):
+    generate_case(
+        pattern='def %s(&$slf:ident) -> $res_type:ty { $($body:tt)* }' % special_name,
+        new_impl='py_class_impl_item! { $class, $py, %s(&$slf,) $res_type; { $($body)* } [] }'
+                 % special_name,
+        new_slots=[
+            ('bf_getbuffer', 'py_class_buffer_slot!($class::%s)' % special_name),
+            ('bf_releasebuffer', 'Some($crate::py_class::buffer::bf_releasebuffer)')
+        ]
+    )
+    error('Invalid signature for operator %s' % special_name)(special_name)
+
 @special_method
 def call_operator(special_name, slot):
     generate_instance_method(
@@ -604,6 +714,14 @@ def call_operator(special_name, slot):
         value_macro='py_class_call_slot',
         value_args='$class::%s' % special_name)
 
+@special_method
+def init_operator(special_name, slot):
+    generate_instance_method(
+        special_name=special_name,
+        slot=slot,
+        value_macro='py_class_init_slot',
+        value_args='$class::%s' % special_name)
+
 @special_method
 def binary_numeric_operator(special_name, slot):
     generate_case(
@@ -626,12 +744,12 @@ def inplace_numeric_operator(special_name, slot):
              args=[Argument('other')])(special_name)
 
 special_names = {
-    '__init__': error('__init__ is not supported by py_class!; use __new__ instead.'),
+    '__init__': init_operator('tp_init'),
     '__new__': special_class_method(
         slot='tp_new',
         value_macro='py_class_wrap_newfunc',
         value_args='$class::__new__'),
-    '__del__': error('__del__ is not supported by py_class!; Use a data member with a Drop impl instead.'),
+    '__del__': error('__del__ is not supported by py_class!; Use __dealloc__ or a data member with a Drop impl instead.'),
     '__repr__': operator('tp_repr', res_type="PyString"),
     '__str__': operator('tp_str', res_type="PyString"),
     '__unicode__': normal_method(),
@@ -767,6 +885,9 @@ special_names = {
     '__enter__': normal_method(),
     '__exit__': normal_method(),
 
+    # Buffer protocol
+    '__buffer__': buffer_operator(),
+
     # Coroutines
     '__await__': unimplemented(),
     '__aiter__': unimplemented(),
@@ -791,18 +912,23 @@ def main():
     print(base_case)
     data_decl()
     traverse_and_clear()
+    dealloc()
+    metaclass_decl()
     for name, f in sorted(special_names.items()):
+        documented_special_method(name)
         f(name)
-    generate_instance_method(
-        add_member=True,
-        value_macro='py_class_instance_method',
-        value_args='$py, $class::$name')
-    generate_class_method(decoration='@classmethod',
-        add_member=True,
-        value_macro='py_class_class_method',
-        value_args='$py, $class::$name')
-    static_method()
+    for decoration, docs in (('', '{}'), (doc_attributes, doc_strings)):
+        generate_instance_method(decoration=decoration,
+            add_member=True,
+            value_macro='py_class_instance_method',
+            value_args='$py, $class::$name, ' + docs)
+        generate_class_method(decoration=decoration + ' @classmethod',
+            add_member=True,
+            value_macro='py_class_class_method',
+            value_args='$py, $class::$name, ' + docs)
+        static_method(decoration, docs)
     static_data()
+    undocumented_item()
     print(macro_end)
 
 if __name__ == '__main__':
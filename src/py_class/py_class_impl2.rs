@@ -37,6 +37,8 @@ macro_rules! py_class_impl {
             $size:expr,
             { $( $class_visibility:tt )* },
             $gc:tt,
+            /* dealloc: */ $dealloc:tt,
+            /* metaclass: */ $metaclass:tt,
             /* data: */ [ $( { $data_offset:expr, $data_name:ident, $data_ty:ty } )* ]
         }
         $slots:tt { $( $imp:item )* } $members:tt
@@ -74,6 +76,13 @@ macro_rules! py_class_impl {
             }
         }
 
+        impl ::std::fmt::Debug for $class {
+            #[inline]
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                ::std::fmt::Debug::fmt(&self._unsafe_inner, f)
+            }
+        }
+
         impl $crate::PythonObjectWithCheckedDowncast for $class {
             #[inline]
             fn downcast_from<'p>(py: $crate::Python<'p>, obj: $crate::PyObject) -> Result<$class, $crate::PythonObjectDowncastError<'p>> {
@@ -115,6 +124,9 @@ macro_rules! py_class_impl {
                 }
 
                 unsafe fn dealloc(py: $crate::Python, obj: *mut $crate::_detail::ffi::PyObject) {
+                    if !py_class_call_dealloc_hook!($class, py, obj, $dealloc) {
+                        return; // object was resurrected by __dealloc__
+                    }
                     $( $crate::py_class::data_drop::<$data_ty>(py, obj, $data_offset); )*
                     <$base_type as $crate::py_class::BaseObject>::dealloc(py, obj)
                 }
@@ -174,9 +186,10 @@ macro_rules! py_class_impl {
                     }
 
                     fn init($py: $crate::Python, module_name: Option<&str>) -> $crate::PyResult<$crate::PyType> {
-                        py_class_type_object_dynamic_init!($class, $py, TYPE_OBJECT, module_name, $slots);
+                        py_class_type_object_dynamic_init!($class, $py, TYPE_OBJECT, module_name, $metaclass, $slots);
                         py_class_init_members!($class, $py, TYPE_OBJECT, $members);
                         unsafe {
+                            $crate::py_class::slots::validate_type_object($py, &TYPE_OBJECT)?;
                             if $crate::_detail::ffi::PyType_Ready(&mut TYPE_OBJECT) == 0 {
                                 Ok($crate::PyType::from_type_ptr($py, &mut TYPE_OBJECT))
                             } else {
@@ -196,6 +209,8 @@ macro_rules! py_class_impl {
             $size: expr,
             $class_visibility: tt,
             $gc: tt,
+            $dealloc: tt,
+            $metaclass: tt,
             [ $( $data:tt )* ]
         }
         $slots:tt
@@ -209,6 +224,8 @@ macro_rules! py_class_impl {
             /* size: */ $crate::py_class::data_new_size::<$data_type>($size),
             $class_visibility,
             $gc,
+            $dealloc,
+            $metaclass,
             /* data: */ [
                 $($data)*
                 {
@@ -245,6 +262,8 @@ macro_rules! py_class_impl {
                 /* traverse_proc: */ None,
                 $traverse_data: tt
             },
+            $dealloc: tt,
+            $metaclass: tt,
             $datas: tt
         }
         $slots:tt
@@ -261,6 +280,8 @@ macro_rules! py_class_impl {
                 /* traverse_proc: */ $class::__traverse__,
                 $traverse_data
             },
+            $dealloc,
+            $metaclass,
             $datas
         }
         $slots
@@ -282,7 +303,7 @@ macro_rules! py_class_impl {
         $class:ident $py:ident $info:tt
         /* slots: */ {
             /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
-            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -294,7 +315,7 @@ macro_rules! py_class_impl {
                 $( $tp_slot_name : $tp_slot_value, )*
                 tp_clear: py_class_tp_clear!($class),
             ]
-            $as_number $as_sequence $as_mapping $setdelitem
+            $as_number $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -306,12 +327,79 @@ macro_rules! py_class_impl {
         }
         $members
     }};
+    { { def __dealloc__ (&$slf:ident) $body:block $($tail:tt)* }
+        $class:ident $py:ident
+        /* info: */ {
+            $base_type: ty,
+            $size: expr,
+            $class_visibility: tt,
+            $gc: tt,
+            /* dealloc: */ None,
+            $metaclass: tt,
+            $datas: tt
+        }
+        $slots:tt
+        { $( $imp:item )* }
+        $members:tt
+    } => { py_class_impl! {
+        { $($tail)* }
+        $class $py
+        /* info: */ {
+            $base_type,
+            $size,
+            $class_visibility,
+            $gc,
+            /* dealloc: */ { $class::__dealloc__ },
+            $metaclass,
+            $datas
+        }
+        $slots
+        /* impl: */ {
+            $($imp)*
+            py_coerce_item!{
+                impl $class {
+                    fn __dealloc__(&$slf, $py: $crate::Python) $body
+                }
+            }
+        }
+        $members
+    }};
+    { { metaclass = $metaclass:expr; $($tail:tt)* }
+        $class:ident $py:ident
+        /* info: */ {
+            $base_type: ty,
+            $size: expr,
+            $class_visibility: tt,
+            $gc: tt,
+            $dealloc: tt,
+            /* metaclass: */ None,
+            $datas: tt
+        }
+        $slots:tt $impls:tt $members:tt
+    } => { py_class_impl! {
+        { $($tail)* }
+        $class $py
+        /* info: */ {
+            $base_type,
+            $size,
+            $class_visibility,
+            $gc,
+            $dealloc,
+            /* metaclass: */ { $metaclass },
+            $datas
+        }
+        $slots $impls $members
+    }};
+
+    { { $(#[doc=$doc:expr])+ def __abs__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __abs__ $($tail)* } $( $stuff )* }
+    };
     { { def __abs__(&$slf:ident) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -324,7 +412,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_absolute: py_class_unary_slot!($class::__abs__, *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -336,12 +424,16 @@ macro_rules! py_class_impl {
     { { def __abs__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "Invalid signature for operator __abs__" }
     };
+
+    { { $(#[doc=$doc:expr])+ def __add__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __add__ $($tail)* } $( $stuff )* }
+    };
     { { def __add__($left:ident, $right:ident) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -354,7 +446,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_add: py_class_binary_numeric_slot!($class::__add__),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -367,23 +459,39 @@ macro_rules! py_class_impl {
         py_error! { "Invalid signature for binary numeric operator __add__" }
     };
 
+    { { $(#[doc=$doc:expr])+ def __aenter__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __aenter__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __aenter__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__aenter__ is not supported by py_class! yet." }
     };
 
+    { { $(#[doc=$doc:expr])+ def __aexit__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __aexit__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __aexit__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__aexit__ is not supported by py_class! yet." }
     };
 
+    { { $(#[doc=$doc:expr])+ def __aiter__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __aiter__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __aiter__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__aiter__ is not supported by py_class! yet." }
     };
+
+    { { $(#[doc=$doc:expr])+ def __and__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __and__ $($tail)* } $( $stuff )* }
+    };
     { { def __and__($left:ident, $right:ident) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -396,7 +504,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_and: py_class_binary_numeric_slot!($class::__and__),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -409,15 +517,23 @@ macro_rules! py_class_impl {
         py_error! { "Invalid signature for binary numeric operator __and__" }
     };
 
+    { { $(#[doc=$doc:expr])+ def __await__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __await__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __await__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__await__ is not supported by py_class! yet." }
     };
+
+    { { $(#[doc=$doc:expr])+ def __bool__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __bool__ $($tail)* } $( $stuff )* }
+    };
     { { def __bool__(&$slf:ident) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -430,7 +546,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_nonzero: py_class_unary_slot!($class::__bool__, $crate::_detail::libc::c_int, $crate::py_class::slots::BoolConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -442,11 +558,52 @@ macro_rules! py_class_impl {
     { { def __bool__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "Invalid signature for operator __bool__" }
     };
+
+    { { $(#[doc=$doc:expr])+ def __buffer__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __buffer__ $($tail)* } $( $stuff )* }
+    };
+    { { def __buffer__(&$slf:ident) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
+        $class:ident $py:ident $info:tt
+        /* slots: */ {
+            $type_slots:tt $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            /* as_buffer */ [ $( $bf_slot_name:ident : $bf_slot_value:expr, )* ]
+        }
+        { $( $imp:item )* }
+        $members:tt
+    } => { py_class_impl! {
+        { $($tail)* }
+        $class $py $info
+        /* slots: */ {
+            $type_slots $as_number $as_sequence $as_mapping $setdelitem
+            /* as_buffer */ [
+                $( $bf_slot_name : $bf_slot_value, )*
+                bf_getbuffer: py_class_buffer_slot!($class::__buffer__),
+                bf_releasebuffer: Some($crate::py_class::buffer::bf_releasebuffer),
+            ]
+        }
+        /* impl: */ {
+            $($imp)*
+            py_class_impl_item! { $class, $py, __buffer__(&$slf,) $res_type; { $($body)* } [] }
+        }
+        $members
+    }};
+
+    { { def __buffer__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_error! { "Invalid signature for operator __buffer__" }
+    };
+
+    { { $(#[doc=$doc:expr])+ def __bytes__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __bytes__ $($tail)* } $( $stuff )* }
+    };
+
+    { { $(#[doc=$doc:expr])+ def __call__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __call__ $($tail)* } $( $stuff )* }
+    };
     { {  def __call__ (&$slf:ident) -> $res_type:ty { $( $body:tt )* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
-            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -458,7 +615,7 @@ macro_rules! py_class_impl {
                 $( $tp_slot_name : $tp_slot_value, )*
                 tp_call: py_class_call_slot!{$class::__call__ []},
             ]
-            $as_number $as_sequence $as_mapping $setdelitem
+            $as_number $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -470,7 +627,7 @@ macro_rules! py_class_impl {
         $class:ident $py:ident $info:tt
         /* slots: */ {
             /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
-            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -482,7 +639,7 @@ macro_rules! py_class_impl {
                 $( $tp_slot_name : $tp_slot_value, )*
                 tp_call: py_argparse_parse_plist_impl!{py_class_call_slot {$class::__call__} [] ($($p)+,)},
             ]
-            $as_number $as_sequence $as_mapping $setdelitem
+            $as_number $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -494,23 +651,39 @@ macro_rules! py_class_impl {
         $members
     }};
 
+    { { $(#[doc=$doc:expr])+ def __cmp__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __cmp__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __cmp__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__cmp__ is not supported by py_class! use __richcmp__ instead." }
     };
 
+    { { $(#[doc=$doc:expr])+ def __coerce__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __coerce__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __coerce__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__coerce__ is not supported by py_class! yet." }
     };
 
+    { { $(#[doc=$doc:expr])+ def __complex__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __complex__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __complex__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__complex__ is not supported by py_class! yet." }
     };
+
+    { { $(#[doc=$doc:expr])+ def __contains__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __contains__ $($tail)* } $( $stuff )* }
+    };
     { { def __contains__(&$slf:ident, $item:ident : $item_type:ty) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             $type_slots:tt $as_number:tt
             /* as_sequence */ [ $( $sq_slot_name:ident : $sq_slot_value:expr, )* ]
-            $as_mapping:tt $setdelitem:tt
+            $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -523,7 +696,7 @@ macro_rules! py_class_impl {
                 $( $sq_slot_name : $sq_slot_value, )*
                 sq_contains: py_class_contains_slot!($class::__contains__, $item_type),
             ]
-            $as_mapping $setdelitem
+            $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -536,17 +709,33 @@ macro_rules! py_class_impl {
         py_error! { "Invalid signature for operator __contains__" }
     };
 
+    { { $(#[doc=$doc:expr])+ def __del__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __del__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __del__ $($tail:tt)* } $( $stuff:tt )* } => {
-        py_error! { "__del__ is not supported by py_class!; Use a data member with a Drop impl instead." }
+        py_error! { "__del__ is not supported by py_class!; Use __dealloc__ or a data member with a Drop impl instead." }
+    };
+
+    { { $(#[doc=$doc:expr])+ def __delattr__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __delattr__ $($tail)* } $( $stuff )* }
     };
 
     { { def __delattr__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__delattr__ is not supported by py_class! yet." }
     };
 
+    { { $(#[doc=$doc:expr])+ def __delete__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __delete__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __delete__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__delete__ is not supported by py_class! yet." }
     };
+
+    { { $(#[doc=$doc:expr])+ def __delitem__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __delitem__ $($tail)* } $( $stuff )* }
+    };
     { { def __delitem__(&$slf:ident, $key:ident : $key_type:ty) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
@@ -555,6 +744,7 @@ macro_rules! py_class_impl {
                 sdi_setitem: $sdi_setitem_slot_value:tt,
                 sdi_delitem: {},
             ]
+            $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -567,6 +757,7 @@ macro_rules! py_class_impl {
                 sdi_setitem: $sdi_setitem_slot_value,
                 sdi_delitem: { py_class_binary_slot!($class::__delitem__, $key_type, $crate::_detail::libc::c_int, $crate::py_class::slots::UnitCallbackConverter) },
             ]
+            $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -579,52 +770,108 @@ macro_rules! py_class_impl {
         py_error! { "Invalid signature for operator __delitem__" }
     };
 
+    { { $(#[doc=$doc:expr])+ def __dir__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __dir__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __dir__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__dir__ is not supported by py_class! yet." }
     };
 
+    { { $(#[doc=$doc:expr])+ def __div__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __div__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __div__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__div__ is not supported by py_class! yet." }
     };
 
+    { { $(#[doc=$doc:expr])+ def __divmod__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __divmod__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __divmod__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__divmod__ is not supported by py_class! yet." }
     };
 
+    { { $(#[doc=$doc:expr])+ def __enter__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __enter__ $($tail)* } $( $stuff )* }
+    };
+
+    { { $(#[doc=$doc:expr])+ def __eq__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __eq__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __eq__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__eq__ is not supported by py_class! use __richcmp__ instead." }
     };
 
+    { { $(#[doc=$doc:expr])+ def __exit__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __exit__ $($tail)* } $( $stuff )* }
+    };
+
+    { { $(#[doc=$doc:expr])+ def __float__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __float__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __float__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__float__ is not supported by py_class! yet." }
     };
 
+    { { $(#[doc=$doc:expr])+ def __floordiv__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __floordiv__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __floordiv__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__floordiv__ is not supported by py_class! yet." }
     };
 
+    { { $(#[doc=$doc:expr])+ def __format__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __format__ $($tail)* } $( $stuff )* }
+    };
+
+    { { $(#[doc=$doc:expr])+ def __ge__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __ge__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __ge__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__ge__ is not supported by py_class! use __richcmp__ instead." }
     };
 
+    { { $(#[doc=$doc:expr])+ def __get__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __get__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __get__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__get__ is not supported by py_class! yet." }
     };
 
+    { { $(#[doc=$doc:expr])+ def __getattr__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __getattr__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __getattr__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__getattr__ is not supported by py_class! yet." }
     };
 
+    { { $(#[doc=$doc:expr])+ def __getattribute__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __getattribute__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __getattribute__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__getattribute__ is not supported by py_class! yet." }
     };
+
+    { { $(#[doc=$doc:expr])+ def __getitem__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __getitem__ $($tail)* } $( $stuff )* }
+    };
     { { def __getitem__(&$slf:ident, $key:ident : $key_type:ty) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             $type_slots:tt $as_number:tt
             /* as_sequence */ [ $( $sq_slot_name:ident : $sq_slot_value:expr, )* ]
             /* as_mapping */ [ $( $mp_slot_name:ident : $mp_slot_value:expr, )* ]
-            $setdelitem:tt
+            $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -641,7 +888,7 @@ macro_rules! py_class_impl {
                 $( $mp_slot_name : $mp_slot_value, )*
                 mp_subscript: py_class_binary_slot!($class::__getitem__, $key_type, *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $setdelitem
+            $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -654,14 +901,22 @@ macro_rules! py_class_impl {
         py_error! { "Invalid signature for operator __getitem__" }
     };
 
+    { { $(#[doc=$doc:expr])+ def __gt__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __gt__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __gt__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__gt__ is not supported by py_class! use __richcmp__ instead." }
     };
+
+    { { $(#[doc=$doc:expr])+ def __hash__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __hash__ $($tail)* } $( $stuff )* }
+    };
     { { def __hash__(&$slf:ident) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
-            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -673,7 +928,7 @@ macro_rules! py_class_impl {
                 $( $tp_slot_name : $tp_slot_value, )*
                 tp_hash: py_class_unary_slot!($class::__hash__, $crate::Py_hash_t, $crate::py_class::slots::HashConverter),
             ]
-            $as_number $as_sequence $as_mapping $setdelitem
+            $as_number $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -685,12 +940,16 @@ macro_rules! py_class_impl {
     { { def __hash__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "Invalid signature for operator __hash__" }
     };
+
+    { { $(#[doc=$doc:expr])+ def __iadd__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __iadd__ $($tail)* } $( $stuff )* }
+    };
     { { def __iadd__(&$slf:ident, $other:ident : $other_type:ty) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -703,7 +962,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_add: py_class_binary_slot!($class::__iadd__, $other_type, *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -715,12 +974,16 @@ macro_rules! py_class_impl {
     { { def __iadd__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "Invalid signature for operator __iadd__" }
     };
+
+    { { $(#[doc=$doc:expr])+ def __iand__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __iand__ $($tail)* } $( $stuff )* }
+    };
     { { def __iand__(&$slf:ident, $other:ident : $other_type:ty) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -733,7 +996,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_and: py_class_binary_slot!($class::__iand__, $other_type, *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -746,15 +1009,23 @@ macro_rules! py_class_impl {
         py_error! { "Invalid signature for operator __iand__" }
     };
 
+    { { $(#[doc=$doc:expr])+ def __idiv__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __idiv__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __idiv__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__idiv__ is not supported by py_class! yet." }
     };
+
+    { { $(#[doc=$doc:expr])+ def __ifloordiv__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __ifloordiv__ $($tail)* } $( $stuff )* }
+    };
     { { def __ifloordiv__(&$slf:ident, $other:ident : $other_type:ty) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -767,7 +1038,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_floor_divide: py_class_binary_slot!($class::__ifloordiv__, $other_type, *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -779,12 +1050,16 @@ macro_rules! py_class_impl {
     { { def __ifloordiv__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "Invalid signature for operator __ifloordiv__" }
     };
+
+    { { $(#[doc=$doc:expr])+ def __ilshift__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __ilshift__ $($tail)* } $( $stuff )* }
+    };
     { { def __ilshift__(&$slf:ident, $other:ident : $other_type:ty) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -797,7 +1072,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_lshift: py_class_binary_slot!($class::__ilshift__, $other_type, *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -809,12 +1084,16 @@ macro_rules! py_class_impl {
     { { def __ilshift__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "Invalid signature for operator __ilshift__" }
     };
+
+    { { $(#[doc=$doc:expr])+ def __imatmul__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __imatmul__ $($tail)* } $( $stuff )* }
+    };
     { { def __imatmul__(&$slf:ident, $other:ident : $other_type:ty) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -827,7 +1106,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_matrix_multiply: py_class_binary_slot!($class::__imatmul__, $other_type, *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -839,12 +1118,16 @@ macro_rules! py_class_impl {
     { { def __imatmul__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "Invalid signature for operator __imatmul__" }
     };
+
+    { { $(#[doc=$doc:expr])+ def __imod__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __imod__ $($tail)* } $( $stuff )* }
+    };
     { { def __imod__(&$slf:ident, $other:ident : $other_type:ty) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -857,7 +1140,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_remainder: py_class_binary_slot!($class::__imod__, $other_type, *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -869,12 +1152,16 @@ macro_rules! py_class_impl {
     { { def __imod__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "Invalid signature for operator __imod__" }
     };
+
+    { { $(#[doc=$doc:expr])+ def __imul__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __imul__ $($tail)* } $( $stuff )* }
+    };
     { { def __imul__(&$slf:ident, $other:ident : $other_type:ty) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -887,7 +1174,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_multiply: py_class_binary_slot!($class::__imul__, $other_type, *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -900,27 +1187,94 @@ macro_rules! py_class_impl {
         py_error! { "Invalid signature for operator __imul__" }
     };
 
+    { { $(#[doc=$doc:expr])+ def __index__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __index__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __index__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__index__ is not supported by py_class! yet." }
     };
 
-    { { def __init__ $($tail:tt)* } $( $stuff:tt )* } => {
-        py_error! { "__init__ is not supported by py_class!; use __new__ instead." }
+    { { $(#[doc=$doc:expr])+ def __init__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __init__ $($tail)* } $( $stuff )* }
+    };
+    { {  def __init__ (&$slf:ident) -> $res_type:ty { $( $body:tt )* } $($tail:tt)* }
+        $class:ident $py:ident $info:tt
+        /* slots: */ {
+            /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
+        }
+        { $( $imp:item )* }
+        $members:tt
+    } => { py_class_impl! {
+        { $($tail)* }
+        $class $py $info
+        /* slots: */ {
+            /* type_slots */ [
+                $( $tp_slot_name : $tp_slot_value, )*
+                tp_init: py_class_init_slot!{$class::__init__ []},
+            ]
+            $as_number $as_sequence $as_mapping $setdelitem $as_buffer
+        }
+        /* impl: */ {
+            $($imp)*
+            py_class_impl_item! { $class, $py, __init__(&$slf,) $res_type; { $($body)* } [] }
+        }
+        $members
+    }};
+    { {  def __init__ (&$slf:ident, $($p:tt)+) -> $res_type:ty { $( $body:tt )* } $($tail:tt)* }
+        $class:ident $py:ident $info:tt
+        /* slots: */ {
+            /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
+        }
+        { $( $imp:item )* }
+        $members:tt
+    } => { py_class_impl! {
+        { $($tail)* }
+        $class $py $info
+        /* slots: */ {
+            /* type_slots */ [
+                $( $tp_slot_name : $tp_slot_value, )*
+                tp_init: py_argparse_parse_plist_impl!{py_class_init_slot {$class::__init__} [] ($($p)+,)},
+            ]
+            $as_number $as_sequence $as_mapping $setdelitem $as_buffer
+        }
+        /* impl: */ {
+            $($imp)*
+            py_argparse_parse_plist_impl!{
+                py_class_impl_item { $class, $py, __init__(&$slf,) $res_type; { $($body)* } }
+                [] ($($p)+,)
+            }
+        }
+        $members
+    }};
+
+    { { $(#[doc=$doc:expr])+ def __instancecheck__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __instancecheck__ $($tail)* } $( $stuff )* }
     };
 
     { { def __instancecheck__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__instancecheck__ is not supported by py_class! yet." }
     };
 
+    { { $(#[doc=$doc:expr])+ def __int__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __int__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __int__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__int__ is not supported by py_class! yet." }
     };
+
+    { { $(#[doc=$doc:expr])+ def __invert__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __invert__ $($tail)* } $( $stuff )* }
+    };
     { { def __invert__(&$slf:ident) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -933,7 +1287,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_invert: py_class_unary_slot!($class::__invert__, *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -945,12 +1299,16 @@ macro_rules! py_class_impl {
     { { def __invert__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "Invalid signature for operator __invert__" }
     };
+
+    { { $(#[doc=$doc:expr])+ def __ior__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __ior__ $($tail)* } $( $stuff )* }
+    };
     { { def __ior__(&$slf:ident, $other:ident : $other_type:ty) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -963,7 +1321,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_or: py_class_binary_slot!($class::__ior__, $other_type, *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -976,15 +1334,23 @@ macro_rules! py_class_impl {
         py_error! { "Invalid signature for operator __ior__" }
     };
 
+    { { $(#[doc=$doc:expr])+ def __ipow__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __ipow__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __ipow__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__ipow__ is not supported by py_class! yet." }
     };
+
+    { { $(#[doc=$doc:expr])+ def __irshift__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __irshift__ $($tail)* } $( $stuff )* }
+    };
     { { def __irshift__(&$slf:ident, $other:ident : $other_type:ty) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -997,7 +1363,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_rshift: py_class_binary_slot!($class::__irshift__, $other_type, *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -1009,12 +1375,16 @@ macro_rules! py_class_impl {
     { { def __irshift__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "Invalid signature for operator __irshift__" }
     };
+
+    { { $(#[doc=$doc:expr])+ def __isub__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __isub__ $($tail)* } $( $stuff )* }
+    };
     { { def __isub__(&$slf:ident, $other:ident : $other_type:ty) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -1027,7 +1397,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_subtract: py_class_binary_slot!($class::__isub__, $other_type, *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -1039,11 +1409,15 @@ macro_rules! py_class_impl {
     { { def __isub__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "Invalid signature for operator __isub__" }
     };
+
+    { { $(#[doc=$doc:expr])+ def __iter__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __iter__ $($tail)* } $( $stuff )* }
+    };
     { { def __iter__(&$slf:ident) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
-            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -1055,7 +1429,7 @@ macro_rules! py_class_impl {
                 $( $tp_slot_name : $tp_slot_value, )*
                 tp_iter: py_class_unary_slot!($class::__iter__, *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_number $as_sequence $as_mapping $setdelitem
+            $as_number $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -1067,12 +1441,16 @@ macro_rules! py_class_impl {
     { { def __iter__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "Invalid signature for operator __iter__" }
     };
+
+    { { $(#[doc=$doc:expr])+ def __itruediv__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __itruediv__ $($tail)* } $( $stuff )* }
+    };
     { { def __itruediv__(&$slf:ident, $other:ident : $other_type:ty) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -1085,7 +1463,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_true_divide: py_class_binary_slot!($class::__itruediv__, $other_type, *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -1097,12 +1475,16 @@ macro_rules! py_class_impl {
     { { def __itruediv__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "Invalid signature for operator __itruediv__" }
     };
+
+    { { $(#[doc=$doc:expr])+ def __ixor__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __ixor__ $($tail)* } $( $stuff )* }
+    };
     { { def __ixor__(&$slf:ident, $other:ident : $other_type:ty) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -1115,7 +1497,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_xor: py_class_binary_slot!($class::__ixor__, $other_type, *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -1128,16 +1510,24 @@ macro_rules! py_class_impl {
         py_error! { "Invalid signature for operator __ixor__" }
     };
 
+    { { $(#[doc=$doc:expr])+ def __le__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __le__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __le__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__le__ is not supported by py_class! use __richcmp__ instead." }
     };
+
+    { { $(#[doc=$doc:expr])+ def __len__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __len__ $($tail)* } $( $stuff )* }
+    };
     { { def __len__(&$slf:ident) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             $type_slots:tt $as_number:tt
             /* as_sequence */ [ $( $sq_slot_name:ident : $sq_slot_value:expr, )* ]
             /* as_mapping */ [ $( $mp_slot_name:ident : $mp_slot_value:expr, )* ]
-            $setdelitem:tt
+            $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -1154,7 +1544,7 @@ macro_rules! py_class_impl {
                 $( $mp_slot_name : $mp_slot_value, )*
                 mp_length: Some($crate::_detail::ffi::PySequence_Size),
             ]
-            $setdelitem
+            $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -1167,15 +1557,27 @@ macro_rules! py_class_impl {
         py_error! { "Invalid signature for operator __len__" }
     };
 
+    { { $(#[doc=$doc:expr])+ def __length_hint__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __length_hint__ $($tail)* } $( $stuff )* }
+    };
+
+    { { $(#[doc=$doc:expr])+ def __long__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __long__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __long__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__long__ is not supported by py_class! yet." }
     };
+
+    { { $(#[doc=$doc:expr])+ def __lshift__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __lshift__ $($tail)* } $( $stuff )* }
+    };
     { { def __lshift__($left:ident, $right:ident) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -1188,7 +1590,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_lshift: py_class_binary_numeric_slot!($class::__lshift__),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -1201,23 +1603,43 @@ macro_rules! py_class_impl {
         py_error! { "Invalid signature for binary numeric operator __lshift__" }
     };
 
+    { { $(#[doc=$doc:expr])+ def __lt__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __lt__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __lt__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__lt__ is not supported by py_class! use __richcmp__ instead." }
     };
 
+    { { $(#[doc=$doc:expr])+ def __matmul__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __matmul__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __matmul__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__matmul__ is not supported by py_class! yet." }
     };
 
+    { { $(#[doc=$doc:expr])+ def __missing__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __missing__ $($tail)* } $( $stuff )* }
+    };
+
+    { { $(#[doc=$doc:expr])+ def __mod__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __mod__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __mod__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__mod__ is not supported by py_class! yet." }
     };
+
+    { { $(#[doc=$doc:expr])+ def __mul__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __mul__ $($tail)* } $( $stuff )* }
+    };
     { { def __mul__($left:ident, $right:ident) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -1230,7 +1652,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_multiply: py_class_binary_numeric_slot!($class::__mul__),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -1243,15 +1665,23 @@ macro_rules! py_class_impl {
         py_error! { "Invalid signature for binary numeric operator __mul__" }
     };
 
+    { { $(#[doc=$doc:expr])+ def __ne__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __ne__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __ne__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__ne__ is not supported by py_class! use __richcmp__ instead." }
     };
+
+    { { $(#[doc=$doc:expr])+ def __neg__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __neg__ $($tail)* } $( $stuff )* }
+    };
     { { def __neg__(&$slf:ident) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -1264,7 +1694,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_negative: py_class_unary_slot!($class::__neg__, *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -1276,11 +1706,15 @@ macro_rules! py_class_impl {
     { { def __neg__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "Invalid signature for operator __neg__" }
     };
+
+    { { $(#[doc=$doc:expr])+ def __new__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __new__ $($tail)* } $( $stuff )* }
+    };
     { {  def __new__ ($cls:ident) -> $res_type:ty { $( $body:tt )* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
-            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -1292,7 +1726,7 @@ macro_rules! py_class_impl {
                 $( $tp_slot_name : $tp_slot_value, )*
                 tp_new: py_class_wrap_newfunc!{$class::__new__ []},
             ]
-            $as_number $as_sequence $as_mapping $setdelitem
+            $as_number $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -1304,7 +1738,7 @@ macro_rules! py_class_impl {
         $class:ident $py:ident $info:tt
         /* slots: */ {
             /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
-            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -1316,7 +1750,7 @@ macro_rules! py_class_impl {
                 $( $tp_slot_name : $tp_slot_value, )*
                 tp_new: py_argparse_parse_plist_impl!{py_class_wrap_newfunc {$class::__new__} [] ($($p)+,)},
             ]
-            $as_number $as_sequence $as_mapping $setdelitem
+            $as_number $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -1327,11 +1761,15 @@ macro_rules! py_class_impl {
         }
         $members
     }};
+
+    { { $(#[doc=$doc:expr])+ def __next__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __next__ $($tail)* } $( $stuff )* }
+    };
     { { def __next__(&$slf:ident) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
-            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -1343,7 +1781,7 @@ macro_rules! py_class_impl {
                 $( $tp_slot_name : $tp_slot_value, )*
                 tp_iternext: py_class_unary_slot!($class::__next__, *mut $crate::_detail::ffi::PyObject, $crate::py_class::slots::IterNextResultConverter),
             ]
-            $as_number $as_sequence $as_mapping $setdelitem
+            $as_number $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -1356,15 +1794,23 @@ macro_rules! py_class_impl {
         py_error! { "Invalid signature for operator __next__" }
     };
 
+    { { $(#[doc=$doc:expr])+ def __nonzero__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __nonzero__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __nonzero__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__nonzero__ is not supported by py_class!; use the Python 3 spelling __bool__ instead." }
     };
+
+    { { $(#[doc=$doc:expr])+ def __or__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __or__ $($tail)* } $( $stuff )* }
+    };
     { { def __or__($left:ident, $right:ident) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -1377,7 +1823,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_or: py_class_binary_numeric_slot!($class::__or__),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -1389,12 +1835,16 @@ macro_rules! py_class_impl {
     { { def __or__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "Invalid signature for binary numeric operator __or__" }
     };
+
+    { { $(#[doc=$doc:expr])+ def __pos__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __pos__ $($tail)* } $( $stuff )* }
+    };
     { { def __pos__(&$slf:ident) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -1407,7 +1857,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_positive: py_class_unary_slot!($class::__pos__, *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -1420,30 +1870,54 @@ macro_rules! py_class_impl {
         py_error! { "Invalid signature for operator __pos__" }
     };
 
+    { { $(#[doc=$doc:expr])+ def __pow__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __pow__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __pow__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__pow__ is not supported by py_class! yet." }
     };
 
+    { { $(#[doc=$doc:expr])+ def __radd__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __radd__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __radd__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "Reflected numeric operator __radd__ is not supported by py_class! Use __add__ instead!" }
     };
 
+    { { $(#[doc=$doc:expr])+ def __rand__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __rand__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __rand__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "Reflected numeric operator __rand__ is not supported by py_class! Use __and__ instead!" }
     };
 
+    { { $(#[doc=$doc:expr])+ def __rdiv__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __rdiv__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __rdiv__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "Reflected numeric operator __rdiv__ is not supported by py_class! Use __div__ instead!" }
     };
 
+    { { $(#[doc=$doc:expr])+ def __rdivmod__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __rdivmod__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __rdivmod__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "Reflected numeric operator __rdivmod__ is not supported by py_class! Use __divmod__ instead!" }
     };
+
+    { { $(#[doc=$doc:expr])+ def __repr__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __repr__ $($tail)* } $( $stuff )* }
+    };
     { { def __repr__(&$slf:ident) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
-            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -1455,7 +1929,7 @@ macro_rules! py_class_impl {
                 $( $tp_slot_name : $tp_slot_value, )*
                 tp_repr: py_class_unary_slot!($class::__repr__, *mut $crate::_detail::ffi::PyObject, $crate::_detail::PythonObjectCallbackConverter::<$crate::PyString>(::std::marker::PhantomData)),
             ]
-            $as_number $as_sequence $as_mapping $setdelitem
+            $as_number $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -1468,14 +1942,26 @@ macro_rules! py_class_impl {
         py_error! { "Invalid signature for operator __repr__" }
     };
 
+    { { $(#[doc=$doc:expr])+ def __reversed__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __reversed__ $($tail)* } $( $stuff )* }
+    };
+
+    { { $(#[doc=$doc:expr])+ def __rfloordiv__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __rfloordiv__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __rfloordiv__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "Reflected numeric operator __rfloordiv__ is not supported by py_class! Use __floordiv__ instead!" }
     };
+
+    { { $(#[doc=$doc:expr])+ def __richcmp__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __richcmp__ $($tail)* } $( $stuff )* }
+    };
     { { def __richcmp__(&$slf:ident, $other:ident : $other_type:ty, $op:ident : $op_type:ty) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
-            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -1487,7 +1973,7 @@ macro_rules! py_class_impl {
                 $( $tp_slot_name : $tp_slot_value, )*
                 tp_richcompare: py_class_richcompare_slot!($class::__richcmp__, $other_type, *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_number $as_sequence $as_mapping $setdelitem
+            $as_number $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -1500,43 +1986,79 @@ macro_rules! py_class_impl {
         py_error! { "Invalid signature for operator __richcmp__" }
     };
 
+    { { $(#[doc=$doc:expr])+ def __rlshift__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __rlshift__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __rlshift__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "Reflected numeric operator __rlshift__ is not supported by py_class! Use __lshift__ instead!" }
     };
 
+    { { $(#[doc=$doc:expr])+ def __rmatmul__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __rmatmul__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __rmatmul__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "Reflected numeric operator __rmatmul__ is not supported by py_class! Use __matmul__ instead!" }
     };
 
+    { { $(#[doc=$doc:expr])+ def __rmod__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __rmod__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __rmod__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "Reflected numeric operator __rmod__ is not supported by py_class! Use __mod__ instead!" }
     };
 
+    { { $(#[doc=$doc:expr])+ def __rmul__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __rmul__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __rmul__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "Reflected numeric operator __rmul__ is not supported by py_class! Use __mul__ instead!" }
     };
 
+    { { $(#[doc=$doc:expr])+ def __ror__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __ror__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __ror__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "Reflected numeric operator __ror__ is not supported by py_class! Use __or__ instead!" }
     };
 
+    { { $(#[doc=$doc:expr])+ def __round__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __round__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __round__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__round__ is not supported by py_class! yet." }
     };
 
+    { { $(#[doc=$doc:expr])+ def __rpow__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __rpow__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __rpow__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "Reflected numeric operator __rpow__ is not supported by py_class! Use __pow__ instead!" }
     };
 
+    { { $(#[doc=$doc:expr])+ def __rrshift__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __rrshift__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __rrshift__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "Reflected numeric operator __rrshift__ is not supported by py_class! Use __rshift__ instead!" }
     };
+
+    { { $(#[doc=$doc:expr])+ def __rshift__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __rshift__ $($tail)* } $( $stuff )* }
+    };
     { { def __rshift__($left:ident, $right:ident) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -1549,7 +2071,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_rshift: py_class_binary_numeric_slot!($class::__rshift__),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -1562,25 +2084,49 @@ macro_rules! py_class_impl {
         py_error! { "Invalid signature for binary numeric operator __rshift__" }
     };
 
+    { { $(#[doc=$doc:expr])+ def __rsub__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __rsub__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __rsub__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "Reflected numeric operator __rsub__ is not supported by py_class! Use __sub__ instead!" }
     };
 
+    { { $(#[doc=$doc:expr])+ def __rtruediv__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __rtruediv__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __rtruediv__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "Reflected numeric operator __rtruediv__ is not supported by py_class! Use __truediv__ instead!" }
     };
 
+    { { $(#[doc=$doc:expr])+ def __rxor__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __rxor__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __rxor__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "Reflected numeric operator __rxor__ is not supported by py_class! Use __xor__ instead!" }
     };
 
+    { { $(#[doc=$doc:expr])+ def __set__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __set__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __set__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__set__ is not supported by py_class! yet." }
     };
 
+    { { $(#[doc=$doc:expr])+ def __setattr__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __setattr__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __setattr__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__setattr__ is not supported by py_class! yet." }
     };
+
+    { { $(#[doc=$doc:expr])+ def __setitem__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __setitem__ $($tail)* } $( $stuff )* }
+    };
     { { def __setitem__(&$slf:ident, $key:ident : $key_type:ty, $value:ident : $value_type:ty) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
@@ -1589,6 +2135,7 @@ macro_rules! py_class_impl {
                 sdi_setitem: {},
                 sdi_delitem: $sdi_delitem_slot_value:tt,
             ]
+            $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -1601,6 +2148,7 @@ macro_rules! py_class_impl {
                 sdi_setitem: { py_class_ternary_slot!($class::__setitem__, $key_type, $value_type, $crate::_detail::libc::c_int, $crate::py_class::slots::UnitCallbackConverter) },
                 sdi_delitem: $sdi_delitem_slot_value,
             ]
+            $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -1612,11 +2160,15 @@ macro_rules! py_class_impl {
     { { def __setitem__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "Invalid signature for operator __setitem__" }
     };
+
+    { { $(#[doc=$doc:expr])+ def __str__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __str__ $($tail)* } $( $stuff )* }
+    };
     { { def __str__(&$slf:ident) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
-            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -1628,7 +2180,7 @@ macro_rules! py_class_impl {
                 $( $tp_slot_name : $tp_slot_value, )*
                 tp_str: py_class_unary_slot!($class::__str__, *mut $crate::_detail::ffi::PyObject, $crate::_detail::PythonObjectCallbackConverter::<$crate::PyString>(::std::marker::PhantomData)),
             ]
-            $as_number $as_sequence $as_mapping $setdelitem
+            $as_number $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -1640,12 +2192,16 @@ macro_rules! py_class_impl {
     { { def __str__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "Invalid signature for operator __str__" }
     };
+
+    { { $(#[doc=$doc:expr])+ def __sub__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __sub__ $($tail)* } $( $stuff )* }
+    };
     { { def __sub__($left:ident, $right:ident) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -1658,7 +2214,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_subtract: py_class_binary_numeric_slot!($class::__sub__),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -1671,19 +2227,35 @@ macro_rules! py_class_impl {
         py_error! { "Invalid signature for binary numeric operator __sub__" }
     };
 
+    { { $(#[doc=$doc:expr])+ def __subclasscheck__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __subclasscheck__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __subclasscheck__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__subclasscheck__ is not supported by py_class! yet." }
     };
 
+    { { $(#[doc=$doc:expr])+ def __truediv__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __truediv__ $($tail)* } $( $stuff )* }
+    };
+
     { { def __truediv__ $($tail:tt)* } $( $stuff:tt )* } => {
         py_error! { "__truediv__ is not supported by py_class! yet." }
     };
+
+    { { $(#[doc=$doc:expr])+ def __unicode__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __unicode__ $($tail)* } $( $stuff )* }
+    };
+
+    { { $(#[doc=$doc:expr])+ def __xor__ $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { def __xor__ $($tail)* } $( $stuff )* }
+    };
     { { def __xor__($left:ident, $right:ident) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $as_buffer:tt
         }
         { $( $imp:item )* }
         $members:tt
@@ -1696,7 +2268,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_xor: py_class_binary_numeric_slot!($class::__xor__),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $as_buffer
         }
         /* impl: */ {
             $($imp)*
@@ -1721,7 +2293,7 @@ macro_rules! py_class_impl {
         }
         /* members: */ {
             $( $member_name = $member_expr; )*
-            $name = py_class_instance_method!{$py, $class::$name []};
+            $name = py_class_instance_method!{$py, $class::$name, {} []};
         }
     }};
     { {  def $name:ident (&$slf:ident, $($p:tt)+) -> $res_type:ty { $( $body:tt )* } $($tail:tt)* }
@@ -1740,10 +2312,104 @@ macro_rules! py_class_impl {
         }
         /* members: */ {
             $( $member_name = $member_expr; )*
-            $name = py_argparse_parse_plist_impl!{py_class_instance_method {$py, $class::$name} [] ($($p)+,)};
+            $name = py_argparse_parse_plist_impl!{py_class_instance_method {$py, $class::$name, {}} [] ($($p)+,)};
+        }
+    }};
+    { {  @classmethod def $name:ident ($cls:ident) -> $res_type:ty { $( $body:tt )* } $($tail:tt)* }
+        $class:ident $py:ident $info:tt $slots:tt
+        { $( $imp:item )* }
+        { $( $member_name:ident = $member_expr:expr; )* }
+    } => { py_class_impl! {
+        { $($tail)* }
+        $class $py $info $slots
+        /* impl: */ {
+            $($imp)*
+            py_class_impl_item! { $class, $py,$name($cls: &$crate::PyType,) $res_type; { $($body)* } [] }
+        }
+        /* members: */ {
+            $( $member_name = $member_expr; )*
+            $name = py_class_class_method!{$py, $class::$name, {} []};
+        }
+    }};
+    { {  @classmethod def $name:ident ($cls:ident, $($p:tt)+) -> $res_type:ty { $( $body:tt )* } $($tail:tt)* }
+        $class:ident $py:ident $info:tt $slots:tt
+        { $( $imp:item )* }
+        { $( $member_name:ident = $member_expr:expr; )* }
+    } => { py_class_impl! {
+        { $($tail)* }
+        $class $py $info $slots
+        /* impl: */ {
+            $($imp)*
+            py_argparse_parse_plist_impl!{
+                py_class_impl_item { $class, $py, $name($cls: &$crate::PyType,) $res_type; { $($body)* } }
+                [] ($($p)+,)
+            }
+        }
+        /* members: */ {
+            $( $member_name = $member_expr; )*
+            $name = py_argparse_parse_plist_impl!{py_class_class_method {$py, $class::$name, {}} [] ($($p)+,)};
+        }
+    }};
+    { {  @staticmethod def $name:ident ($($p:tt)*) -> $res_type:ty { $( $body:tt )* } $($tail:tt)* }
+        $class:ident $py:ident $info:tt $slots:tt
+        { $( $imp:item )* }
+        { $( $member_name:ident = $member_expr:expr; )* }
+    } => { py_class_impl! {
+        { $($tail)* }
+        $class $py $info $slots
+        /* impl: */ {
+            $($imp)*
+            py_argparse_parse_plist!{
+                py_class_impl_item { $class, $py, $name() $res_type; { $($body)* } }
+                ($($p)*)
+            }
+        }
+        /* members: */ {
+            $( $member_name = $member_expr; )*
+            $name =
+            py_argparse_parse_plist!{
+                py_class_static_method {$py, $class::$name, {}}
+                ($($p)*)
+            }
+            ;
+        }
+    }};
+    { { $(#[doc=$doc:expr])+ def $name:ident (&$slf:ident) -> $res_type:ty { $( $body:tt )* } $($tail:tt)* }
+        $class:ident $py:ident $info:tt $slots:tt
+        { $( $imp:item )* }
+        { $( $member_name:ident = $member_expr:expr; )* }
+    } => { py_class_impl! {
+        { $($tail)* }
+        $class $py $info $slots
+        /* impl: */ {
+            $($imp)*
+            py_class_impl_item! { $class, $py, $name(&$slf,) $res_type; { $($body)* } [] }
+        }
+        /* members: */ {
+            $( $member_name = $member_expr; )*
+            $name = py_class_instance_method!{$py, $class::$name, { $($doc),+ } []};
+        }
+    }};
+    { { $(#[doc=$doc:expr])+ def $name:ident (&$slf:ident, $($p:tt)+) -> $res_type:ty { $( $body:tt )* } $($tail:tt)* }
+        $class:ident $py:ident $info:tt $slots:tt
+        { $( $imp:item )* }
+        { $( $member_name:ident = $member_expr:expr; )* }
+    } => { py_class_impl! {
+        { $($tail)* }
+        $class $py $info $slots
+        /* impl: */ {
+            $($imp)*
+            py_argparse_parse_plist_impl!{
+                py_class_impl_item { $class, $py, $name(&$slf,) $res_type; { $($body)* } }
+                [] ($($p)+,)
+            }
+        }
+        /* members: */ {
+            $( $member_name = $member_expr; )*
+            $name = py_argparse_parse_plist_impl!{py_class_instance_method {$py, $class::$name, { $($doc),+ }} [] ($($p)+,)};
         }
     }};
-    { { @classmethod def $name:ident ($cls:ident) -> $res_type:ty { $( $body:tt )* } $($tail:tt)* }
+    { { $(#[doc=$doc:expr])+ @classmethod def $name:ident ($cls:ident) -> $res_type:ty { $( $body:tt )* } $($tail:tt)* }
         $class:ident $py:ident $info:tt $slots:tt
         { $( $imp:item )* }
         { $( $member_name:ident = $member_expr:expr; )* }
@@ -1756,10 +2422,10 @@ macro_rules! py_class_impl {
         }
         /* members: */ {
             $( $member_name = $member_expr; )*
-            $name = py_class_class_method!{$py, $class::$name []};
+            $name = py_class_class_method!{$py, $class::$name, { $($doc),+ } []};
         }
     }};
-    { { @classmethod def $name:ident ($cls:ident, $($p:tt)+) -> $res_type:ty { $( $body:tt )* } $($tail:tt)* }
+    { { $(#[doc=$doc:expr])+ @classmethod def $name:ident ($cls:ident, $($p:tt)+) -> $res_type:ty { $( $body:tt )* } $($tail:tt)* }
         $class:ident $py:ident $info:tt $slots:tt
         { $( $imp:item )* }
         { $( $member_name:ident = $member_expr:expr; )* }
@@ -1775,10 +2441,10 @@ macro_rules! py_class_impl {
         }
         /* members: */ {
             $( $member_name = $member_expr; )*
-            $name = py_argparse_parse_plist_impl!{py_class_class_method {$py, $class::$name} [] ($($p)+,)};
+            $name = py_argparse_parse_plist_impl!{py_class_class_method {$py, $class::$name, { $($doc),+ }} [] ($($p)+,)};
         }
     }};
-    { { @staticmethod def $name:ident ($($p:tt)*) -> $res_type:ty { $( $body:tt )* } $($tail:tt)* }
+    { { $(#[doc=$doc:expr])+ @staticmethod def $name:ident ($($p:tt)*) -> $res_type:ty { $( $body:tt )* } $($tail:tt)* }
         $class:ident $py:ident $info:tt $slots:tt
         { $( $imp:item )* }
         { $( $member_name:ident = $member_expr:expr; )* }
@@ -1796,7 +2462,7 @@ macro_rules! py_class_impl {
             $( $member_name = $member_expr; )*
             $name =
             py_argparse_parse_plist!{
-                py_class_static_method {$py, $class::$name}
+                py_class_static_method {$py, $class::$name, { $($doc),+ }}
                 ($($p)*)
             }
             ;
@@ -1814,6 +2480,10 @@ macro_rules! py_class_impl {
         }
     }};
 
+    { { #[doc=$doc:expr] $($tail:tt)* } $( $stuff:tt )* } => {
+        py_class_impl! { { $($tail)* } $( $stuff )* }
+    };
+
 }
 
 #[doc(hidden)]
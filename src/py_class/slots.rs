@@ -18,11 +18,11 @@
 
 use ffi;
 use std::{mem, isize, ptr};
-use std::ffi::CString;
+use std::ffi::{CStr, CString};
 use libc::{c_char, c_int};
 use python::{Python, PythonObject};
 use conversion::ToPyObject;
-use objects::PyObject;
+use objects::{PyObject, PyType};
 use function::CallbackConverter;
 use err::{PyErr, PyResult};
 use py_class::{CompareOp};
@@ -40,6 +40,7 @@ macro_rules! py_class_type_object_static_init {
         $as_sequence:tt
         $as_mapping:tt
         $setdelitem:tt
+        $as_buffer:tt
     }) => (
         $crate::_detail::ffi::PyTypeObject {
             $( $slot_name : $slot_value, )*
@@ -72,7 +73,8 @@ macro_rules! py_class_type_object_flags {
 
 #[cfg(feature="python27-sys")]
 pub const TPFLAGS_DEFAULT : ::libc::c_long = ffi::Py_TPFLAGS_DEFAULT
-                                           | ffi::Py_TPFLAGS_CHECKTYPES;
+                                           | ffi::Py_TPFLAGS_CHECKTYPES
+                                           | ffi::Py_TPFLAGS_HAVE_NEWBUFFER;
 
 #[cfg(feature="python3-sys")]
 pub const TPFLAGS_DEFAULT : ::libc::c_ulong = ffi::Py_TPFLAGS_DEFAULT;
@@ -81,17 +83,18 @@ pub const TPFLAGS_DEFAULT : ::libc::c_ulong = ffi::Py_TPFLAGS_DEFAULT;
 #[doc(hidden)]
 macro_rules! py_class_type_object_dynamic_init {
     // initialize those fields of PyTypeObject that we couldn't initialize statically
-    ($class: ident, $py:ident, $type_object:ident, $module_name: ident,
+    ($class: ident, $py:ident, $type_object:ident, $module_name: ident, $metaclass:tt,
         /* slots: */ {
             $type_slots:tt
             $as_number:tt
             $as_sequence:tt
             $as_mapping:tt
             $setdelitem:tt
+            $as_buffer:tt
         }
     ) => {
+        py_class_init_metaclass!($py, $type_object, $metaclass);
         unsafe {
-            $type_object.init_ob_type(&mut $crate::_detail::ffi::PyType_Type);
             $type_object.tp_name = $crate::py_class::slots::build_tp_name($module_name, _cpython__py_class__slots__stringify!($class));
             $type_object.tp_basicsize = <$class as $crate::py_class::BaseObject>::size()
                                         as $crate::_detail::ffi::Py_ssize_t;
@@ -100,7 +103,48 @@ macro_rules! py_class_type_object_dynamic_init {
         *(unsafe { &mut $type_object.tp_as_sequence }) = py_class_as_sequence!($as_sequence);
         *(unsafe { &mut $type_object.tp_as_number }) = py_class_as_number!($as_number);
         py_class_as_mapping!($type_object, $as_mapping, $setdelitem);
+        *(unsafe { &mut $type_object.tp_as_buffer }) = py_class_as_buffer!($as_buffer);
+    }
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! py_class_init_metaclass {
+    ($py:ident, $type_object:ident, None) => {
+        unsafe {
+            $type_object.init_ob_type(&mut $crate::_detail::ffi::PyType_Type);
+        }
+    };
+    ($py:ident, $type_object:ident, { $metaclass:expr }) => {{
+        // keep $metaclass out of unsafe block; it might contain user code
+        let metaclass: $crate::PyType = $metaclass;
+        let metaclass = $crate::py_class::slots::metaclass_type_ptr($py, metaclass)?;
+        unsafe {
+            $type_object.init_ob_type(metaclass);
+        }
+    }};
+}
+
+/// Checks that `metaclass` can be used as the type of a `py_class!` type object.
+///
+/// The metaclass must be a subclass of `type` that does not add instance fields
+/// (e.g. via `__slots__`), as those would not exist on the static type object.
+///
+/// On success, the reference to the metaclass is leaked: the type object
+/// will refer to it for the remaining lifetime of the interpreter.
+pub fn metaclass_type_ptr(py: Python, metaclass: PyType) -> PyResult<*mut ffi::PyTypeObject> {
+    let type_type = py.get_type::<PyType>();
+    if !metaclass.is_subtype_of(py, &type_type) {
+        return Err(PyErr::new::<exc::TypeError, _>(py, format!(
+            "metaclass {} is not a subclass of type", metaclass.name(py))));
+    }
+    let ptr = metaclass.as_type_ptr();
+    if unsafe { (*ptr).tp_basicsize != (*type_type.as_type_ptr()).tp_basicsize } {
+        return Err(PyErr::new::<exc::TypeError, _>(py, format!(
+            "metaclass {} must not add instance fields", metaclass.name(py))));
     }
+    mem::forget(metaclass);
+    Ok(ptr)
 }
 
 pub fn build_tp_name(module_name: Option<&str>, type_name: &str) -> *mut c_char {
@@ -111,6 +155,47 @@ pub fn build_tp_name(module_name: Option<&str>, type_name: &str) -> *mut c_char
     name.expect("Module name/type name must not contain NUL byte").into_raw()
 }
 
+/// Checks the type object for inconsistent slots before it is passed to `PyType_Ready()`.
+///
+/// Configurations that would produce a broken type result in a `TypeError`;
+/// configurations that are legal but likely unintended emit a `RuntimeWarning`.
+pub fn validate_type_object(py: Python, ty: &ffi::PyTypeObject) -> PyResult<()> {
+    let name = unsafe { CStr::from_ptr(ty.tp_name) }.to_string_lossy();
+    if ty.tp_flags & ffi::Py_TPFLAGS_HAVE_GC != 0 && ty.tp_traverse.is_none() {
+        return Err(PyErr::new::<exc::TypeError, _>(py, format!(
+            "{} requests garbage collection support, but does not provide __traverse__", name)));
+    }
+    if !ty.tp_as_mapping.is_null() {
+        let mapping = unsafe { &*ty.tp_as_mapping };
+        if mapping.mp_ass_subscript.is_some() && mapping.mp_subscript.is_none() {
+            warn_inconsistent_type(py, &format!(
+                "{} defines __setitem__ or __delitem__ but not __getitem__", name))?;
+        }
+    }
+    if ty.tp_init.is_some() && ty.tp_new.is_none() {
+        warn_inconsistent_type(py, &format!(
+            "{} defines __init__ but not __new__, so it cannot be instantiated from Python", name))?;
+    }
+    Ok(())
+}
+
+fn warn_inconsistent_type(py: Python, msg: &str) -> PyResult<()> {
+    let msg = CString::new(msg).unwrap();
+    unsafe {
+        if ffi::PyErr_WarnEx(ffi::PyExc_RuntimeWarning, msg.as_ptr(), 1) < 0 {
+            Err(PyErr::fetch(py))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Used as the `tp_dealloc` slot of `py_class!` types.
+///
+/// CPython only invokes `tp_dealloc` while holding the GIL, so the `Python` token
+/// passed on to `BaseObject::dealloc` (and from there to `__dealloc__` and the
+/// `Drop` impls of the data members) is valid for the whole call.
+/// Panics cannot unwind into CPython and abort the process instead.
 pub unsafe extern "C" fn tp_dealloc_callback<T>(obj: *mut ffi::PyObject)
     where T: super::BaseObject
 {
@@ -121,6 +206,51 @@ pub unsafe extern "C" fn tp_dealloc_callback<T>(obj: *mut ffi::PyObject)
     r
 }
 
+#[macro_export]
+#[doc(hidden)]
+macro_rules! py_class_call_dealloc_hook {
+    ($class:ident, $py:ident, $obj:ident, None) => { true };
+    ($class:ident, $py:ident, $obj:ident, { $hook:expr }) => {
+        $crate::py_class::slots::call_dealloc_hook::<$class>($py, $obj, $hook)
+    };
+}
+
+/// Runs the `__dealloc__` hook of a `py_class!` type on an object whose
+/// reference count has dropped to zero.
+///
+/// The object is kept alive (with a reference count of one) while the hook runs,
+/// so the hook can use `self` and its data members like on any other instance.
+/// Any pending exception is saved and restored around the hook.
+///
+/// The hook is skipped while the interpreter is finalizing, because the
+/// state it might access (modules, builtins, other objects) may already be gone.
+/// The data members are still dropped in that case.
+///
+/// Returns `false` if the hook stored a new reference to the object somewhere;
+/// in that case the object has been resurrected and must not be deallocated.
+pub unsafe fn call_dealloc_hook<T>(py: Python, obj: *mut ffi::PyObject, hook: fn(&T, Python)) -> bool
+    where T: PythonObject
+{
+    debug_assert_eq!(ffi::Py_REFCNT(obj), 0);
+    if ::pythonrun::is_finalizing() {
+        return true;
+    }
+    let mut ptype = ptr::null_mut();
+    let mut pvalue = ptr::null_mut();
+    let mut ptraceback = ptr::null_mut();
+    ffi::PyErr_Fetch(&mut ptype, &mut pvalue, &mut ptraceback);
+
+    (*obj).ob_refcnt = 1;
+    // Never drop `slf`: a panic unwinding out of the hook must not
+    // decrement the refcount back to zero and re-enter tp_dealloc.
+    let slf = mem::ManuallyDrop::new(T::unchecked_downcast_from(PyObject::from_owned_ptr(py, obj)));
+    hook(&slf, py);
+    (*obj).ob_refcnt -= 1;
+
+    ffi::PyErr_Restore(ptype, pvalue, ptraceback);
+    ffi::Py_REFCNT(obj) == 0
+}
+
 #[macro_export(local_inner_macros)]
 #[doc(hidden)]
 macro_rules! py_class_wrap_newfunc {
@@ -150,6 +280,38 @@ macro_rules! py_class_wrap_newfunc {
 }
 
 
+// On Python 3, bf_getbuffer and bf_releasebuffer are the only slots.
+#[cfg(feature="python3-sys")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! py_class_as_buffer {
+    ([]) => (0 as *mut $crate::_detail::ffi::PyBufferProcs);
+    ([$( $slot_name:ident : $slot_value:expr ,)+]) => {{
+        static mut BUFFER_PROCS : $crate::_detail::ffi::PyBufferProcs
+            = $crate::_detail::ffi::PyBufferProcs {
+                $( $slot_name : $slot_value, )*
+            };
+        unsafe { &mut BUFFER_PROCS }
+    }}
+}
+
+// Python 2 additionally has the old-style buffer slots, which are left empty.
+#[cfg(feature="python27-sys")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! py_class_as_buffer {
+    ([]) => (0 as *mut $crate::_detail::ffi::PyBufferProcs);
+    ([$( $slot_name:ident : $slot_value:expr ,)+]) => {{
+        static mut BUFFER_PROCS : $crate::_detail::ffi::PyBufferProcs
+            = $crate::_detail::ffi::PyBufferProcs {
+                $( $slot_name : $slot_value, )*
+                ..
+                $crate::_detail::ffi::PyBufferProcs_INIT
+            };
+        unsafe { &mut BUFFER_PROCS }
+    }}
+}
+
 #[macro_export]
 #[doc(hidden)]
 macro_rules! py_class_as_sequence {
@@ -593,6 +755,34 @@ macro_rules! py_class_call_slot {
     }}
 }
 
+#[macro_export(local_inner_macros)]
+#[doc(hidden)]
+macro_rules! py_class_init_slot {
+    ($class:ident :: $f:ident [ $( { $pname:ident : $ptype:ty = $detail:tt } )* ]) => {{
+        unsafe extern "C" fn wrap_init(
+            slf: *mut $crate::_detail::ffi::PyObject,
+            args: *mut $crate::_detail::ffi::PyObject,
+            kwargs: *mut $crate::_detail::ffi::PyObject)
+        -> $crate::_detail::libc::c_int
+        {
+            const LOCATION: &'static str = _cpython__py_class__slots__concat!(_cpython__py_class__slots__stringify!($class), ".", _cpython__py_class__slots__stringify!($f), "()");
+            $crate::_detail::handle_callback(
+                LOCATION, $crate::py_class::slots::UnitCallbackConverter,
+                |py| {
+                    py_argparse_raw!(py, Some(LOCATION), args, kwargs,
+                        [ $( { $pname : $ptype = $detail } )* ]
+                        {
+                            let slf = $crate::PyObject::from_borrowed_ptr(py, slf).unchecked_cast_into::<$class>();
+                            let ret = slf.$f(py $(, $pname )* );
+                            $crate::PyDrop::release_ref(slf, py);
+                            ret
+                        })
+                })
+        }
+        Some(wrap_init)
+    }}
+}
+
 /// Used as implementation in the `sq_item` slot to forward calls to the `mp_subscript` slot.
 pub unsafe extern "C" fn sq_item(obj: *mut ffi::PyObject, index: ffi::Py_ssize_t) -> *mut ffi::PyObject {
     let arg = ffi::PyLong_FromSsize_t(index);
@@ -619,3 +809,70 @@ macro_rules! _cpython__py_class__slots__stringify {
         stringify! { $($inner)* }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use ffi;
+    use python::Python;
+    use objects::PyDict;
+    use exc;
+    use super::validate_type_object;
+
+    #[test]
+    fn validate_gc_without_traverse() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let mut ty = ffi::PyTypeObject_INIT;
+        ty.tp_name = b"Broken\0".as_ptr() as *const _;
+        ty.tp_flags = ffi::Py_TPFLAGS_HAVE_GC;
+        let mut err = validate_type_object(py, &ty).unwrap_err();
+        assert!(err.matches(py, py.get_type::<exc::TypeError>()));
+        let msg = err.instance(py).to_string();
+        assert!(msg.contains("Broken") && msg.contains("__traverse__"), "{}", msg);
+    }
+
+    #[test]
+    fn validate_warns_on_setitem_without_getitem() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        unsafe extern "C" fn ass_subscript(_: *mut ffi::PyObject, _: *mut ffi::PyObject,
+                                           _: *mut ffi::PyObject) -> ::libc::c_int { 0 }
+        let mut mapping = ffi::PyMappingMethods {
+            mp_length: None, mp_subscript: None, mp_ass_subscript: Some(ass_subscript)
+        };
+        let mut ty = ffi::PyTypeObject_INIT;
+        ty.tp_name = b"WriteOnly\0".as_ptr() as *const _;
+        ty.tp_as_mapping = &mut mapping;
+        let d = PyDict::new(py);
+        py.run("import warnings\nw = warnings.catch_warnings(record=True)\nlog = w.__enter__()\nwarnings.simplefilter('always')",
+            None, Some(&d)).unwrap();
+        let res = validate_type_object(py, &ty);
+        let log = py.eval("[str(x.message) for x in log]", None, Some(&d)).unwrap();
+        py.run("w.__exit__(None, None, None)", None, Some(&d)).unwrap();
+        assert!(res.is_ok());
+        let log: Vec<String> = log.extract(py).unwrap();
+        assert_eq!(1, log.len());
+        assert!(log[0].contains("WriteOnly") && log[0].contains("__getitem__"), "{:?}", log);
+    }
+
+    #[test]
+    fn validate_warns_on_init_without_new() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        unsafe extern "C" fn init(_: *mut ffi::PyObject, _: *mut ffi::PyObject,
+                                  _: *mut ffi::PyObject) -> ::libc::c_int { 0 }
+        let mut ty = ffi::PyTypeObject_INIT;
+        ty.tp_name = b"InitOnly\0".as_ptr() as *const _;
+        ty.tp_init = Some(init);
+        let d = PyDict::new(py);
+        py.run("import warnings\nw = warnings.catch_warnings(record=True)\nlog = w.__enter__()\nwarnings.simplefilter('always')",
+            None, Some(&d)).unwrap();
+        let res = validate_type_object(py, &ty);
+        let log = py.eval("[str(x.message) for x in log]", None, Some(&d)).unwrap();
+        py.run("w.__exit__(None, None, None)", None, Some(&d)).unwrap();
+        assert!(res.is_ok());
+        let log: Vec<String> = log.extract(py).unwrap();
+        assert_eq!(1, log.len());
+        assert!(log[0].contains("InitOnly") && log[0].contains("__new__"), "{:?}", log);
+    }
+}
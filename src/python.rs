@@ -19,11 +19,13 @@
 use std;
 use std::ffi::CString;
 use std::marker::PhantomData;
-use libc::c_int;
+use libc::{c_char, c_int};
 use ffi;
-use objects::{PyObject, PyType, PyBool, PyDict, PyModule};
+use objects::{PyObject, PyType, PyBool, PyDict, PyModule, PyString, exc};
 use err::{self, PyErr, PyResult};
+use conversion::FromPyObject;
 use pythonrun::GILGuard;
+use objectprotocol::ObjectProtocol;
 
 /// Marker type that indicates that the GIL is currently held.
 ///
@@ -197,29 +199,53 @@ impl<'p> Python<'p> {
     }
 
     /// Temporarily releases the GIL, thus allowing other Python threads to run.
+    ///
+    /// This is the equivalent of `Py_BEGIN_ALLOW_THREADS`/`Py_END_ALLOW_THREADS`:
+    /// the GIL is released, `f` is executed, and then the GIL is re-acquired.
+    /// Use this around long-running pure-Rust computations or blocking I/O.
+    /// For code that does not fit into a closure, see `GILGuard::release()`.
     pub fn allow_threads<T, F>(self, f: F) -> T where F : Send + FnOnce() -> T {
         // The `Send` bound on the closure prevents the user from
         // transferring the `Python` token into the closure.
         unsafe {
+            let suspended = ::pythonrun::SuspendGIL::new();
             let save = ffi::PyEval_SaveThread();
             let result = f();
             ffi::PyEval_RestoreThread(save);
+            drop(suspended);
             result
         }
     }
 
     /// Evaluates a Python expression in the given context and returns the result.
     ///
-    /// If `globals` is `None`, it defaults to Python module `__main__`.
+    /// If `globals` is `None`, a fresh namespace containing only `__builtins__` is used.
     /// If `locals` is `None`, it defaults to the value of `globals`.
     pub fn eval(self, code: &str, globals: Option<&PyDict>,
                 locals: Option<&PyDict>) -> PyResult<PyObject> {
         self.run_code(code, ffi::Py_eval_input, globals, locals)
     }
 
+    /// Evaluates a Python expression in the given context and extracts the result.
+    ///
+    /// Returns `Ok(None)` if the expression evaluates to Python `None`,
+    /// otherwise the result is extracted as `T`.
+    ///
+    /// If `globals` is `None`, a fresh namespace containing only `__builtins__` is used.
+    /// If `locals` is `None`, it defaults to the value of `globals`.
+    pub fn eval_opt<T>(self, code: &str, globals: Option<&PyDict>,
+                locals: Option<&PyDict>) -> PyResult<Option<T>>
+        where T: for<'a> FromPyObject<'a>
+    {
+        let obj = self.eval(code, globals, locals)?;
+        let result = obj.extract::<Option<T>>(self);
+        obj.release_ref(self);
+        result
+    }
+
     /// Executes one or more Python statements in the given context.
     ///
-    /// If `globals` is `None`, it defaults to Python module `__main__`.
+    /// If `globals` is `None`, a fresh namespace containing only `__builtins__` is used.
     /// If `locals` is `None`, it defaults to the value of `globals`.
     pub fn run(self, code: &str, globals: Option<&PyDict>,
                 locals: Option<&PyDict>) -> PyResult<()> {
@@ -231,34 +257,29 @@ impl<'p> Python<'p> {
     /// `start` indicates the type of input expected:
     /// one of `Py_single_input`, `Py_file_input`, or `Py_eval_input`.
     ///
-    /// If `globals` is `None`, it defaults to Python module `__main__`.
+    /// If `globals` is `None`, a fresh namespace containing only `__builtins__` is used.
     /// If `locals` is `None`, it defaults to the value of `globals`.
     fn run_code(self, code: &str, start: c_int,
                 globals: Option<&PyDict>, locals: Option<&PyDict>)
                 -> PyResult<PyObject> {
-        let code = CString::new(code).unwrap();
-
-        unsafe {
-            let mptr = ffi::PyImport_AddModule("__main__\0".as_ptr() as *const _);
-
-            if mptr.is_null() {
-                return Err(PyErr::fetch(self));
+        let code = match CString::new(code) {
+            Ok(code) => code,
+            Err(_) => return Err(PyErr::new::<exc::ValueError, _>(self,
+                "source code string cannot contain null bytes"))
+        };
+        let default_globals;
+        let globals = match globals {
+            Some(g) => g,
+            None => {
+                default_globals = globals_with_builtins(self)?;
+                &default_globals
             }
+        };
+        let locals = locals.unwrap_or(globals);
 
-            let mdict = ffi::PyModule_GetDict(mptr);
-
-            let globals = match globals {
-                Some(g) => g.as_ptr(),
-                None => mdict,
-            };
-
-            let locals = match locals {
-                Some(l) => l.as_ptr(),
-                None => globals
-            };
-
+        unsafe {
             let res_ptr = ffi::PyRun_StringFlags(code.as_ptr(),
-                start, globals, locals, 0 as *mut _);
+                start, globals.as_ptr(), locals.as_ptr(), 0 as *mut _);
 
             err::result_from_owned_ptr(self, res_ptr)
         }
@@ -297,10 +318,162 @@ impl<'p> Python<'p> {
         T::type_object(self)
     }
 
+    /// Gets the Python builtin type `bool`.
+    #[inline]
+    pub fn type_bool(self) -> PyType {
+        self.get_type::<PyBool>()
+    }
+
+    /// Gets the Python builtin type `int`.
+    #[inline]
+    pub fn type_int(self) -> PyType {
+        self.get_type::<::objects::PyInt>()
+    }
+
+    /// Gets the Python builtin type `float`.
+    #[inline]
+    pub fn type_float(self) -> PyType {
+        self.get_type::<::objects::PyFloat>()
+    }
+
+    /// Gets the Python builtin type `str`.
+    ///
+    /// On Python 2, this is the byte string type.
+    #[inline]
+    pub fn type_str(self) -> PyType {
+        #[cfg(feature="python27-sys")]
+        fn type_str_impl(py: Python) -> PyType { py.get_type::<::objects::PyBytes>() }
+        #[cfg(feature="python3-sys")]
+        fn type_str_impl(py: Python) -> PyType { py.get_type::<::objects::PyString>() }
+        type_str_impl(self)
+    }
+
+    /// Gets the Python builtin type `list`.
+    #[inline]
+    pub fn type_list(self) -> PyType {
+        self.get_type::<::objects::PyList>()
+    }
+
+    /// Gets the Python builtin type `tuple`.
+    #[inline]
+    pub fn type_tuple(self) -> PyType {
+        self.get_type::<::objects::PyTuple>()
+    }
+
+    /// Gets the Python builtin type `dict`.
+    #[inline]
+    pub fn type_dict(self) -> PyType {
+        self.get_type::<PyDict>()
+    }
+
+    /// Gets the Python builtin type `set`.
+    #[inline]
+    pub fn type_set(self) -> PyType {
+        self.get_type::<::objects::PySet>()
+    }
+
     /// Import the Python module with the specified name.
+    ///
+    /// For a dotted name like `os.path`, this returns the submodule, not the top-level package.
     pub fn import(self, name : &str) -> PyResult<PyModule> {
         PyModule::import(self, name)
     }
+
+    /// Import the Python module with the specified name, together with the names in `fromlist`.
+    ///
+    /// See `PyModule::import_from()` for details.
+    pub fn import_from(self, name: &str, fromlist: &[&str]) -> PyResult<PyModule> {
+        PyModule::import_from(self, name, fromlist)
+    }
+
+    /// Creates an interned Python string.
+    ///
+    /// Interned strings with equal contents are the same object, which makes
+    /// dictionary lookups with them faster. Creating the interned string once
+    /// and passing it to `getattr()` or `call_method()` avoids converting
+    /// the name on each call.
+    ///
+    /// `s` may contain null bytes.
+    pub fn intern(self, s: &str) -> PyString {
+        let data = s.as_ptr() as *const c_char;
+        let len = s.len() as ffi::Py_ssize_t;
+        unsafe {
+            #[cfg(feature="python3-sys")]
+            let mut ptr = ffi::PyUnicode_FromStringAndSize(data, len);
+            #[cfg(feature="python27-sys")]
+            let mut ptr = ffi::PyString_FromStringAndSize(data, len);
+            if !ptr.is_null() {
+                #[cfg(feature="python3-sys")]
+                ffi::PyUnicode_InternInPlace(&mut ptr);
+                #[cfg(feature="python27-sys")]
+                ffi::PyString_InternInPlace(&mut ptr);
+            }
+            err::cast_from_owned_ptr_or_panic(self, ptr)
+        }
+    }
+
+    /// Runs the Python signal handlers for any signals received since the last check.
+    ///
+    /// Returns `Err` if a signal handler raised an exception; for `SIGINT` with the
+    /// default handler this is a `KeyboardInterrupt`. Long-running code that holds the GIL
+    /// should call this periodically and return the error to Python to stay interruptible.
+    ///
+    /// Signal handlers only run on the main thread; on other threads this is a no-op.
+    pub fn check_signals(self) -> PyResult<()> {
+        err::error_on_minusone(self, unsafe { ffi::PyErr_CheckSignals() })
+    }
+
+    /// Runs `f` while `sys.stdout` is redirected into an in-memory buffer.
+    ///
+    /// Returns the result of `f` together with everything that was written to
+    /// `sys.stdout` in the meantime. The original `sys.stdout` is restored
+    /// even if `f` returns an error.
+    pub fn capture_stdout<F, R>(self, f: F) -> PyResult<(R, String)>
+        where F: FnOnce(Python<'p>) -> PyResult<R>
+    {
+        self.capture_stream("stdout", f)
+    }
+
+    /// Runs `f` while `sys.stderr` is redirected into an in-memory buffer.
+    ///
+    /// See `capture_stdout()`.
+    pub fn capture_stderr<F, R>(self, f: F) -> PyResult<(R, String)>
+        where F: FnOnce(Python<'p>) -> PyResult<R>
+    {
+        self.capture_stream("stderr", f)
+    }
+
+    fn capture_stream<F, R>(self, name: &str, f: F) -> PyResult<(R, String)>
+        where F: FnOnce(Python<'p>) -> PyResult<R>
+    {
+        #[cfg(feature="python27-sys")]
+        const STRINGIO_MODULE: &'static str = "StringIO";
+        #[cfg(feature="python3-sys")]
+        const STRINGIO_MODULE: &'static str = "io";
+
+        let sys = self.import("sys")?;
+        let buffer = self.import(STRINGIO_MODULE)?.call(self, "StringIO", ::NoArgs, None)?;
+        let saved = sys.get(self, name)?;
+        sys.add(self, name, &buffer)?;
+        let result = f(self);
+        let restored = sys.add(self, name, saved);
+        // an error from `f` takes priority over one from restoring the stream
+        let result = result?;
+        restored?;
+        let text = buffer.getattr(self, "getvalue")?.call(self, ::NoArgs, None)?.extract(self)?;
+        Ok((result, text))
+    }
+}
+
+/// Creates a new globals dict that contains only `__builtins__`.
+///
+/// Python 2 and Python < 3.10 do not add `__builtins__` to a globals dict that lacks it,
+/// so code run with a plain empty dict as globals cannot use the builtins.
+pub fn globals_with_builtins(py: Python) -> PyResult<PyDict> {
+    let globals = PyDict::new(py);
+    let builtins = unsafe { PyObject::from_borrowed_ptr(py, ffi::PyEval_GetBuiltins()) };
+    globals.set_item(py, "__builtins__", builtins)?;
+    Ok(globals)
 }
 
 impl <'p> std::fmt::Debug for PythonObjectDowncastError<'p> {
@@ -311,7 +484,7 @@ impl <'p> std::fmt::Debug for PythonObjectDowncastError<'p> {
 
 #[cfg(test)]
 mod test {
-    use {Python, PyDict};
+    use {Python, PyDict, PythonObject, ObjectProtocol, ToPyObject};
 
     #[test]
     fn test_eval() {
@@ -333,4 +506,94 @@ mod test {
         let v: i32 = py.eval("min(foo, 2)", None, Some(&d)).unwrap().extract(py).unwrap();
         assert_eq!(v, 2);
     }
+
+    #[test]
+    fn test_run() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        assert_eq!(py.eval("1 + 2", None, None).unwrap().extract::<i32>(py).unwrap(), 3);
+
+        let d = PyDict::new(py);
+        d.set_item(py, "x", 1).unwrap();
+        py.run("for i in range(3):\n    x += i\ny = x * 2", None, Some(&d)).unwrap();
+        assert_eq!(d.get_item(py, "x").unwrap().extract::<i32>(py).unwrap(), 4);
+        assert_eq!(d.get_item(py, "y").unwrap().extract::<i32>(py).unwrap(), 8);
+
+        // Without explicit globals, each call gets a fresh namespace
+        py.run("leaked_name = 1", None, None).unwrap();
+        assert!(py.eval("leaked_name", None, None).is_err());
+        assert!(!py.import("__main__").unwrap().as_object().hasattr(py, "leaked_name").unwrap());
+
+        assert!(py.eval("1\0", None, None).is_err());
+    }
+
+    #[test]
+    fn test_check_signals() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        // no signal is pending
+        assert!(py.check_signals().is_ok());
+        assert!(!::PyErr::occurred(py));
+    }
+
+    #[test]
+    fn test_intern() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let a = py.intern("some_attribute_name");
+        let b = py.intern("some_attribute_name");
+        assert!(a.as_object() == b.as_object());
+        assert_eq!(a.to_string_lossy(py), "some_attribute_name");
+        assert_eq!(py.intern("a\0b").to_string_lossy(py), "a\0b");
+
+        let name = py.intern("upper");
+        let s = "abc".to_py_object(py).into_object();
+        for _ in 0..100 {
+            let upper = s.call_method(py, &name, ::NoArgs, None).unwrap();
+            assert_eq!(upper.extract::<String>(py).unwrap(), "ABC");
+        }
+        assert!(s.getattr(py, &name).is_ok());
+        assert!(s.call_method0(py, &name).is_ok());
+    }
+
+    #[test]
+    fn test_eval_opt() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        assert_eq!(None, py.eval_opt::<i32>("None", None, None).unwrap());
+        assert_eq!(Some(3), py.eval_opt::<i32>("1 + 2", None, None).unwrap());
+        assert!(py.eval_opt::<i32>("'abc'", None, None).is_err());
+    }
+
+    #[test]
+    fn test_builtin_types() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        assert!(py.type_list() == py.eval("type([])", None, None).unwrap().cast_into::<::PyType>(py).unwrap());
+        for &(ref ty, name) in &[(py.type_bool(), "bool"), (py.type_int(), "int"), (py.type_float(), "float"),
+                             (py.type_str(), "str"), (py.type_list(), "list"), (py.type_tuple(), "tuple"),
+                             (py.type_dict(), "dict"), (py.type_set(), "set")] {
+            assert!(ty.as_object() == &py.eval(name, None, None).unwrap(), "type {}", name);
+        }
+    }
+
+    #[test]
+    fn test_capture_stdout() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let sys = py.import("sys").unwrap();
+        let stdout = sys.get(py, "stdout").unwrap();
+        let (value, text) = py.capture_stdout(|py| {
+            py.run("print('hello'); print('world')", None, None)?;
+            py.eval("1 + 2", None, None)?.extract::<i32>(py)
+        }).unwrap();
+        assert_eq!(3, value);
+        assert_eq!("hello\nworld\n", text);
+        assert!(sys.get(py, "stdout").unwrap() == stdout);
+
+        // stdout is restored when the closure fails
+        let err = py.capture_stdout(|py| py.run("raise ValueError", None, None)).unwrap_err();
+        assert!(err.matches(py, py.get_type::<::exc::ValueError>()));
+        assert!(sys.get(py, "stdout").unwrap() == stdout);
+    }
 }
@@ -17,11 +17,24 @@
 // DEALINGS IN THE SOFTWARE.
 
 use std::{sync, rc, marker};
+use std::cell::{Cell, UnsafeCell};
 use ffi;
 use python::Python;
+use err::{PyErr, PyResult};
+use objects::exc;
 
 static START: sync::Once = sync::ONCE_INIT;
 
+thread_local!(
+    /// Number of `SubInterpreter::enter()` scopes active on the current thread.
+    static SUBINTERPRETER_DEPTH: Cell<usize> = Cell::new(0)
+);
+
+thread_local!(
+    /// Number of live `GILGuard`s on the current thread.
+    static GIL_GUARDS: Cell<usize> = Cell::new(0)
+);
+
 /// Prepares the use of Python in a free-threaded context.
 ///
 /// If the Python interpreter is not already initialized, this function
@@ -31,7 +44,12 @@ static START: sync::Once = sync::ONCE_INIT;
 /// the thread that initializes the Python interpreter.
 ///
 /// If both the Python interpreter and Python threading are already initialized,
-/// this function has no effect.
+/// this function has no effect. It is safe to call this function repeatedly
+/// and from multiple threads; only the first call does any work.
+///
+/// After this function returns, the GIL is not held by the calling thread,
+/// so any thread (including threads spawned by Rust) can use
+/// `Python::acquire_gil()` to run Python code.
 ///
 /// # Panic
 /// If the Python interpreter is initialized but Python threading is not,
@@ -79,6 +97,32 @@ pub fn prepare_freethreaded_python() {
     });
 }
 
+/// Returns whether the interpreter is currently shutting down (inside `Py_Finalize`).
+///
+/// During finalization, module globals are torn down in an unspecified order,
+/// so code running from deallocators should avoid calling back into Python.
+pub fn is_finalizing() -> bool {
+    #[cfg(all(feature="python3-sys", Py_3_13))]
+    fn is_finalizing_impl() -> bool {
+        unsafe { ffi::Py_IsFinalizing() != 0 }
+    }
+    #[cfg(all(feature="python3-sys", Py_3_7, not(Py_3_13)))]
+    fn is_finalizing_impl() -> bool {
+        unsafe { ffi::_Py_IsFinalizing() != 0 }
+    }
+    #[cfg(all(feature="python3-sys", not(Py_3_7)))]
+    fn is_finalizing_impl() -> bool {
+        unsafe { !ffi::_Py_Finalizing.is_null() }
+    }
+    // Python 2 has no finalization flag, but Py_Finalize() marks the interpreter
+    // as uninitialized before it tears down any modules.
+    #[cfg(feature="python27-sys")]
+    fn is_finalizing_impl() -> bool {
+        unsafe { ffi::Py_IsInitialized() == 0 }
+    }
+    is_finalizing_impl()
+}
+
 /// RAII type that represents the Global Interpreter Lock acquisition.
 ///
 /// # Example
@@ -90,9 +134,17 @@ pub fn prepare_freethreaded_python() {
 ///     let py = gil_guard.python();
 /// } // GIL is released when gil_guard is dropped
 /// ```
+///
+/// `GILGuard` is reentrant: it is implemented with `PyGILState_Ensure` and
+/// `PyGILState_Release`, so acquiring it on a thread that already holds the GIL
+/// (e.g. from within a Python callback, or through a nested `GILGuard`)
+/// does not deadlock. Each guard only releases the GIL if it was the one to
+/// acquire it, so dropping an inner guard leaves the outer acquisition intact.
+/// Guards must be dropped in the reverse order of their acquisition.
 #[must_use]
 pub struct GILGuard {
-    gstate: ffi::PyGILState_STATE,
+    // None if the GIL was already held by a sub-interpreter scope
+    gstate: Option<ffi::PyGILState_STATE>,
     // hack to opt out of Send on stable rust, which doesn't
     // have negative impls
     no_send: marker::PhantomData<rc::Rc<()>>
@@ -101,7 +153,10 @@ pub struct GILGuard {
 /// The Drop implementation for GILGuard will release the GIL.
 impl Drop for GILGuard {
     fn drop(&mut self) {
-        unsafe { ffi::PyGILState_Release(self.gstate) }
+        GIL_GUARDS.with(|guards| guards.set(guards.get() - 1));
+        if let Some(gstate) = self.gstate {
+            unsafe { ffi::PyGILState_Release(gstate) }
+        }
     }
 }
 
@@ -111,11 +166,19 @@ impl GILGuard {
     /// If the Python runtime is not already initialized, this function will initialize it.
     /// See [prepare_freethreaded_python()](fn.prepare_freethreaded_python.html) for details.
     pub fn acquire() -> GILGuard {
+        if SUBINTERPRETER_DEPTH.with(|depth| depth.get()) > 0 {
+            // Within SubInterpreter::enter() the GIL is held by the sub-interpreter's
+            // thread state; PyGILState_Ensure() would try to switch back to the
+            // main interpreter's thread state and deadlock.
+            GIL_GUARDS.with(|guards| guards.set(guards.get() + 1));
+            return GILGuard { gstate: None, no_send: marker::PhantomData };
+        }
         if !cfg!(feature = "no-auto-initialize") {
             ::pythonrun::prepare_freethreaded_python();
         }
         let gstate = unsafe { ffi::PyGILState_Ensure() }; // acquire GIL
-        GILGuard { gstate: gstate, no_send: marker::PhantomData }
+        GIL_GUARDS.with(|guards| guards.set(guards.get() + 1));
+        GILGuard { gstate: Some(gstate), no_send: marker::PhantomData }
     }
 
     /// Retrieves the marker type that proves that the GIL was acquired.
@@ -123,6 +186,182 @@ impl GILGuard {
     pub fn python<'p>(&'p self) -> Python<'p> {
         unsafe { Python::assume_gil_acquired() }
     }
+
+    /// Temporarily releases the GIL until the returned guard is dropped.
+    ///
+    /// This is the scoped counterpart of `Python::allow_threads()`,
+    /// for code that does not fit into a closure (e.g. because of early returns).
+    ///
+    /// The guard mutably borrows the `GILGuard`, so while it is alive,
+    /// neither a new `Python` token nor one obtained earlier from this `GILGuard`
+    /// can be used:
+    ///
+    /// ```compile_fail
+    /// let mut gil = cpython::Python::acquire_gil();
+    /// let py = gil.python();
+    /// let released = gil.release();
+    /// py.None(); // error: `gil` is mutably borrowed
+    /// ```
+    ///
+    /// Python tokens from other sources (like the `py` parameter of a callback,
+    /// or a `Python` obtained from another `GILGuard`) are not covered by the borrow,
+    /// so `release()` is only allowed on the guard that actually acquired the GIL,
+    /// while it is the only `GILGuard` alive on the current thread.
+    /// Within a callback or a nested guard, use `allow_threads()` instead.
+    ///
+    /// # Panics
+    /// Panics if the GIL was already held when this guard was acquired,
+    /// or if another `GILGuard` is alive on the current thread.
+    pub fn release<'a>(&'a mut self) -> ReleaseGIL<'a> {
+        match self.gstate {
+            Some(ffi::PyGILState_STATE::PyGILState_UNLOCKED) => {}
+            _ => panic!("GILGuard::release() called on a guard that did not acquire the GIL")
+        }
+        assert!(GIL_GUARDS.with(|guards| guards.get()) == 1,
+            "GILGuard::release() called while another GILGuard is alive");
+        let suspended = SuspendGIL::new();
+        ReleaseGIL {
+            tstate: unsafe { ffi::PyEval_SaveThread() },
+            _suspended: suspended,
+            _gil: marker::PhantomData
+        }
+    }
+}
+
+/// Hides the `GILGuard`s and `SubInterpreter::enter()` scopes of the current thread
+/// while the GIL is released, and restores them when dropped.
+///
+/// None of the `Python` tokens obtained before the GIL was released may be used
+/// within that scope, so a `GILGuard` acquired there starts from a clean state
+/// and really acquires the GIL (for the main interpreter).
+#[doc(hidden)]
+pub struct SuspendGIL {
+    guards: usize,
+    depth: usize
+}
+
+impl SuspendGIL {
+    pub fn new() -> SuspendGIL {
+        SuspendGIL {
+            guards: GIL_GUARDS.with(|guards| guards.replace(0)),
+            depth: SUBINTERPRETER_DEPTH.with(|depth| depth.replace(0))
+        }
+    }
+}
+
+impl Drop for SuspendGIL {
+    fn drop(&mut self) {
+        GIL_GUARDS.with(|guards| guards.set(self.guards));
+        SUBINTERPRETER_DEPTH.with(|depth| depth.set(self.depth));
+    }
+}
+
+/// RAII guard that keeps the GIL released; created by `GILGuard::release()`.
+///
+/// The GIL is re-acquired when the guard is dropped.
+#[must_use]
+pub struct ReleaseGIL<'a> {
+    // the raw pointer also makes the guard !Send: it must be dropped on the
+    // thread that released the GIL
+    tstate: *mut ffi::PyThreadState,
+    _suspended: SuspendGIL,
+    _gil: marker::PhantomData<&'a mut GILGuard>
+}
+
+impl <'a> Drop for ReleaseGIL<'a> {
+    fn drop(&mut self) {
+        unsafe { ffi::PyEval_RestoreThread(self.tstate) }
+    }
+}
+
+/// An isolated Python sub-interpreter, created with `Py_NewInterpreter`.
+///
+/// A sub-interpreter has its own `sys.modules`, builtins and `__main__` module,
+/// so code run inside it cannot observe modules imported or patched elsewhere.
+/// Use `enter()` to run code in the sub-interpreter; the interpreter is destroyed
+/// with `Py_EndInterpreter` when the `SubInterpreter` is dropped.
+///
+/// A `SubInterpreter` is bound to the thread that created it.
+///
+/// # Caveats
+/// * Python objects must not be shared between interpreters:
+///   objects created within `enter()` should not escape the closure,
+///   and objects from the main interpreter should not be used inside it.
+/// * Extension modules using single-phase initialization (including most
+///   modules built with `py_module_initializer!`) are not properly isolated,
+///   and some C extensions refuse to be imported into sub-interpreters at all.
+/// * The `PyGILState_*` API does not support sub-interpreters; C code relying on it
+///   may misbehave when called inside `enter()`.
+/// * `Python::acquire_gil()` within `enter()` does not switch interpreters.
+///   If the GIL was released (e.g. with `allow_threads()`), it acquires the GIL
+///   for the main interpreter.
+///
+/// # Example
+/// ```
+/// use cpython::{Python, SubInterpreter};
+///
+/// let gil = Python::acquire_gil();
+/// let py = gil.python();
+/// let sub = SubInterpreter::new(py).unwrap();
+/// let value: i32 = sub.enter(py, |py| {
+///     py.eval("6 * 7", None, None).unwrap().extract(py).unwrap()
+/// });
+/// assert_eq!(value, 42);
+/// ```
+pub struct SubInterpreter {
+    tstate: *mut ffi::PyThreadState
+}
+
+impl SubInterpreter {
+    /// Creates a new sub-interpreter.
+    ///
+    /// The calling thread keeps running in its current interpreter.
+    pub fn new(py: Python) -> PyResult<SubInterpreter> {
+        unsafe {
+            let current = ffi::PyThreadState_Get();
+            let tstate = ffi::Py_NewInterpreter();
+            // On success, Py_NewInterpreter() makes the new thread state current.
+            ffi::PyThreadState_Swap(current);
+            if tstate.is_null() {
+                Err(PyErr::new::<exc::RuntimeError, _>(py, "failed to create sub-interpreter"))
+            } else {
+                Ok(SubInterpreter { tstate })
+            }
+        }
+    }
+
+    /// Runs `f` within the sub-interpreter.
+    ///
+    /// The thread state is switched to the sub-interpreter for the duration of the
+    /// closure, and switched back afterwards (also if the closure panics).
+    pub fn enter<F, R>(&self, _py: Python, f: F) -> R
+        where F: FnOnce(Python) -> R
+    {
+        struct RestoreThreadState(*mut ffi::PyThreadState);
+
+        impl Drop for RestoreThreadState {
+            fn drop(&mut self) {
+                unsafe { ffi::PyThreadState_Swap(self.0); }
+                SUBINTERPRETER_DEPTH.with(|depth| depth.set(depth.get() - 1));
+            }
+        }
+
+        SUBINTERPRETER_DEPTH.with(|depth| depth.set(depth.get() + 1));
+        let _restore = RestoreThreadState(unsafe { ffi::PyThreadState_Swap(self.tstate) });
+        f(unsafe { Python::assume_gil_acquired() })
+    }
+}
+
+/// Dropping a `SubInterpreter` ends the interpreter with `Py_EndInterpreter`.
+impl Drop for SubInterpreter {
+    fn drop(&mut self) {
+        let _gil_guard = GILGuard::acquire();
+        unsafe {
+            let current = ffi::PyThreadState_Swap(self.tstate);
+            ffi::Py_EndInterpreter(self.tstate);
+            ffi::PyThreadState_Swap(current);
+        }
+    }
 }
 
 /// Mutex-like wrapper object for data that is protected by the Python GIL.
@@ -182,3 +421,320 @@ impl <T> GILProtected<T> {
     }
 }
 
+/// A cell that is initialized at most once, protected by the Python GIL.
+///
+/// This is useful for caching values that are expensive to compute
+/// (an imported module, a compiled regular expression, a type object).
+/// With the `nightly` feature, `GILOnceCell::new()` is a `const fn`,
+/// so the cell can be stored in a `static`.
+///
+/// The initialization function may temporarily release the GIL
+/// (e.g. while importing a module), so two threads may both run it.
+/// In that case, the first value stored wins and the other one is dropped.
+///
+/// # Example
+/// ```
+/// use cpython::{Python, PyModule, GILOnceCell};
+///
+/// let json_cell: GILOnceCell<PyModule> = GILOnceCell::new();
+///
+/// let gil = Python::acquire_gil();
+/// let py = gil.python();
+/// let json = json_cell.get_or_init(py, || py.import("json").unwrap());
+/// assert_eq!(json.name(py).unwrap(), "json");
+/// ```
+pub struct GILOnceCell<T> {
+    data: UnsafeCell<Option<T>>
+}
+
+unsafe impl<T: Send> Send for GILOnceCell<T> { }
+
+/// The contents are only accessed while the GIL is held,
+/// and are never modified once initialized.
+unsafe impl<T: Send> Sync for GILOnceCell<T> { }
+
+impl <T> GILOnceCell<T> {
+    /// Creates a new, uninitialized cell.
+    #[inline]
+    #[cfg(feature="nightly")]
+    pub const fn new() -> GILOnceCell<T> {
+        GILOnceCell { data: UnsafeCell::new(None) }
+    }
+
+    /// Creates a new, uninitialized cell.
+    #[inline]
+    #[cfg(not(feature="nightly"))]
+    pub fn new() -> GILOnceCell<T> {
+        GILOnceCell { data: UnsafeCell::new(None) }
+    }
+
+    /// Returns the contents of the cell, or `None` if it is not initialized yet.
+    ///
+    /// Requires a `Python` instance as proof that the GIL is acquired.
+    #[inline]
+    pub fn get<'a>(&'a self, _py: Python<'a>) -> Option<&'a T> {
+        unsafe { (*self.data.get()).as_ref() }
+    }
+
+    /// Returns the contents of the cell, initializing it with `f()` if necessary.
+    ///
+    /// If the cell was initialized while `f` was running (because `f` released the GIL,
+    /// or initialized the cell itself), the value returned by `f` is dropped.
+    pub fn get_or_init<'a, F>(&'a self, py: Python<'a>, f: F) -> &'a T
+        where F: FnOnce() -> T
+    {
+        if let Some(value) = self.get(py) {
+            return value;
+        }
+        let value = f();
+        // Ignore the error: the cell was filled while `f` was running.
+        let _ = self.set(py, value);
+        self.get(py).unwrap()
+    }
+
+    /// Initializes the cell with `value`.
+    ///
+    /// Returns `Err(value)` if the cell is already initialized.
+    pub fn set(&self, py: Python, value: T) -> Result<(), T> {
+        if self.get(py).is_some() {
+            return Err(value);
+        }
+        // Safe because the GIL is held, and references to the contents
+        // only exist once the cell is initialized.
+        unsafe { *self.data.get() = Some(value); }
+        Ok(())
+    }
+
+    /// Consumes the cell, returning the contents.
+    #[inline]
+    pub fn into_inner(self) -> Option<T> {
+        self.data.into_inner()
+    }
+}
+
+impl <T> Default for GILOnceCell<T> {
+    #[inline]
+    fn default() -> GILOnceCell<T> {
+        GILOnceCell::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::thread;
+    use super::{GILGuard, GILOnceCell, SubInterpreter, prepare_freethreaded_python};
+
+    #[cfg(feature="python3-sys")]
+    fn gil_held() -> bool {
+        unsafe { ::ffi::PyGILState_Check() != 0 }
+    }
+
+    #[test]
+    fn test_prepare_freethreaded_python() {
+        prepare_freethreaded_python();
+        prepare_freethreaded_python();
+        let threads: Vec<_> = (0..2i32).map(|i| thread::spawn(move || {
+            let gil = GILGuard::acquire();
+            let py = gil.python();
+            let d = ::objects::PyDict::new(py);
+            d.set_item(py, "i", i).unwrap();
+            py.eval("i * 10", None, Some(&d)).unwrap().extract::<i32>(py).unwrap()
+        })).collect();
+        let results: Vec<i32> = threads.into_iter().map(|t| t.join().unwrap()).collect();
+        assert_eq!(results, vec![0, 10]);
+    }
+
+    #[test]
+    fn test_nested_acquire() {
+        thread::spawn(|| {
+            let outer = GILGuard::acquire();
+            let py = outer.python();
+            assert_eq!(py.eval("1 + 1", None, None).unwrap().extract::<i32>(py).unwrap(), 2);
+            {
+                let inner = GILGuard::acquire();
+                let py = inner.python();
+                assert_eq!(py.eval("2 + 2", None, None).unwrap().extract::<i32>(py).unwrap(), 4);
+            }
+            // releasing the inner guard keeps the GIL held by the outer guard
+            #[cfg(feature="python3-sys")]
+            assert!(gil_held());
+            assert_eq!(py.eval("3 + 3", None, None).unwrap().extract::<i32>(py).unwrap(), 6);
+            drop(outer);
+            #[cfg(feature="python3-sys")]
+            assert!(!gil_held());
+        }).join().unwrap();
+    }
+
+    #[test]
+    fn test_release_gil() {
+        use std::time::Duration;
+
+        fn blocking_work(gil: &mut GILGuard, fail: bool) -> Result<i32, &'static str> {
+            let _released = gil.release();
+            #[cfg(feature="python3-sys")]
+            assert!(!gil_held());
+            // another thread can use Python while the GIL is released
+            let other = thread::spawn(|| {
+                let gil = GILGuard::acquire();
+                let py = gil.python();
+                py.eval("6 * 7", None, None).unwrap().extract::<i32>(py).unwrap()
+            }).join().unwrap();
+            thread::sleep(Duration::from_millis(1));
+            if fail {
+                return Err("early return");
+            }
+            Ok(other)
+        }
+
+        thread::spawn(|| {
+            let mut gil = GILGuard::acquire();
+            assert_eq!(Ok(42), blocking_work(&mut gil, false));
+            #[cfg(feature="python3-sys")]
+            assert!(gil_held());
+            assert_eq!(Err("early return"), blocking_work(&mut gil, true));
+            #[cfg(feature="python3-sys")]
+            assert!(gil_held());
+            let py = gil.python();
+            assert_eq!(py.eval("1 + 1", None, None).unwrap().extract::<i32>(py).unwrap(), 2);
+        }).join().unwrap();
+    }
+
+    #[test]
+    fn test_release_gil_nested() {
+        // the inner guard did not acquire the GIL, and the outer `py` is still usable
+        assert!(thread::spawn(|| {
+            let outer = GILGuard::acquire();
+            let _py = outer.python();
+            let mut inner = GILGuard::acquire();
+            let _released = inner.release();
+        }).join().is_err());
+        // the outer guard acquired the GIL, but the inner `py` is still usable
+        assert!(thread::spawn(|| {
+            let mut outer = GILGuard::acquire();
+            let inner = GILGuard::acquire();
+            let _py = inner.python();
+            let _released = outer.release();
+        }).join().is_err());
+        thread::spawn(|| {
+            let gil = GILGuard::acquire();
+            let py = gil.python();
+            // none of the outer tokens can be used within allow_threads(),
+            // so a guard acquired there may release the GIL again
+            let value = py.allow_threads(|| {
+                let mut inner = GILGuard::acquire();
+                {
+                    let _released = inner.release();
+                    #[cfg(feature="python3-sys")]
+                    assert!(!gil_held());
+                }
+                let py = inner.python();
+                py.eval("6 * 7", None, None).unwrap().extract::<i32>(py).unwrap()
+            });
+            assert_eq!(value, 42);
+            #[cfg(feature="python3-sys")]
+            assert!(gil_held());
+        }).join().unwrap();
+    }
+
+    #[test]
+    fn test_gil_once_cell() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use python::{Python, PythonObject};
+        use objects::PyModule;
+
+        static IMPORTS: AtomicUsize = AtomicUsize::new(0);
+
+        fn json_ptr(py: Python, cell: &GILOnceCell<PyModule>) -> usize {
+            let json = cell.get_or_init(py, || {
+                IMPORTS.fetch_add(1, Ordering::SeqCst);
+                py.import("json").unwrap()
+            });
+            assert_eq!(json.name(py).unwrap(), "json");
+            json.as_object().as_ptr() as usize
+        }
+
+        prepare_freethreaded_python();
+        let json = Arc::new(GILOnceCell::new());
+        let first = json_ptr(GILGuard::acquire().python(), &json);
+        let threads: Vec<_> = (0..4).map(|_| {
+            let json = json.clone();
+            thread::spawn(move || json_ptr(GILGuard::acquire().python(), &json))
+        }).collect();
+        for t in threads {
+            assert_eq!(t.join().unwrap(), first);
+        }
+        assert_eq!(IMPORTS.load(Ordering::SeqCst), 1);
+
+        let gil = GILGuard::acquire();
+        let py = gil.python();
+        let cell = GILOnceCell::new();
+        assert!(cell.get(py).is_none());
+        assert_eq!(cell.set(py, 1), Ok(()));
+        assert_eq!(cell.set(py, 2), Err(2));
+        assert_eq!(*cell.get_or_init(py, || panic!("already initialized")), 1);
+        assert_eq!(cell.into_inner(), Some(1));
+    }
+
+    #[test]
+    fn test_gil_once_cell_race() {
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        prepare_freethreaded_python();
+        let cell = Arc::new(GILOnceCell::new());
+        // The initializer releases the GIL, so several threads may run it;
+        // all of them must observe the first value that was stored.
+        let threads: Vec<_> = (0..4i32).map(|i| {
+            let cell = cell.clone();
+            thread::spawn(move || {
+                let gil = GILGuard::acquire();
+                let py = gil.python();
+                *cell.get_or_init(py, || {
+                    py.allow_threads(|| thread::sleep(Duration::from_millis(10)));
+                    i
+                })
+            })
+        }).collect();
+        let values: Vec<i32> = threads.into_iter().map(|t| t.join().unwrap()).collect();
+        assert!(values.iter().all(|&v| v == values[0]), "{:?}", values);
+    }
+
+    #[test]
+    fn test_sub_interpreter() {
+        let gil = GILGuard::acquire();
+        let py = gil.python();
+        let modules_id = |py: ::python::Python| {
+            py.eval("id(__import__('sys').modules)", None, None).unwrap().extract::<i64>(py).unwrap()
+        };
+        let main_modules = modules_id(py);
+        py.run("import sys; sys.marker = 1", None, None).unwrap();
+
+        let sub = SubInterpreter::new(py).unwrap();
+        let (sub_modules, has_marker) = sub.enter(py, |py| {
+            // dropping objects inside the sub-interpreter must not deadlock
+            let has_marker = py.eval("hasattr(__import__('sys'), 'marker')", None, None)
+                .unwrap().extract::<bool>(py).unwrap();
+            (modules_id(py), has_marker)
+        });
+        assert!(sub_modules != main_modules);
+        assert!(!has_marker);
+        // a guard acquired while the sub-interpreter released the GIL
+        // really acquires it, for the main interpreter
+        let released_modules = sub.enter(py, |py| {
+            py.allow_threads(|| {
+                let gil = GILGuard::acquire();
+                #[cfg(feature="python3-sys")]
+                assert!(gil_held());
+                modules_id(gil.python())
+            })
+        });
+        assert_eq!(released_modules, main_modules);
+        // the sub-interpreter keeps its state between scopes
+        assert_eq!(sub_modules, sub.enter(py, modules_id));
+        // and the main interpreter is active again afterwards
+        assert_eq!(main_modules, modules_id(py));
+        drop(sub);
+        assert_eq!(main_modules, modules_id(py));
+    }
+}
@@ -5,7 +5,7 @@
 use cpython::*;
 use std::{mem, isize, iter};
 use std::cell::{Cell, RefCell};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::sync::atomic::{AtomicBool, Ordering};
 use cpython::_detail::ffi;
 
@@ -140,6 +140,127 @@ fn data_is_dropped() {
     assert!(drop_called2.load(Ordering::Relaxed) == true);
 }
 
+py_class!(class DeallocHook |py| {
+    data obj: PyObject;
+    data member: TestDropCall;
+    data observed: Arc<Mutex<Option<(i32, bool)>>>;
+    def __dealloc__(&self) {
+        let value = self.obj(py).extract::<i32>(py).unwrap();
+        let dropped = self.member(py).drop_called.load(Ordering::Relaxed);
+        *self.observed(py).lock().unwrap() = Some((value, dropped));
+    }
+});
+
+#[test]
+fn dealloc_hook_runs_before_data_is_dropped() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let drop_called = Arc::new(AtomicBool::new(false));
+    let observed = Arc::new(Mutex::new(None));
+    let inst = DeallocHook::create_instance(py,
+        42i32.to_py_object(py).into_object(),
+        TestDropCall { drop_called: drop_called.clone() },
+        observed.clone()).unwrap();
+    drop(inst);
+    assert_eq!(*observed.lock().unwrap(), Some((42, false)));
+    assert!(drop_called.load(Ordering::Relaxed));
+}
+
+static FINALIZE_HOOK_CALLED: AtomicBool = AtomicBool::new(false);
+static FINALIZE_DATA_DROPPED: AtomicBool = AtomicBool::new(false);
+
+struct FinalizeDropCall;
+impl Drop for FinalizeDropCall {
+    fn drop(&mut self) {
+        FINALIZE_DATA_DROPPED.store(true, Ordering::SeqCst);
+    }
+}
+
+py_class!(class FinalizeHook |py| {
+    data obj: PyObject;
+    data member: FinalizeDropCall;
+    def __dealloc__(&self) {
+        // Importing modules during interpreter shutdown is not safe.
+        py.import("json").unwrap();
+        FINALIZE_HOOK_CALLED.store(true, Ordering::SeqCst);
+    }
+});
+
+const FINALIZE_CHILD_ENV: &'static str = "CPYTHON_TEST_FINALIZE_CHILD";
+
+/// Runs in a child process (see `dealloc_during_finalization`), because it shuts down
+/// the interpreter for the remainder of the process.
+#[test]
+fn dealloc_during_finalization_child() {
+    if std::env::var_os(FINALIZE_CHILD_ENV).is_none() {
+        return;
+    }
+    {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let inst = FinalizeHook::create_instance(py,
+            "payload".to_py_object(py).into_object(), FinalizeDropCall).unwrap();
+        let module = PyModule::new(py, "finalize_test_module").unwrap();
+        module.add(py, "instance", inst).unwrap();
+        let modules = py.import("sys").unwrap().get(py, "modules").unwrap();
+        modules.set_item(py, "finalize_test_module", module).unwrap();
+    }
+    unsafe {
+        ffi::PyGILState_Ensure();
+        ffi::Py_Finalize();
+    }
+    assert!(FINALIZE_DATA_DROPPED.load(Ordering::SeqCst));
+    assert!(!FINALIZE_HOOK_CALLED.load(Ordering::SeqCst));
+}
+
+#[test]
+fn dealloc_during_finalization() {
+    let output = std::process::Command::new(std::env::current_exe().unwrap())
+        .args(&["dealloc_during_finalization_child", "--exact", "--test-threads=1"])
+        .env(FINALIZE_CHILD_ENV, "1")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "child failed: {}\n{}", stdout, String::from_utf8_lossy(&output.stderr));
+    assert!(stdout.contains("1 passed"), "{}", stdout);
+}
+
+#[repr(align(64))]
+struct Aligned64 {
+    value: u32,
+    drop_called: Arc<AtomicBool>
+}
+
+impl Drop for Aligned64 {
+    fn drop(&mut self) {
+        self.drop_called.store(true, Ordering::Relaxed);
+    }
+}
+
+py_class!(class OverAlignedData |py| {
+    data flag: u8;
+    data aligned: Aligned64;
+});
+
+#[test]
+fn over_aligned_data() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let drop_called = Arc::new(AtomicBool::new(false));
+    let instances: Vec<_> = (0..8).map(|i| OverAlignedData::create_instance(py, i as u8,
+        Aligned64 { value: i, drop_called: drop_called.clone() }).unwrap()).collect();
+    for (i, inst) in instances.iter().enumerate() {
+        let aligned = inst.aligned(py);
+        assert_eq!(aligned as *const Aligned64 as usize % 64, 0);
+        assert_eq!(aligned.value, i as u32);
+        assert_eq!(*inst.flag(py), i as u8);
+    }
+    drop(instances);
+    assert!(drop_called.load(Ordering::Relaxed));
+}
+
 py_class!(class InstanceMethod |py| {
     data member: i32;
 
@@ -160,6 +281,185 @@ fn instance_method() {
     py.run("assert obj.method() == 42", None, Some(&d)).unwrap();
 }
 
+#[test]
+fn debug_uses_repr() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let obj = InstanceMethod::create_instance(py, 42).unwrap();
+    assert_eq!(format!("{:?}", obj), format!("{:?}", obj.as_object()));
+    assert!(format!("{:?}", obj).starts_with("<InstanceMethod object at 0x"));
+}
+
+py_class!(class DocumentedMethods |py| {
+    /// Returns the answer.
+    def method(&self) -> PyResult<i32> {
+        Ok(42)
+    }
+
+    /// First line.
+    /// Second line.
+    @classmethod
+    def class_method(cls) -> PyResult<i32> {
+        Ok(1)
+    }
+
+    /// Static.
+    @staticmethod
+    def static_method(x: i32) -> PyResult<i32> {
+        Ok(x)
+    }
+
+    def undocumented(&self) -> PyResult<i32> {
+        Ok(0)
+    }
+
+    /// Special methods accept doc comments, too.
+    def __len__(&self) -> PyResult<usize> {
+        Ok(3)
+    }
+});
+
+#[test]
+fn method_docstrings() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let d = PyDict::new(py);
+    d.set_item(py, "C", py.get_type::<DocumentedMethods>()).unwrap();
+    py.run("assert C.method.__doc__ == ' Returns the answer.'", None, Some(&d)).unwrap();
+    py.run("assert C.class_method.__doc__ == ' First line.\\n Second line.'", None, Some(&d)).unwrap();
+    py.run("assert C.static_method.__doc__ == ' Static.'", None, Some(&d)).unwrap();
+    py.run("assert C.undocumented.__doc__ is None", None, Some(&d)).unwrap();
+    py.run("assert C.static_method(5) == 5", None, Some(&d)).unwrap();
+}
+
+py_class!(class SameDataAsInstanceMethod |py| {
+    data member: i32;
+});
+
+#[test]
+fn extract_rust_class() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let obj = InstanceMethod::create_instance(py, 42).unwrap().into_object();
+    let owned = obj.extract::<InstanceMethod>(py).unwrap();
+    assert_eq!(*owned.member(py), 42);
+    assert_eq!(*obj.extract::<&InstanceMethod>(py).unwrap().member(py), 42);
+
+    // each class has its own type object, even if the Rust data layout is identical
+    match obj.extract::<SameDataAsInstanceMethod>(py) {
+        Err(err) => assert!(err.matches(py, py.get_type::<exc::TypeError>())),
+        Ok(_) => panic!("extracted the wrong class")
+    }
+    let other = SameDataAsInstanceMethod::create_instance(py, 1).unwrap().into_object();
+    assert!(other.extract::<InstanceMethod>(py).is_err());
+    assert!(py.None().extract::<&InstanceMethod>(py).is_err());
+}
+
+#[test]
+fn instance_and_subclass_checks() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let obj = InstanceMethod::create_instance(py, 42).unwrap().into_object();
+    let ty = py.get_type::<InstanceMethod>();
+    let int_ty = py.get_type::<PyLong>();
+    assert!(obj.is_instance_of(py, &ty).unwrap());
+    assert!(obj.is_instance_of(py, &py.get_type::<PyObject>()).unwrap());
+    assert!(!obj.is_instance_of(py, &int_ty).unwrap());
+    assert!(obj.is_instance_of(py, &(int_ty.clone_ref(py), ty.clone_ref(py)).to_py_object(py)).unwrap());
+    assert!(!1i32.to_py_object(py).into_object().is_instance_of(py, &ty).unwrap());
+
+    assert!(ty.is_subclass_of(py, &ty).unwrap());
+    assert!(ty.is_subclass_of(py, &py.get_type::<PyObject>()).unwrap());
+    assert!(!ty.is_subclass_of(py, &int_ty).unwrap());
+    // on Python 2, bool is a subclass of int rather than long
+    assert!(py.get_type::<PyBool>().is_subclass_of(py, &py.get_type::<PyInt>()).unwrap());
+    assert!(ty.is_subclass_of(py, &(int_ty.clone_ref(py), ty.clone_ref(py)).to_py_object(py)).unwrap());
+
+    // the second argument must be a type or a tuple of types
+    assert!(obj.is_instance_of(py, &obj).is_err());
+    assert!(ty.is_subclass_of(py, &obj).is_err());
+}
+
+#[test]
+fn type_mro_and_lookup() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let ty = py.get_type::<InstanceMethod>();
+    let mro = ty.mro(py);
+    assert_eq!(mro.len(py), 2);
+    assert!(mro.get_item(py, 0) == *ty.as_object());
+    assert!(mro.get_item(py, 1) == *py.get_type::<PyObject>().as_object());
+
+    assert!(ty.lookup(py, "method").is_some());
+    assert!(ty.lookup(py, "missing").is_none());
+    // inherited from `object`
+    let repr = ty.lookup(py, "__repr__").unwrap();
+    assert!(repr == py.get_type::<PyObject>().lookup(py, "__repr__").unwrap());
+
+    // a method on a base type is found through the subclass
+    let d = PyDict::new(py);
+    py.run("class Base(object):\n    def base_method(self): pass\nclass Derived(Base): pass", None, Some(&d)).unwrap();
+    let derived = d.get_item(py, "Derived").unwrap().cast_into::<PyType>(py).unwrap();
+    assert_eq!(derived.mro(py).len(py), 3);
+    let base_method = derived.lookup(py, "base_method").unwrap();
+    assert!(base_method == d.get_item(py, "Base").unwrap().getattr(py, "__dict__").unwrap().get_item(py, "base_method").unwrap());
+}
+
+#[test]
+fn dir_lists_methods() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let ty = py.get_type::<InstanceMethodWithArgs>();
+    let names = ty.as_object().get_attr_names(py).unwrap();
+    assert!(names.iter().any(|name| name == "method"));
+    assert!(names.iter().any(|name| name == "__repr__"));
+    let mut sorted = names.clone();
+    sorted.sort();
+    assert_eq!(names, sorted);
+
+    let obj = InstanceMethodWithArgs::create_instance(py, 7).unwrap();
+    assert_eq!(obj.as_object().dir(py).unwrap().len(py), names.len());
+    let module = py.import("sys").unwrap();
+    assert!(module.as_object().get_attr_names(py).unwrap().iter().any(|name| name == "path"));
+}
+
+py_class!(class WithMetaclass |py| {
+    metaclass = py.eval("type('Meta', (type,), {'registered': True})", None, None)?.cast_into::<PyType>(py)?;
+
+    def method(&self) -> PyResult<i32> {
+        Ok(42)
+    }
+});
+
+py_class!(class WithInvalidMetaclass |py| {
+    metaclass = py.eval("type('Meta', (type,), {'__slots__': ('extra',)})", None, None)?.cast_into::<PyType>(py)?;
+});
+
+#[test]
+fn metaclass() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let ty = py.get_type::<WithMetaclass>();
+    let meta = ty.as_object().get_type(py);
+    assert_eq!(meta.name(py), "Meta");
+    assert!(meta.is_subtype_of(py, &py.get_type::<PyType>()));
+    assert!(ty.as_object().getattr(py, "registered").unwrap().extract::<bool>(py).unwrap());
+
+    let obj = WithMetaclass::create_instance(py).unwrap();
+    py_assert!(py, obj, "obj.method() == 42");
+    py_assert!(py, obj, "not hasattr(obj, 'registered')");
+
+    let err = <WithInvalidMetaclass as py_class::PythonObjectFromPyClassMacro>::initialize(py, None).unwrap_err();
+    assert!(err.matches(py, py.get_type::<exc::TypeError>()));
+}
+
 py_class!(class InstanceMethodWithArgs |py| {
     data member: i32;
 
@@ -276,6 +576,11 @@ fn static_data() {
     py.run("assert C.VAL1 == 123", None, Some(&d)).unwrap();
     py.run("assert C.VAL2 is None", None, Some(&d)).unwrap();
     assert!(py.run("C.VAL1 = 124", None, Some(&d)).is_err());
+
+    let obj = StaticData::create_instance(py).unwrap();
+    d.set_item(py, "obj", obj).unwrap();
+    py.run("assert obj.VAL1 == 123", None, Some(&d)).unwrap();
+    py.run("assert obj.VAL2 is None", None, Some(&d)).unwrap();
 }
 
 py_class!(class GCIntegration |py| {
@@ -310,6 +615,36 @@ fn gc_integration() {
     assert!(drop_called.load(Ordering::Relaxed));
 }
 
+py_class!(class CachedSlots |py| {
+    data cache: [RefCell<Option<PyObject>>; 2];
+
+    def cached(&self, i: usize) -> PyResult<PyObject> {
+        let slot = &self.cache(py)[i];
+        if let Some(ref obj) = *slot.borrow() {
+            return Ok(obj.clone_ref(py));
+        }
+        let obj = py.eval(&format!("str({}) * 2", i), None, None)?;
+        *slot.borrow_mut() = Some(obj.clone_ref(py));
+        Ok(obj)
+    }
+});
+
+#[test]
+fn data_as_object_slots() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let inst = CachedSlots::create_instance(py, [RefCell::new(None), RefCell::new(None)]).unwrap();
+    let first = inst.cached(py, 1).unwrap();
+    assert_eq!(first.extract::<String>(py).unwrap(), "11");
+    assert!(inst.cached(py, 1).unwrap() == first);
+    assert!(inst.cache(py)[0].borrow().is_none());
+
+    let refcnt = first.get_refcnt(py);
+    drop(inst);
+    assert_eq!(first.get_refcnt(py), refcnt - 1);
+}
+
 py_class!(class Len |py| {
     data l: usize;
 
@@ -466,6 +801,46 @@ fn sequence() {
     py_assert!(py, c, "c['abc'] == 'abc'");
 }
 
+py_class!(class SliceableSequence |py| {
+    data items: Vec<i32>;
+
+    def __len__(&self) -> PyResult<usize> {
+        Ok(self.items(py).len())
+    }
+
+    def __getitem__(&self, key: PyObject) -> PyResult<PyObject> {
+        let items = self.items(py);
+        if let Ok(slice) = key.cast_as::<PySlice>(py) {
+            let (start, stop, step) = slice.indices(py, items.len())?;
+            let mut result = Vec::new();
+            let mut i = start;
+            while (step > 0 && i < stop) || (step < 0 && i > stop) {
+                result.push(items[i as usize]);
+                i += step;
+            }
+            return Ok(result.to_py_object(py).into_object());
+        }
+        let index = key.extract::<usize>(py)?;
+        match items.get(index) {
+            Some(item) => Ok(item.to_py_object(py).into_object()),
+            None => Err(PyErr::new::<exc::IndexError, _>(py, "index out of range"))
+        }
+    }
+});
+
+#[test]
+fn sequence_slicing() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let c = SliceableSequence::create_instance(py, (0..8).collect()).unwrap();
+    py_assert!(py, c, "c[1:10:2] == [1, 3, 5, 7]");
+    py_assert!(py, c, "c[::-3] == [7, 4, 1]");
+    py_assert!(py, c, "c[-2:] == [6, 7]");
+    py_assert!(py, c, "c[3] == 3");
+    py_expect_exception!(py, c, "c[::0]", ValueError);
+}
+
 
 py_class!(class Callable |py| {
     def __call__(&self, arg: i32) -> PyResult<i32> {
@@ -486,6 +861,42 @@ fn callable() {
     py_assert!(py, nc, "not callable(nc)");
 }
 
+py_class!(class Init |py| {
+    data value: Cell<i32>;
+    def __new__(_cls, value: i32) -> PyResult<Init> {
+        // __init__ overwrites this
+        Init::create_instance(py, Cell::new(-value))
+    }
+    def __init__(&self, value: i32) -> PyResult<()> {
+        self.value(py).set(value);
+        Ok(())
+    }
+});
+
+#[test]
+fn init() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let c = Init::create_instance(py, Cell::new(0)).unwrap();
+    py_run!(py, c, "c.__init__(42)");
+    assert_eq!(c.value(py).get(), 42);
+    py_run!(py, c, "c.__init__(value=7)");
+    assert_eq!(c.value(py).get(), 7);
+    py_expect_exception!(py, c, "c.__init__('x')", TypeError);
+    assert_eq!(c.value(py).get(), 7);
+}
+
+#[test]
+fn init_after_new() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    // calling the class runs __new__, then __init__ with the same arguments
+    let obj = py.get_type::<Init>().call(py, (5,), None).unwrap();
+    assert_eq!(obj.cast_into::<Init>(py).unwrap().value(py).get(), 5);
+}
+
 py_class!(class SetItem |py| {
     data key: Cell<i32>;
     data val: Cell<i32>;
@@ -878,3 +1289,73 @@ fn context_manager() {
     assert!(c.exit_called(py).get());
 }
 
+py_class!(class Resource |py| {
+    data lines: RefCell<Vec<String>>;
+    data closed: Cell<bool>;
+
+    def __enter__(&self) -> PyResult<Resource> {
+        Ok(self.clone_ref(py))
+    }
+
+    def write(&self, line: String) -> PyResult<PyObject> {
+        if self.closed(py).get() {
+            return Err(PyErr::new::<exc::ValueError, _>(py, "I/O operation on closed resource"));
+        }
+        self.lines(py).borrow_mut().push(line);
+        Ok(py.None())
+    }
+
+    def __exit__(&self, ty: Option<PyType>, value: PyObject, traceback: PyObject) -> PyResult<bool> {
+        self.closed(py).set(true);
+        if ty.is_none() {
+            assert!(value == py.None() && traceback == py.None());
+        }
+        Ok(false)
+    }
+});
+
+#[test]
+fn context_manager_resource() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let r = Resource::create_instance(py, RefCell::new(Vec::new()), Cell::new(false)).unwrap();
+    py_run!(py, r, "with r as f:\n  assert f is r\n  f.write('a')");
+    assert!(r.closed(py).get());
+    assert_eq!(*r.lines(py).borrow(), vec!["a"]);
+
+    let r = Resource::create_instance(py, RefCell::new(Vec::new()), Cell::new(false)).unwrap();
+    py_expect_exception!(py, r, "with r as f:\n  f.write('b')\n  raise KeyError", KeyError);
+    assert!(r.closed(py).get());
+    py_expect_exception!(py, r, "r.write('c')", ValueError);
+    assert_eq!(*r.lines(py).borrow(), vec!["b"]);
+}
+
+
+py_class!(class FloatBuffer |py| {
+    data values: Vec<f64>;
+
+    def __buffer__(&self) -> PyResult<py_class::buffer::BufferInfo> {
+        Ok(unsafe { py_class::buffer::BufferInfo::from_slice(self.values(py)) })
+    }
+});
+
+#[test]
+#[cfg(feature="python3-sys")] // on Python 2, memoryview.tolist() only supports byte views and bytes() is str()
+fn buffer_export() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let obj = FloatBuffer::create_instance(py, vec![1.0, 2.5, -3.0]).unwrap();
+    py_assert!(py, obj, "memoryview(obj).format == 'd'");
+    py_assert!(py, obj, "memoryview(obj).shape == (3,)");
+    py_assert!(py, obj, "memoryview(obj).readonly");
+    py_assert!(py, obj, "memoryview(obj).tolist() == [1.0, 2.5, -3.0]");
+    py_assert!(py, obj, "len(bytes(obj)) == 24");
+    py_expect_exception!(py, obj, "memoryview(obj)[0] = 1.0", TypeError);
+
+    // the buffer refers to the Vec's storage without copying
+    let buffer = obj.as_object().as_buffer(py).unwrap();
+    assert_eq!(obj.values(py).as_ptr() as *mut _, buffer.buf_ptr());
+    assert_eq!(vec![1.0, 2.5, -3.0], buffer.to_vec::<f64>(py).unwrap());
+}
@@ -1,6 +1,8 @@
 #[macro_use] extern crate cpython;
 
-use cpython::{PyResult, Python, NoArgs, ObjectProtocol, PyDict};
+use cpython::{PyResult, Python, NoArgs, ObjectProtocol, PyDict, PyObject, PyTuple};
+use cpython::exc;
+use std::io;
 use std::sync::atomic;
 use std::sync::atomic::Ordering::Relaxed;
 
@@ -30,6 +32,45 @@ fn no_args() {
     assert_eq!(CALL_COUNT.load(Relaxed), 3);
 }
 
+#[test]
+fn unit_result_returns_none() {
+    fn f(_py: Python) -> PyResult<()> {
+        Ok(())
+    }
+
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let obj = py_fn!(py, f());
+
+    assert!(obj.call(py, NoArgs, None).unwrap().is_none(py));
+    let d = PyDict::new(py);
+    d.set_item(py, "f", obj).unwrap();
+    py.run("assert f() is None", None, Some(&d)).unwrap();
+}
+
+#[test]
+fn io_error_is_raised_as_os_error() {
+    fn f(_py: Python, fail: bool) -> Result<i64, io::Error> {
+        if fail {
+            Err(io::Error::new(io::ErrorKind::NotFound, "no such thing"))
+        } else {
+            Ok(42)
+        }
+    }
+
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let obj = py_fn!(py, f(fail: bool));
+
+    assert_eq!(obj.call(py, (false,), None).unwrap().extract::<i64>(py).unwrap(), 42);
+    let err = obj.call(py, (true,), None).unwrap_err();
+    assert!(err.matches(py, py.get_type::<exc::OSError>()));
+    let d = PyDict::new(py);
+    d.set_item(py, "f", obj).unwrap();
+    py.run("try:\n    f(True)\nexcept OSError as e:\n    assert 'no such thing' in str(e)\nelse:\n    assert False",
+           None, Some(&d)).unwrap();
+}
+
 #[test]
 fn one_arg() {
     fn f(_py: Python, i: usize) -> PyResult<usize> {
@@ -89,3 +130,117 @@ fn flexible_sig() {
 }
 */
 
+
+#[test]
+fn named_and_variadic_args() {
+    fn f(py: Python, a: i32, args: &PyTuple, kwargs: &PyDict) -> PyResult<(i32, usize, usize)> {
+        Ok((a, args.len(py), kwargs.len(py)))
+    }
+
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let obj = py_fn!(py, f(a: i32, *args: &PyTuple, **kwargs: &PyDict));
+
+    assert!(obj.call(py, NoArgs, None).is_err());
+    assert_eq!(obj.call(py, (1,), None).unwrap().extract::<(i32, usize, usize)>(py).unwrap(), (1, 0, 0));
+    assert_eq!(obj.call(py, (1, 2, 3), None).unwrap().extract::<(i32, usize, usize)>(py).unwrap(), (1, 2, 0));
+
+    let dict = PyDict::new(py);
+    dict.set_item(py, "x", 10).unwrap();
+    dict.set_item(py, "y", 20).unwrap();
+    assert_eq!(obj.call(py, (1, 2), Some(&dict)).unwrap().extract::<(i32, usize, usize)>(py).unwrap(), (1, 1, 2));
+    // a missing named parameter is still an error, even if there are extra keywords
+    assert!(obj.call(py, NoArgs, Some(&dict)).is_err());
+    dict.set_item(py, "a", 5).unwrap();
+    assert_eq!(obj.call(py, NoArgs, Some(&dict)).unwrap().extract::<(i32, usize, usize)>(py).unwrap(), (5, 0, 2));
+    assert!(obj.call(py, (1,), Some(&dict)).is_err());
+}
+
+#[test]
+fn default_args() {
+    fn greet(_py: Python, name: &str, greeting: &str) -> PyResult<String> {
+        Ok(format!("{}, {}!", greeting, name))
+    }
+
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let obj = py_fn!(py, greet(name: &str, greeting: &str = "hello"));
+
+    assert_eq!(obj.call(py, ("world",), None).unwrap().extract::<String>(py).unwrap(), "hello, world!");
+    assert_eq!(obj.call(py, ("world", "bye"), None).unwrap().extract::<String>(py).unwrap(), "bye, world!");
+
+    let dict = PyDict::new(py);
+    dict.set_item(py, "greeting", "hi").unwrap();
+    assert_eq!(obj.call(py, ("world",), Some(&dict)).unwrap().extract::<String>(py).unwrap(), "hi, world!");
+
+    let mut err = obj.call(py, NoArgs, Some(&dict)).unwrap_err();
+    assert!(err.matches(py, py.get_type::<exc::TypeError>()));
+    let msg = err.instance(py).str(py).unwrap().to_string_lossy(py).into_owned();
+    assert!(msg.contains("'name'"), "{}", msg);
+}
+
+#[test]
+#[cfg(feature="python3-sys")] // Python 2 has no inspect.signature()
+fn text_signature() {
+    fn f(_py: Python, a: i32, b: i32, args: &PyTuple, kwargs: Option<&PyDict>) -> PyResult<i32> {
+        Ok(a + b + args.len(_py) as i32 + kwargs.map_or(0, |k| k.len(_py) as i32))
+    }
+    fn g(_py: Python) -> PyResult<i32> {
+        Ok(0)
+    }
+
+    if !cpython::_detail::TEXT_SIGNATURES {
+        return; // Python < 3.8
+    }
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let d = PyDict::new(py);
+    d.set_item(py, "f", py_fn!(py, f(a: i32, b: i32 = 1, *args, **kwargs))).unwrap();
+    d.set_item(py, "g", py_fn!(py, g())).unwrap();
+    py.run("import inspect", None, Some(&d)).unwrap();
+    let sig = py.eval("str(inspect.signature(f))", None, Some(&d)).unwrap();
+    assert_eq!(sig.extract::<String>(py).unwrap(), "(a, b=Ellipsis, *args, **kwargs)");
+    let sig = py.eval("str(inspect.signature(g))", None, Some(&d)).unwrap();
+    assert_eq!(sig.extract::<String>(py).unwrap(), "()");
+    py.run("P = inspect.Parameter\n\
+            params = list(inspect.signature(f).parameters.values())\n\
+            assert [p.kind for p in params] == [P.POSITIONAL_OR_KEYWORD, P.POSITIONAL_OR_KEYWORD, \
+                                               P.VAR_POSITIONAL, P.VAR_KEYWORD]\n\
+            assert params[0].default is P.empty\n\
+            assert f.__doc__ is None\n\
+            assert f(1, b=2) == 3", None, Some(&d)).unwrap();
+}
+
+#[test]
+fn allow_threads_overlaps() {
+    use std::{thread, time};
+
+    static ACTIVE: atomic::AtomicUsize = atomic::ATOMIC_USIZE_INIT;
+    static MAX_ACTIVE: atomic::AtomicUsize = atomic::ATOMIC_USIZE_INIT;
+
+    fn sleep(py: Python) -> PyResult<PyObject> {
+        py.allow_threads(|| {
+            let active = ACTIVE.fetch_add(1, Relaxed) + 1;
+            let mut max = MAX_ACTIVE.load(Relaxed);
+            while active > max {
+                match MAX_ACTIVE.compare_exchange(max, active, Relaxed, Relaxed) {
+                    Ok(_) => break,
+                    Err(current) => max = current
+                }
+            }
+            thread::sleep(time::Duration::from_millis(200));
+            ACTIVE.fetch_sub(1, Relaxed);
+        });
+        Ok(py.None())
+    }
+
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let locals = PyDict::new(py);
+    locals.set_item(py, "sleep", py_fn!(py, sleep())).unwrap();
+    py.run("import threading\n\
+            threads = [threading.Thread(target=sleep), threading.Thread(target=sleep)]\n\
+            for t in threads: t.start()\n\
+            for t in threads: t.join()\n", None, Some(&locals)).unwrap();
+    assert_eq!(MAX_ACTIVE.load(Relaxed), 2);
+}
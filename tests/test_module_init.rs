@@ -0,0 +1,69 @@
+#[macro_use] extern crate cpython;
+
+use cpython::{ObjectProtocol, PyBool, PyDict, PyModule, PyObject, PyResult, PyType, Python};
+
+fn init_smoke(py: Python, m: &PyModule) -> PyResult<()> {
+    m.add(py, "__doc__", "Smoke test module")?;
+    m.add(py, "answer", py_fn!(py, answer()))?;
+    Ok(())
+}
+
+fn answer(_py: Python) -> PyResult<i32> {
+    Ok(42)
+}
+
+#[cfg(export_name_concat)]
+py_module_initializer!(smoke, init_smoke);
+
+// Rust < 1.54 can't derive the entry point names
+#[cfg(not(export_name_concat))]
+py_module_initializer!(smoke, initsmoke, PyInit_smoke, init_smoke);
+
+#[cfg(all(feature="python3-sys", export_name_concat))]
+extern "C" {
+    fn PyInit_smoke() -> *mut cpython::_detail::ffi::PyObject;
+}
+
+#[test]
+#[cfg(feature="python3-sys")]
+fn import_initialized_module() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let module = unsafe { PyObject::from_owned_ptr(py, PyInit_smoke()) };
+    let module = module.cast_into::<PyModule>(py).unwrap();
+    assert_eq!(module.name(py).unwrap(), "smoke");
+
+    let sys = py.import("sys").unwrap();
+    let modules = sys.get(py, "modules").unwrap();
+    modules.set_item(py, "smoke", &module).unwrap();
+    let imported = py.import("smoke").unwrap();
+    assert_eq!(imported.call(py, "answer", cpython::NoArgs, None).unwrap().extract::<i32>(py).unwrap(), 42);
+}
+
+#[test]
+fn add_function_and_type() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let m = PyModule::new(py, "add_test").unwrap();
+    m.add(py, "answer", 42).unwrap();
+    m.add_function(py, "double", py_fn!(py, double(x: i32) -> PyResult<i32> {
+        let _ = py;
+        Ok(x * 2)
+    })).unwrap();
+    let d = PyDict::new(py);
+    py.run("class Point(object): pass", None, Some(&d)).unwrap();
+    let ty = d.get_item(py, "Point").unwrap().cast_into::<PyType>(py).unwrap();
+    m.add_type(py, "Point", &ty).unwrap();
+    m.add_type(py, "Bool", &py.get_type::<PyBool>()).unwrap();
+
+    let modules = py.import("sys").unwrap().get(py, "modules").unwrap();
+    modules.set_item(py, "add_test", &m).unwrap();
+    let locals = PyDict::new(py);
+    py.run("import add_test\n\
+            result = (add_test.answer, add_test.double(21), add_test.double.__module__,\
+                      add_test.Point.__module__, add_test.Bool is bool)",
+           None, Some(&locals)).unwrap();
+    let result: (i32, i32, String, String, bool) = locals.get_item(py, "result").unwrap().extract(py).unwrap();
+    assert_eq!(result, (42, 42, "add_test".to_string(), "add_test".to_string(), true));
+    modules.del_item(py, "add_test").unwrap();
+}